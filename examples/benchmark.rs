@@ -0,0 +1,52 @@
+use std::time::{Duration, Instant};
+
+use nexrad_data::volume;
+use pynexrad::bindings::convert::{convert_nexrad_file_with_options, process_volume};
+use pynexrad::pymodel::py_convert_options::PyConvertOptions;
+
+const ITERATIONS: u32 = 10;
+
+fn main() {
+    let file_name = std::env::args()
+        .nth(1)
+        .unwrap_or(String::from("examples/KDMX20240521_224629_V06"));
+
+    println!("Loading file {}", file_name);
+    let bytes = std::fs::read(&file_name).expect("file exists");
+    let file = volume::File::new(bytes);
+    let options = PyConvertOptions::default();
+
+    let volume = process_volume(file.records(), &options);
+    let gates: usize = volume
+        .sweeps
+        .iter()
+        .flat_map(|sweep| [&sweep.reflectivity, &sweep.velocity])
+        .flatten()
+        .map(|data| data.radials * data.gates)
+        .sum();
+    println!(
+        "VCP {}, {} sweeps, {} gates",
+        volume.vcp,
+        volume.sweeps.len(),
+        gates
+    );
+    if volume.vcp != 212 {
+        println!("Warning: results are usually reported for VCP 212 volumes");
+    }
+
+    let mut process_time = Duration::ZERO;
+    let mut convert_time = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        process_volume(file.records(), &options);
+        process_time += start.elapsed();
+
+        let start = Instant::now();
+        convert_nexrad_file_with_options(file.records(), &options);
+        convert_time += start.elapsed();
+    }
+
+    println!("Decode and process: {:?}", process_time / ITERATIONS);
+    println!("Full conversion:    {:?}", convert_time / ITERATIONS);
+}
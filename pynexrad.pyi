@@ -1,15 +1,49 @@
-from typing import List
+from typing import Awaitable, List, Optional, Tuple
+
+
+class PySweepBuffer:
+    """
+    PySweepBuffer is a read-only radials x gates view of the values or mask
+    of a converted sweep. It supports the buffer protocol, so
+    memoryview(buffer) and numpy.asarray(buffer) share the storage of the
+    converted volume instead of copying it.
+    """
+    # (radials, gates) of the buffer
+    shape: Tuple[int, int]
+    # Range in km of the first gate and between gates, which locate the
+    # columns of the buffer. Unlike PySweep.range_first these are not
+    # trimmed to the gates with data.
+    range_first: float
+    range_step: float
 
 
 class PySweep:
     """
     PySweep represents a sweep at a single elevation
     """
+    # Index of the cut in the volume coverage pattern
+    cut_index: int
+    # Waveform type of the cut: CS, CDW, CDWO, B, SPP or Unknown
+    waveform: str
+    # Cut index of the other half of a split cut at the same elevation
+    paired_cut: Optional[int]
+    # True for supplemental low level (SAILS/MESO-SAILS) repeat scans
+    is_sails: bool
+
     elevation: float
 
     az_first: float
     az_step: float
     az_count: int
+    # Number of radials in a complete sweep
+    nominal_az_count: int
+    # Fraction of the nominal radials which are present
+    completeness: float
+    # Actual antenna azimuth and elevation of each radial, in radians
+    azimuths: List[float]
+    elevations: List[float]
+    # Collection time of each radial, in milliseconds since epoch
+    times: List[int]
 
     range_first: float
     range_step: float
@@ -18,16 +52,1137 @@ class PySweep:
     start_time: int
     end_time: int
 
-    data: bytes
+    # Quantized value of each gate, 0 for gates without a value
+    data: List[int]
+    # Status of each gate in data: 0 valid, 1 no data, 2 range folded
+    mask: List[int]
+    # Unquantized float32 values of every gate of the full sweep, where
+    # masked gates hold stale values, and the bool mask which is True for
+    # gates without a value. Both are views, not copies.
+    values: PySweepBuffer
+    masked: PySweepBuffer
+
+    def render(self, options: PyRenderOptions) -> PyImage:
+        """
+        render draws this sweep, such as a SAILS repeat which rendering by
+        elevation would not pick, with the size, bounds and colormap of the
+        options. Their product and elevation are not used. Raises ValueError
+        when the radar site is unknown.
+        """
 
 
 class PyLevel2File:
     """
     PyLevel2File contains an entire volume scan
     """
+    # Volume coverage pattern number, e.g. 212
+    vcp: int
 
     reflectivity: List[PySweep]
     velocity: List[PySweep]
+    correlation_coefficient: List[PySweep]
+    differential_reflectivity: List[PySweep]
+    differential_phase: List[PySweep]
+    # Specific differential phase in degrees per km and the unfolded,
+    # smoothed differential phase it was estimated from, only present when
+    # KDP estimation is enabled
+    specific_differential_phase: List[PySweep]
+    filtered_differential_phase: List[PySweep]
+    # Fraction of the clutter tests which flagged each reflectivity gate,
+    # only present when the clutter filter is enabled
+    clutter: List[PySweep]
+    # Hydrometeor class of each gate, only present when classification is
+    # enabled. data holds the class code + 1, with 0 for gates without echo:
+    # 0 unknown, 1 rain, 2 heavy rain, 3 hail, 4 graupel, 5 dry snow,
+    # 6 wet snow, 7 ice crystals, 8 biological, 9 clutter
+    hydrometeor_class: List[PySweep]
+    # Rain rate in mm/h of the lowest tilt and the same rates on a grid,
+    # only present when QPE is enabled
+    rain_rate: Optional[PySweep]
+    rain_rate_grid: Optional[PyRainRateGrid]
+    # Azimuthal shear and divergence in s⁻¹ of the velocity sweeps and their
+    # gridded layer maxima, only present when azimuthal shear is enabled
+    azimuthal_shear: List[PySweep]
+    divergence: List[PySweep]
+    shear_layers: Optional[PyShearLayers]
+    # Storm-relative velocity of the velocity sweeps and the storm motion
+    # (u, v) in m/s it removes, only present when it is enabled
+    storm_relative_velocity: List[PySweep]
+    storm_motion: Optional[Tuple[float, float]]
+    # Melting layer detected from the higher tilts, only present when
+    # detection is enabled and a layer was found
+    melting_layer: Optional[PyMeltingLayer]
+    # Quasi-vertical profiles requested in the options, skipping products
+    # which the volume does not have
+    qvps: List[PyQvp]
+    # Storm cells of the volume, only present when identification is enabled
+    storm_cells: Optional[PyStormCells]
+    # Mesocyclones and tornado vortex signatures, only present when
+    # rotation detection is enabled
+    rotations: List[PyRotation]
+    # Gridded hail products, only present when hail detection is enabled
+    hail_grid: Optional[PyHailGrid]
+    # Images requested in the options, skipping products which the volume
+    # does not have
+    images: List[PyImage]
+    # GeoTIFF rasters requested in the options
+    rasters: List[PyRaster]
+    # GeoJSON contours requested in the options, skipping products which the
+    # volume does not have
+    contours: List[PyContours]
+
+    def latest_low_level(self, product: str) -> Optional[PySweep]:
+        """
+        latest_low_level returns the most recent sweep at the lowest
+        elevation for "reflectivity" or "velocity". When SAILS is active
+        this is the last supplemental low level scan of the volume.
+        """
+
+
+class PyMeltingLayer:
+    """
+    PyMeltingLayer is a melting layer detected from the correlation
+    coefficient dip and the differential reflectivity and reflectivity
+    peaks of quasi-vertical profiles
+    """
+
+    # Heights of the bottom and top of the layer above mean sea level, in km
+    bottom: float
+    top: float
+    # How clear the signature of the layer is, from 0 to 1
+    confidence: float
+
+
+class PyQvpOptions:
+    """
+    PyQvpOptions selects a quasi-vertical profile to produce from a volume
+    """
+
+    # Product to profile, named like the PyLevel2File fields such as
+    # "reflectivity" or "differential_reflectivity"
+    product: str
+    # Elevation in degrees. The closest scanned elevation is used, and the
+    # latest scan of it when it was scanned more than once.
+    elevation: float
+    # Azimuthal statistic of each range: "mean" or "median"
+    statistic: str
+    # Height of each bin and of the top of the profile in km. Raises
+    # ValueError unless both are positive, with at most 10000 bins.
+    bin_size: float
+    max_height: float
+    # Fraction of the radials which must have data for a range to be used
+    min_fraction: float
+
+    def __init__(
+        self,
+        product: str = "reflectivity",
+        elevation: float = 10.0,
+        statistic: str = "median",
+        bin_size: float = 0.05,
+        max_height: float = 8.0,
+        min_fraction: float = 0.3
+    ) -> None: ...
+
+
+class PyQvp:
+    """
+    PyQvp is a quasi-vertical profile of a product, made by combining the
+    gates of each range of a sweep over azimuth and binning them by height
+    """
+
+    product: str
+    # Elevation of the profiled sweep in radians
+    elevation: float
+    # Height of the center of each bin above mean sea level, in km
+    heights: List[float]
+    # Value of each bin, with NaN where there is no data
+    values: List[float]
+    # Start time of the profiled sweep
+    time: int
+
+
+class PyTimeHeight:
+    """
+    PyTimeHeight is a sequence of quasi-vertical profiles stacked in time
+    """
+
+    # Time of each profile, in increasing order
+    times: List[int]
+    # Height of the center of each bin above mean sea level, in km
+    heights: List[float]
+    # Row-major with one row of heights per time, NaN where missing
+    values: List[float]
+
+
+class PyStormCellOptions:
+    """
+    PyStormCellOptions configures storm cell identification. Each tilt is
+    segmented into components of reflectivity above the thresholds, keeping
+    the highest threshold components which are large enough, and the
+    components of consecutive tilts are stacked into cells.
+    """
+
+    # Reflectivity thresholds in dBZ used to segment each tilt
+    thresholds: List[float]
+    # Smallest area of a component on a tilt in km²
+    min_area: float
+    # Largest horizontal distance in km between the components of a cell
+    # on consecutive tilts
+    max_association_distance: float
+    # Number of tilts a cell must span
+    min_components: int
+
+    def __init__(
+        self,
+        thresholds: List[float] = [30.0, 35.0, 40.0, 45.0, 50.0, 55.0, 60.0],
+        min_area: float = 10.0,
+        max_association_distance: float = 5.0,
+        min_components: int = 2
+    ) -> None: ...
+
+
+class PyStormCell:
+    """
+    PyStormCell is a storm cell made of reflectivity components on
+    consecutive tilts
+    """
+
+    # Mass weighted centroid in km east and north of the radar, and in km
+    # above mean sea level
+    x: float
+    y: float
+    height: float
+    # Heights of the lowest and highest components in km
+    base: float
+    top: float
+    max_reflectivity: float
+    max_reflectivity_height: float
+    # Cell based vertically integrated liquid in kg/m²
+    vil: float
+    # Number of tilts with a component of the cell
+    components: int
+    # Hail products of the cell, only present when hail detection is enabled
+    hail: Optional[PyHail]
+
+
+class PyStormCells:
+    """
+    PyStormCells holds the storm cells identified in a volume
+    """
+
+    # Start time of the volume
+    time: int
+    cells: List[PyStormCell]
+
+
+class PyTrackedCell:
+    """
+    PyTrackedCell is a storm cell matched to the cells of earlier volumes
+    """
+
+    # Identifies the cell across volumes
+    id: int
+    cell: PyStormCell
+    # Motion in m/s towards the east and north, once the cell has been
+    # matched across two volumes
+    motion: Optional[Tuple[float, float]]
+    # Forecast positions in km east and north of the radar for each of the
+    # tracker's forecast intervals, empty when the motion is unknown
+    forecast: List[Tuple[float, float]]
+    # Positions of the cell in the previous volumes, oldest first
+    track: List[Tuple[float, float]]
+
+
+class PyStormTracker:
+    """
+    PyStormTracker tracks storm cells across consecutive volumes. Cells of
+    the previous volume are moved along their motion and matched to the
+    nearest new cells within max_speed m/s of travel. Volumes more than
+    max_gap seconds apart start new tracks.
+    """
+
+    def __init__(
+        self,
+        max_speed: float = 30.0,
+        max_gap: int = 1200,
+        forecast_minutes: List[int] = [15, 30, 45, 60]
+    ) -> None: ...
+
+    # Mean motion (u, v) in m/s of the latest cells with a known motion,
+    # for use as the storm motion of storm-relative velocity
+    mean_motion: Optional[Tuple[float, float]]
+
+    def update(self, storm_cells: PyStormCells) -> List[PyTrackedCell]:
+        """
+        update matches the storm cells of a volume to the cells of the
+        previous volume, which must be earlier
+        """
+
+
+class PyRotationOptions:
+    """
+    PyRotationOptions configures mesocyclone and tornado vortex signature
+    detection. Each tilt of dealiased velocity is scanned along azimuth for
+    runs of cyclonically increasing velocity, which are grouped into
+    features and associated across consecutive tilts.
+    """
+
+    # Smallest velocity difference in m/s and azimuthal shear in s⁻¹
+    # across a run of increasing velocity
+    min_delta_v: float
+    min_shear: float
+    # Largest distance in km across a run of increasing velocity
+    max_diameter: float
+    # Range in km beyond which rotation is not searched
+    max_range: float
+    # Largest horizontal distance in km between the features of a rotation
+    # on consecutive tilts
+    max_association_distance: float
+    # Number of tilts a rotation must span
+    min_features: int
+    # Gate to gate velocity difference in m/s and depth in km needed for a
+    # tornado vortex signature
+    tvs_delta_v: float
+    tvs_min_depth: float
+
+    def __init__(
+        self,
+        min_delta_v: float = 10.0,
+        min_shear: float = 0.002,
+        max_diameter: float = 10.0,
+        max_range: float = 200.0,
+        max_association_distance: float = 5.0,
+        min_features: int = 2,
+        tvs_delta_v: float = 25.0,
+        tvs_min_depth: float = 1.5
+    ) -> None: ...
+
+
+class PyRotation:
+    """
+    PyRotation is a rotation detected on consecutive tilts of dealiased
+    velocity
+    """
+
+    # Center on the lowest tilt in km east and north of the radar
+    x: float
+    y: float
+    # Heights of the lowest and highest tilts in km above mean sea level
+    base: float
+    top: float
+    depth: float
+    # Half the largest velocity difference across the rotation in m/s,
+    # over all tilts and on the lowest tilt
+    rotational_velocity: float
+    low_level_rotational_velocity: float
+    # Distance between the velocity peaks where the rotation is strongest,
+    # in km
+    diameter: float
+    # Largest velocity difference between adjacent radials in m/s
+    gate_to_gate_delta_v: float
+    # Rotational velocity in steps of 5 m/s, where 5 and above are
+    # mesocyclone strength
+    strength_rank: int
+    # True for a tornado vortex signature: a strong, deep gate to gate
+    # shear at the lowest tilt
+    is_tvs: bool
+    # Number of tilts with a feature of the rotation
+    features: int
+
+
+class PyAzimuthalShearOptions:
+    """
+    PyAzimuthalShearOptions configures azimuthal shear and divergence
+    estimation by linear least squares derivatives of the dealiased
+    velocity, over a kernel sized in km rather than gates
+    """
+
+    # Width of the kernel across and along the beam, in km
+    azimuthal_width: float
+    radial_width: float
+    # Bottom and top of the low and mid level layers in km above the radar
+    low_layer: Tuple[float, float]
+    mid_layer: Tuple[float, float]
+    # Number of cells along each side of the layer grids and their width
+    # in km
+    grid_size: int
+    grid_spacing: float
+
+    def __init__(
+        self,
+        azimuthal_width: float = 2.5,
+        radial_width: float = 0.75,
+        low_layer: Tuple[float, float] = (0.0, 2.0),
+        mid_layer: Tuple[float, float] = (3.0, 6.0),
+        grid_size: int = 460,
+        grid_spacing: float = 1.0
+    ) -> None: ...
+
+
+class PyShearLayers:
+    """
+    PyShearLayers holds the largest azimuthal shear and divergence within
+    the low and mid level layers on a square grid centered on the radar
+    """
+
+    size: int
+    # Width of each cell in km
+    spacing: float
+    # Bottom and top of each layer in km above the radar
+    low_layer: Tuple[float, float]
+    mid_layer: Tuple[float, float]
+    # Values in s⁻¹, row-major from the north west corner, with NaN where
+    # there is no data
+    low_level_shear: List[float]
+    mid_level_shear: List[float]
+    low_level_divergence: List[float]
+    mid_level_divergence: List[float]
+
+    start_time: int
+    end_time: int
+
+    def contours(self, options: PyContourOptions, layer: str = "low_level_shear") -> PyContours:
+        """
+        contours encloses the cells of a layer, named like its field,
+        passing the thresholds of the options, as in
+        PyRainRateGrid.contours.
+        """
+
+    def geotiff(self, nodata: float = -9999.0) -> bytes:
+        """
+        geotiff returns the layers encoded as the bands of a GeoTIFF, named
+        like their fields, as in PyRainRateGrid.geotiff
+        """
+
+    def save(self, path: str, nodata: float = -9999.0) -> None:
+        """
+        save writes the layers as a GeoTIFF to the path
+        """
+
+
+class PyStormRelativeOptions:
+    """
+    PyStormRelativeOptions gives the storm motion removed from the
+    dealiased velocity. When neither direction and speed nor motion are
+    given, the mean wind from the radar to 6 km is estimated from the
+    velocity and used instead.
+    """
+
+    # Direction in degrees the storm moves from and its speed in m/s
+    direction: Optional[float]
+    speed: Optional[float]
+    # Storm motion (u, v) in m/s towards the east and north, such as
+    # PyStormTracker.mean_motion
+    motion: Optional[Tuple[float, float]]
+
+    def __init__(
+        self,
+        direction: Optional[float] = None,
+        speed: Optional[float] = None,
+        motion: Optional[Tuple[float, float]] = None
+    ) -> None: ...
+
+
+class PyHailOptions:
+    """
+    PyHailOptions configures hail detection, which follows the WSR-88D hail
+    detection algorithm. The severe hail index integrates reflectivity
+    above 40 dBZ from the freezing level up, and the probability of hail
+    follows from the height of the 45 dBZ echo above the freezing level.
+    """
+
+    # Heights of the 0°C and -20°C levels in km above mean sea level, such
+    # as from a nearby sounding or model analysis. The -20°C level must be
+    # above the freezing level.
+    freezing_level: float
+    minus_20_level: float
+    # Number of cells along each side of the hail grid and their width in km.
+    # Raises ValueError unless grid_size is between 1 and 4000 and
+    # grid_spacing is positive.
+    grid_size: int
+    grid_spacing: float
+
+    def __init__(
+        self,
+        freezing_level: float,
+        minus_20_level: float,
+        grid_size: int = 460,
+        grid_spacing: float = 1.0
+    ) -> None: ...
+
+
+class PyHail:
+    """
+    PyHail holds the hail products of a storm cell
+    """
+
+    # Severe hail index in J/m/s
+    shi: float
+    # Probability of hail and of severe hail, in percent
+    poh: float
+    posh: float
+    # Maximum expected hail size in mm
+    mesh: float
+
+
+class PyHailGrid:
+    """
+    PyHailGrid holds the hail products of a volume on a square grid
+    centered on the radar, computed from the column of tilts above each cell
+    """
+
+    size: int
+    # Width of each cell in km
+    spacing: float
+    # Values of each cell, row-major from the north west corner, with NaN
+    # where there is no data. Units are as in PyHail.
+    shi: List[float]
+    poh: List[float]
+    posh: List[float]
+    mesh: List[float]
+
+    start_time: int
+    end_time: int
+
+    def contours(self, options: PyContourOptions, product: str = "mesh") -> PyContours:
+        """
+        contours encloses the cells of "shi", "poh", "posh" or "mesh"
+        passing the thresholds of the options, as in
+        PyRainRateGrid.contours.
+        """
+
+    def geotiff(self, nodata: float = -9999.0) -> bytes:
+        """
+        geotiff returns the hail products encoded as the bands of a GeoTIFF,
+        as in PyRainRateGrid.geotiff
+        """
+
+    def save(self, path: str, nodata: float = -9999.0) -> None:
+        """
+        save writes the hail products as a GeoTIFF to the path
+        """
+
+
+class PyMeshAccumulator:
+    """
+    PyMeshAccumulator accumulates the MESH of a sequence of hail grids into
+    a swath of the largest MESH of each cell
+    """
+
+    # Largest MESH in mm of each cell, row-major from the north west corner,
+    # with NaN where no grid had data
+    maximum: List[float]
+    # Start of the first and end of the last grid added
+    start_time: Optional[int]
+    end_time: Optional[int]
+    grid_size: int
+    grid_spacing: float
+
+    # Raises ValueError for grids which PyHailOptions does not accept
+    def __init__(self, grid_size: int = 460, grid_spacing: float = 1.0) -> None: ...
+
+    def add(self, hail: PyHailGrid) -> None:
+        """
+        add adds the MESH of a hail grid, which must use the same grid and
+        not start before the previous grid ended
+        """
+
+    def geotiff(self, nodata: float = -9999.0) -> bytes:
+        """
+        geotiff returns the MESH swath encoded as a GeoTIFF, as in
+        PyRainRateGrid.geotiff, around the radar of the first grid added
+        """
+
+    def save(self, path: str, nodata: float = -9999.0) -> None:
+        """
+        save writes the MESH swath as a GeoTIFF to the path
+        """
+
+
+class PyColormap:
+    """
+    PyColormap maps product values to colors through stops of increasing
+    value. Values below the first stop, NaN and masked gates are
+    transparent, and range folded gates are purple.
+    """
+
+    # Stops of (value, (red, green, blue, alpha))
+    stops: List[Tuple[float, Tuple[int, int, int, int]]]
+    # Blend the colors between stops. Otherwise each stop colors the values
+    # up to the next stop.
+    interpolate: bool
+
+    def __init__(
+        self,
+        stops: List[Tuple[float, Tuple[int, int, int, int]]],
+        interpolate: bool = False
+    ) -> None: ...
+
+    @staticmethod
+    def load(path: str, interpolate: bool = False) -> PyColormap:
+        """
+        load reads a colormap with one stop per line as a value followed by
+        red, green, blue and optionally alpha. Lines starting with # are
+        ignored.
+        """
+
+    @staticmethod
+    def builtin(product: str) -> PyColormap:
+        """
+        builtin returns the NWS style colormap of a product
+        """
+
+    def color(self, value: float) -> Tuple[int, int, int, int]:
+        """
+        color returns the (red, green, blue, alpha) of a value
+        """
+
+
+class PyRenderOptions:
+    """
+    PyRenderOptions selects a sweep to render as an image and the extent
+    of the image
+    """
+
+    # Product to render, named like the PyLevel2File fields
+    product: str
+    # Elevation in degrees. The closest scanned elevation is used.
+    elevation: float
+    # Size of the image in pixels, which raises ValueError unless both are
+    # between 1 and 16384
+    width: int
+    height: int
+    # Degrees of (west, south, east, north) covered by the image, or None
+    # for the square around the radar out to distance km. Raises ValueError
+    # unless west < east and south < north.
+    bounds: Optional[Tuple[float, float, float, float]]
+    distance: float
+    # Colormap of the product values, or None for the built in colormap
+    colormap: Optional[PyColormap]
+
+    def __init__(
+        self,
+        product: str = "reflectivity",
+        elevation: float = 0.5,
+        width: int = 1024,
+        height: int = 1024,
+        bounds: Optional[Tuple[float, float, float, float]] = None,
+        distance: float = 230.0,
+        colormap: Optional[PyColormap] = None
+    ) -> None: ...
+
+
+class PyImage:
+    """
+    PyImage is a sweep rendered to RGBA pixels on a latitude/longitude grid
+    """
+
+    product: str
+    # Elevation of the rendered sweep in radians
+    elevation: float
+    width: int
+    height: int
+    # Degrees of (west, south, east, north) covered by the image
+    bounds: Tuple[float, float, float, float]
+    start_time: int
+    end_time: int
+    # Contents of a world file georeferencing the image
+    world_file: str
+
+    def rgba(self) -> bytes:
+        """
+        rgba returns the pixels, row-major from the top left corner
+        """
+
+    def png(self) -> bytes:
+        """
+        png returns the image encoded as a PNG
+        """
+
+    def save(self, path: str) -> None:
+        """
+        save writes the image as a PNG to the path, with its world file next
+        to it using the .pgw extension
+        """
+
+
+class PyBandOptions:
+    """
+    PyBandOptions selects what a band of a GeoTIFF raster is resampled from
+    """
+
+    # Product of the band, named like the PyLevel2File fields
+    product: str
+    # How the band is resampled from the volume: "tilt" for the sweep
+    # closest to elevation, "composite" for the largest value of every
+    # sweep or "cappi" for the value at altitude. CAPPI values are
+    # interpolated in height between tilts, and a tilt alone covers the
+    # depth of its beam.
+    method: str
+    # Elevation in degrees of a tilt band
+    elevation: float
+    # Altitude in km above mean sea level of a CAPPI band
+    altitude: float
+
+    def __init__(
+        self,
+        product: str = "reflectivity",
+        method: str = "tilt",
+        elevation: float = 0.5,
+        altitude: float = 3.0
+    ) -> None: ...
+
+
+class PyGeoTiffOptions:
+    """
+    PyGeoTiffOptions configures a raster of one or more bands on a
+    latitude/longitude grid, to be written as a GeoTIFF
+    """
+
+    # Bands of the raster, in order. Raises ValueError when empty.
+    bands: List[PyBandOptions]
+    # Size of the raster in pixels, which raises ValueError unless both are
+    # between 1 and 16384
+    width: int
+    height: int
+    # Degrees of (west, south, east, north) covered by the raster, or None
+    # for the square around the radar out to distance km. Raises ValueError
+    # unless west < east and south < north.
+    bounds: Optional[Tuple[float, float, float, float]]
+    distance: float
+    # Value written for pixels without data
+    nodata: float
+
+    def __init__(
+        self,
+        bands: List[PyBandOptions],
+        width: int = 1024,
+        height: int = 1024,
+        bounds: Optional[Tuple[float, float, float, float]] = None,
+        distance: float = 230.0,
+        nodata: float = -9999.0
+    ) -> None: ...
+
+
+class PyRaster:
+    """
+    PyRaster holds bands resampled onto a latitude/longitude grid. Its
+    GeoTIFF uses 32 bit float samples in the EPSG:4326 coordinate reference
+    system and needs no GDAL to write.
+    """
+
+    width: int
+    height: int
+    # Degrees of (west, south, east, north) covered by the raster
+    bounds: Tuple[float, float, float, float]
+    # Value written to the GeoTIFF for pixels without data
+    nodata: float
+    start_time: int
+    end_time: int
+    # Description of each band
+    band_names: List[str]
+    # Values of each band, row-major from the north west corner, with NaN
+    # where there is no data
+    values: List[List[float]]
+
+    def geotiff(self) -> bytes:
+        """
+        geotiff returns the raster encoded as a GeoTIFF
+        """
+
+    def contours(self, options: PyContourOptions, band: int = 0) -> PyContours:
+        """
+        contours encloses the pixels of a band passing the thresholds of the
+        options, as in PyRainRateGrid.contours. Raises ValueError when there
+        is no such band.
+        """
+
+    def save(self, path: str) -> None:
+        """
+        save writes the raster as a GeoTIFF to the path
+        """
+
+
+class PyContourOptions:
+    """
+    PyContourOptions configures polygons enclosing the values of a product
+    which pass each of a set of thresholds
+    """
+
+    # Values to contour, such as dBZ or m/s
+    thresholds: List[float]
+    # What to contour. Tilts are contoured on their gates, and composites
+    # and CAPPIs on a latitude/longitude grid.
+    band: PyBandOptions
+    # Enclose values at or below the thresholds, such as inbound
+    # velocities, rather than at or above them
+    below: bool
+    # Tolerance in km of the simplification of the polygons, or 0 to keep
+    # every corner
+    simplify: float
+    # Distance in km around the radar and pixel size in km of the grid of
+    # composites and CAPPIs. Raises ValueError unless both are positive,
+    # with at most 16384 pixels across the grid.
+    distance: float
+    resolution: float
+
+    def __init__(
+        self,
+        thresholds: List[float],
+        band: Optional[PyBandOptions] = None,
+        below: bool = False,
+        simplify: float = 0.0,
+        distance: float = 230.0,
+        resolution: float = 1.0
+    ) -> None: ...
+
+
+class PyContours:
+    """
+    PyContours holds the contours of a product as GeoJSON. Polygons follow
+    the edges of the gates, grid cells or pixels which pass a threshold,
+    with holes where they do not. Exteriors are counterclockwise and holes
+    clockwise as in RFC 7946.
+    """
+
+    # Description of what was contoured, as in PyRaster band names, or the
+    # contoured field of a grid such as "mesh"
+    product: str
+    thresholds: List[float]
+    below: bool
+    start_time: int
+    end_time: int
+    # GeoJSON feature collection with a MultiPolygon feature per threshold,
+    # whose properties are the product and threshold
+    geojson: str
+
+    def save(self, path: str) -> None:
+        """
+        save writes the GeoJSON to the path
+        """
+
+
+class PyConvertOptions:
+    """
+    PyConvertOptions configures how volumes are processed
+    """
+
+    # Move incomplete sweeps onto the nominal azimuth grid, centered at
+    # (i + 0.5) * az_step, with the missing radials masked. Otherwise they
+    # only contain the received radials, and the first and last radials are
+    # not treated as neighbors when despeckling and dealiasing.
+    regrid_partial_sweeps: bool
+    # Resample every sweep onto an exact uniform azimuth grid, where radial
+    # i is centered at (i + 0.5) * az_step
+    resample_uniform: bool
+    # Mask ground clutter and anomalous propagation, or None to skip it
+    clutter_filter: Optional[PyClutterFilterOptions]
+    # Mask birds, insects and chaff on every moment, or None to skip it
+    biological_filter: Optional[PyBiologicalFilterOptions]
+    # Estimate KDP from the differential phase of the sweeps with dual pol
+    # data, or None to skip it. The estimate is also used by hydrometeor
+    # classification.
+    estimate_kdp: Optional[PyKdpOptions]
+    # Detect the melting layer from quasi-vertical profiles of the higher
+    # tilts. The detected layer is used for classification when
+    # melting_layer is not given. None skips detection.
+    detect_melting_layer: Optional[PyMeltingLayerOptions]
+    # Classify the hydrometeors of the sweeps with dual pol data
+    classify_hydrometeors: bool
+    # Bottom and top of the melting layer in km above mean sea level, used
+    # to limit the hydrometeor classes by height
+    melting_layer: Optional[Tuple[float, float]]
+    # Estimate and grid the rain rate of the lowest tilt, or None to skip it
+    qpe: Optional[PyQpeOptions]
+    # Quasi-vertical profiles to produce from the processed volume
+    qvps: List[PyQvpOptions]
+    # Identify storm cells from the reflectivity of every tilt, or None to
+    # skip it
+    storm_cells: Optional[PyStormCellOptions]
+    # Detect mesocyclones and tornado vortex signatures in the dealiased
+    # velocity, or None to skip it
+    rotation: Optional[PyRotationOptions]
+    # Estimate azimuthal shear and divergence from the dealiased velocity
+    # and grid their layer maxima, or None to skip it
+    azimuthal_shear: Optional[PyAzimuthalShearOptions]
+    # Remove the storm motion from the dealiased velocity, or None to skip
+    # storm-relative velocity
+    storm_relative: Optional[PyStormRelativeOptions]
+    # Grid the hail detection products and add them to the storm cells, or
+    # None to skip hail detection
+    hail: Optional[PyHailOptions]
+    # Images to render from the processed volume
+    images: List[PyRenderOptions]
+    # GeoTIFF rasters to resample from the processed volume
+    rasters: List[PyGeoTiffOptions]
+    # GeoJSON contours to trace from the processed volume
+    contours: List[PyContourOptions]
+
+    def __init__(
+        self,
+        regrid_partial_sweeps: bool = True,
+        resample_uniform: bool = False,
+        clutter_filter: Optional[PyClutterFilterOptions] = None,
+        biological_filter: Optional[PyBiologicalFilterOptions] = None,
+        estimate_kdp: Optional[PyKdpOptions] = None,
+        detect_melting_layer: Optional[PyMeltingLayerOptions] = None,
+        classify_hydrometeors: bool = False,
+        melting_layer: Optional[Tuple[float, float]] = None,
+        qpe: Optional[PyQpeOptions] = None,
+        qvps: List[PyQvpOptions] = [],
+        storm_cells: Optional[PyStormCellOptions] = None,
+        rotation: Optional[PyRotationOptions] = None,
+        azimuthal_shear: Optional[PyAzimuthalShearOptions] = None,
+        storm_relative: Optional[PyStormRelativeOptions] = None,
+        hail: Optional[PyHailOptions] = None,
+        images: List[PyRenderOptions] = [],
+        rasters: List[PyGeoTiffOptions] = [],
+        contours: List[PyContourOptions] = []
+    ) -> None: ...
+
+
+class PyClutterFilterOptions:
+    """
+    PyClutterFilterOptions holds the thresholds of the clutter filter. A
+    gate is masked when at least score_threshold of the available tests
+    flag it as clutter.
+    """
+
+    # Number of gates either side of a gate used for texture and spin
+    window_half_width: int
+    # Reflectivity texture in dBZ^2 above which a gate looks like clutter
+    texture_threshold: float
+    # Smallest reflectivity difference in dBZ counted as a spin change
+    spin_change: float
+    # Fraction of spin changes above which a gate looks like clutter
+    spin_threshold: float
+    # Absolute velocity in m/s below which a gate looks like clutter. The
+    # surveillance cut of a split cut uses the velocity of its Doppler cut.
+    velocity_threshold: float
+    # Correlation coefficient below which a gate looks like clutter
+    correlation_threshold: float
+    # Fraction of the available tests which must flag a gate to mask it
+    score_threshold: float
+
+    def __init__(
+        self,
+        window_half_width: int = 4,
+        texture_threshold: float = 45.0,
+        spin_change: float = 2.0,
+        spin_threshold: float = 0.5,
+        velocity_threshold: float = 1.0,
+        correlation_threshold: float = 0.85,
+        score_threshold: float = 0.6
+    ) -> None: ...
+
+
+class PyBiologicalFilterOptions:
+    """
+    PyBiologicalFilterOptions holds the thresholds of the biological scatter
+    and chaff filter. Each test scores a gate from 0 to 1 with a linear ramp
+    between its low and high values, correlation coefficient counts twice,
+    and gates whose weighted score reaches score_threshold are masked.
+    """
+
+    # Number of gates either side of a gate used for the textures
+    window_half_width: int
+    # Correlation coefficient ramp, gates at or above correlation_high are
+    # never masked
+    correlation_low: float
+    correlation_high: float
+    # Absolute differential reflectivity ramp in dB
+    zdr_low: float
+    zdr_high: float
+    # Differential reflectivity texture (standard deviation) ramp in dB
+    zdr_texture_low: float
+    zdr_texture_high: float
+    # Differential phase texture (standard deviation) ramp in degrees
+    use_differential_phase: bool
+    phidp_texture_low: float
+    phidp_texture_high: float
+    # Weighted score at or above which a gate is masked
+    score_threshold: float
+
+    def __init__(
+        self,
+        window_half_width: int = 4,
+        correlation_low: float = 0.7,
+        correlation_high: float = 0.9,
+        zdr_low: float = 1.5,
+        zdr_high: float = 4.0,
+        zdr_texture_low: float = 1.0,
+        zdr_texture_high: float = 2.5,
+        use_differential_phase: bool = True,
+        phidp_texture_low: float = 10.0,
+        phidp_texture_high: float = 25.0,
+        score_threshold: float = 0.5
+    ) -> None: ...
+
+
+class PyKdpOptions:
+    """
+    PyKdpOptions configures KDP estimation. KDP is half the range derivative
+    of the filtered differential phase, fitted over the short window where
+    reflectivity reaches reflectivity_threshold and the long window
+    elsewhere.
+    """
+
+    # Correlation coefficient below which the differential phase is too
+    # noisy to use and is interpolated over
+    correlation_threshold: float
+    # Reflectivity in dBZ at or above which the short window is used
+    reflectivity_threshold: float
+    # Lengths in km of the fitting windows
+    short_window: float
+    long_window: float
+
+    def __init__(
+        self,
+        correlation_threshold: float = 0.9,
+        reflectivity_threshold: float = 40.0,
+        short_window: float = 2.0,
+        long_window: float = 6.0
+    ) -> None: ...
+
+
+class PyMeltingLayerOptions:
+    """
+    PyMeltingLayerOptions configures melting layer detection from the
+    quasi-vertical profiles of the higher tilts. The layer is centered on
+    the dip in correlation coefficient and extends while the correlation
+    stays below correlation_threshold.
+    """
+
+    # Range of elevations in degrees whose profiles are used
+    min_elevation: float
+    max_elevation: float
+    # Correlation coefficient below which a bin is inside the layer
+    correlation_threshold: float
+    # Correlation coefficient below which echoes are non-meteorological
+    min_correlation: float
+    # Smallest differential reflectivity in dB and reflectivity in dBZ at
+    # the correlation dip
+    min_zdr: float
+    min_reflectivity: float
+    # Number of consecutive profile bins without data the layer may extend
+    # across
+    max_missing_bins: int
+
+    def __init__(
+        self,
+        min_elevation: float = 4.0,
+        max_elevation: float = 20.0,
+        correlation_threshold: float = 0.97,
+        min_correlation: float = 0.8,
+        min_zdr: float = 0.5,
+        min_reflectivity: float = 15.0,
+        max_missing_bins: int = 2
+    ) -> None: ...
+
+
+class PyQpeOptions:
+    """
+    PyQpeOptions configures rain rate estimation on the lowest tilt
+    """
+
+    # Z-R relationship: "marshall_palmer" (Z = 200 R^1.6), "tropical"
+    # (Z = 250 R^1.2) or "convective" (Z = 300 R^1.4)
+    relationship: str
+    # Use R(KDP) in heavy rain, which needs estimate_kdp to be enabled
+    use_kdp: bool
+    # Use R(Z, ZDR) where ZDR is available
+    use_zdr: bool
+    # Number of cells along each side of the rain rate grid
+    grid_size: int
+    # Width of each grid cell in km
+    grid_spacing: float
+
+    def __init__(
+        self,
+        relationship: str = "convective",
+        use_kdp: bool = False,
+        use_zdr: bool = False,
+        grid_size: int = 460,
+        grid_spacing: float = 1.0
+    ) -> None: ...
+
+
+class PyRainRateGrid:
+    """
+    PyRainRateGrid holds the rain rate of a scan on a square grid centered
+    on the radar
+    """
+
+    size: int
+    # Width of each cell in km
+    spacing: float
+    # Rain rate in mm/h of each cell, row-major from the north west corner,
+    # with NaN where there is no data
+    values: List[float]
+
+    start_time: int
+    end_time: int
+
+    def contours(self, options: PyContourOptions) -> PyContours:
+        """
+        contours encloses the cells passing the thresholds of the options,
+        following the grid cells. The band, distance and resolution of the
+        options are not used. Raises ValueError when the radar site is
+        unknown.
+        """
+
+    def geotiff(self, nodata: float = -9999.0) -> bytes:
+        """
+        geotiff returns the rain rate encoded as a GeoTIFF in latitude and
+        longitude, with a pixel for each cell resampled from the nearest
+        cell and nodata where there is no data. Raises ValueError when the
+        radar site is unknown.
+        """
+
+    def save(self, path: str, nodata: float = -9999.0) -> None:
+        """
+        save writes the rain rate as a GeoTIFF to the path
+        """
+
+
+class PyRainAccumulator:
+    """
+    PyRainAccumulator accumulates the rain rate grids of a sequence of scans
+    into rainfall totals. Each interval between consecutive scans adds the
+    mean of the rates at either end, and intervals longer than max_gap
+    seconds are skipped.
+    """
+
+    # Rainfall in mm of each cell, row-major from the north west corner
+    totals: List[float]
+    # Seconds of rainfall covered by the totals
+    duration: int
+    grid_size: int
+    grid_spacing: float
+
+    def __init__(
+        self,
+        grid_size: int = 460,
+        grid_spacing: float = 1.0,
+        max_gap: int = 1800
+    ) -> None: ...
+
+    def add(self, rain_rate: PyRainRateGrid) -> None:
+        """
+        add adds the rain rate grid of a scan, which must use the same grid
+        and be later than the previous scan
+        """
+
+    def geotiff(self, nodata: float = -9999.0) -> bytes:
+        """
+        geotiff returns the rainfall totals encoded as a GeoTIFF, as in
+        PyRainRateGrid.geotiff, around the radar of the first grid added
+        """
+
+    def save(self, path: str, nodata: float = -9999.0) -> None:
+        """
+        save writes the rainfall totals as a GeoTIFF to the path
+        """
 
 
 class PyChunkIdentifier:
@@ -71,7 +1226,10 @@ def download_chunk(chunk_identifier: PyChunkIdentifier) -> PyChunk:
     """
 
 
-def convert_chunks(chunks: List[PyChunk]) -> PyLevel2File:
+def convert_chunks(
+    chunks: List[PyChunk],
+    options: Optional[PyConvertOptions] = None
+) -> PyLevel2File:
     """
     convert_chunks converts the list of chunks (all from the same volume)
     into a volume file
@@ -84,7 +1242,153 @@ def list_records(site: str, year: int, month: int, day: int) -> List[str]:
     """
 
 
-def download_nexrad_file(id: str) -> PyLevel2File:
+def download_nexrad_file(
+    id: str,
+    options: Optional[PyConvertOptions] = None
+) -> PyLevel2File:
     """
     download_nexrad_file downloads the volume file for the given key
     """
+
+
+def find_volume_at(
+    site: str,
+    time: int,
+    tolerance: int
+) -> Optional[Tuple[str, int]]:
+    """
+    find_volume_at finds the archive record whose scan start time is
+    closest to the given time (seconds since epoch), searching adjacent
+    days when needed. Returns the record name and its scan start time,
+    or None if no scan starts within tolerance seconds of the time. The
+    tolerance may be at most one day.
+    """
+
+
+def read_nexrad_file(
+    path: str,
+    options: Optional[PyConvertOptions] = None
+) -> PyLevel2File:
+    """
+    read_nexrad_file reads and decodes a volume file from disk
+    """
+
+
+def chunks_to_archive(chunks: List[PyChunk]) -> bytes:
+    """
+    chunks_to_archive reassembles the chunks of a single volume into
+    the contents of an Archive II volume file. Raises ValueError unless the
+    chunks start with the start chunk and follow it without gaps.
+    """
+
+
+def write_archive(chunks: List[PyChunk], path: str) -> None:
+    """
+    write_archive reassembles the chunks of a single volume into an
+    Archive II volume file and writes it to the given path
+    """
+
+
+def stack_qvps(qvps: List[PyQvp]) -> PyTimeHeight:
+    """
+    stack_qvps stacks profiles of the same product and height bins, such as
+    those of a sequence of volumes, into a time-height array ordered by time
+    """
+
+
+def set_thread_count(num_threads: int) -> None:
+    """
+    set_thread_count sets the number of threads used to process the sweeps
+    of a volume in parallel. 0 uses one thread per core, which is the
+    default.
+    """
+
+
+def get_thread_count() -> int:
+    """
+    get_thread_count returns the number of threads used to process sweeps
+    """
+
+
+# The following functions are asyncio equivalents of the functions above.
+# They run on a runtime shared with the blocking functions, so many of them
+# may be awaited concurrently from a single event loop.
+
+
+def get_latest_volume_async(site: str) -> Awaitable[int]:
+    """
+    get_latest_volume_async is the awaitable version of get_latest_volume
+    """
+
+
+def list_chunks_in_volume_async(
+    site: str,
+    volume_id: int
+) -> Awaitable[List[PyChunkIdentifier]]:
+    """
+    list_chunks_in_volume_async is the awaitable version of
+    list_chunks_in_volume
+    """
+
+
+def download_chunk_async(
+    chunk_identifier: PyChunkIdentifier
+) -> Awaitable[PyChunk]:
+    """
+    download_chunk_async is the awaitable version of download_chunk
+    """
+
+
+def convert_chunks_async(
+    chunks: List[PyChunk],
+    options: Optional[PyConvertOptions] = None
+) -> Awaitable[PyLevel2File]:
+    """
+    convert_chunks_async is the awaitable version of convert_chunks
+    """
+
+
+def write_archive_async(chunks: List[PyChunk], path: str) -> Awaitable[None]:
+    """
+    write_archive_async is the awaitable version of write_archive
+    """
+
+
+def list_records_async(
+    site: str,
+    year: int,
+    month: int,
+    day: int
+) -> Awaitable[List[str]]:
+    """
+    list_records_async is the awaitable version of list_records
+    """
+
+
+def download_nexrad_file_async(
+    id: str,
+    options: Optional[PyConvertOptions] = None
+) -> Awaitable[PyLevel2File]:
+    """
+    download_nexrad_file_async is the awaitable version of
+    download_nexrad_file
+    """
+
+
+def read_nexrad_file_async(
+    path: str,
+    options: Optional[PyConvertOptions] = None
+) -> Awaitable[PyLevel2File]:
+    """
+    read_nexrad_file_async is the awaitable version of read_nexrad_file
+    """
+
+
+def find_volume_at_async(
+    site: str,
+    time: int,
+    tolerance: int
+) -> Awaitable[Optional[Tuple[str, int]]]:
+    """
+    find_volume_at_async is the awaitable version of find_volume_at
+    """
@@ -1,17 +1,172 @@
 use nexrad_data::volume::Record;
+use rayon::prelude::*;
+
+use super::thread_pool::thread_pool;
 
 use crate::dealias_region::region_dealias::dealias_region_based;
+use crate::filter::biological::filter_biological;
+use crate::filter::clutter::filter_clutter;
 use crate::filter::despeckle::despeckle;
 use crate::filter::velocity_ref_threshold::apply_reflectivity_threshold;
 use crate::model::volume::Volume;
+use crate::products::azimuthal_shear::estimate_azimuthal_shear;
+use crate::products::hail::storm_cell_hail;
+use crate::products::hydrometeor_class::{classify_hydrometeors, HydroClassParams};
+use crate::products::kdp::estimate_kdp;
+use crate::products::melting_layer::detect_melting_layer;
+use crate::products::qpe::estimate_rain_rate;
+use crate::products::rotation::detect_rotation;
+use crate::products::storm_cells::identify_storm_cells;
+use crate::products::storm_relative::{mean_wind, storm_relative_velocity, MeanWindParams};
+use crate::pymodel::py_contours::PyContours;
+use crate::pymodel::py_convert_options::PyConvertOptions;
+use crate::pymodel::py_hail::PyHailGrid;
+use crate::pymodel::py_image::PyImage;
 use crate::pymodel::py_level2_file::PyLevel2File;
+use crate::pymodel::py_qvp::PyQvp;
+use crate::pymodel::py_rain_rate_grid::PyRainRateGrid;
+use crate::pymodel::py_raster::PyRaster;
+use crate::pymodel::py_shear_layers::PyShearLayers;
 
 pub fn convert_nexrad_file(records: Vec<Record>) -> PyLevel2File {
-    let mut volume = Volume::new(records);
+    convert_nexrad_file_with_options(records, &PyConvertOptions::default())
+}
+
+pub fn convert_nexrad_file_with_options(
+    records: Vec<Record>,
+    options: &PyConvertOptions,
+) -> PyLevel2File {
+    let volume = process_volume(records, options);
+
+    let rain_rate_grid = options
+        .qpe
+        .as_ref()
+        .and_then(|qpe| PyRainRateGrid::new(&volume, &qpe.grid()));
+
+    let shear_layers = options
+        .azimuthal_shear
+        .as_ref()
+        .and_then(|azimuthal_shear| PyShearLayers::new(&volume, azimuthal_shear));
+
+    let hail_grid = options
+        .hail
+        .as_ref()
+        .and_then(|hail| PyHailGrid::new(&volume, hail));
+
+    let qvps = options
+        .qvps
+        .iter()
+        .filter_map(|qvp| PyQvp::new(&volume, qvp))
+        .collect();
+
+    let images = options
+        .images
+        .iter()
+        .filter_map(|image| PyImage::new(&volume, image))
+        .collect();
+
+    let rasters = options
+        .rasters
+        .iter()
+        .filter_map(|raster| PyRaster::new(&volume, raster))
+        .collect();
+
+    let contours = options
+        .contours
+        .iter()
+        .filter_map(|contours| PyContours::new(&volume, contours))
+        .collect();
+
+    PyLevel2File {
+        rain_rate_grid,
+        images,
+        rasters,
+        contours,
+        shear_layers,
+        hail_grid,
+        qvps,
+        ..PyLevel2File::new(volume)
+    }
+}
+
+/// Decodes the records into a volume and runs the processing pipeline.
+/// Sweeps are processed in parallel on the shared thread pool.
+pub fn process_volume(records: Vec<Record>, options: &PyConvertOptions) -> Volume {
+    thread_pool().install(|| {
+        let mut volume = Volume::new(records);
+
+        volume.sweeps.par_iter_mut().for_each(|sweep| {
+            if options.resample_uniform {
+                sweep.resample_uniform();
+            } else if options.regrid_partial_sweeps && !sweep.is_complete() {
+                sweep.regrid();
+            }
+        });
+
+        if let Some(kdp) = &options.estimate_kdp {
+            estimate_kdp(&mut volume, &kdp.params());
+        }
+
+        if let Some(melting_layer) = &options.detect_melting_layer {
+            volume.melting_layer = detect_melting_layer(&volume, &melting_layer.params());
+        }
+
+        // Classify before the filters so clutter and biological scatter
+        // are classified rather than masked
+        if options.classify_hydrometeors {
+            let params = HydroClassParams {
+                melting_layer: options
+                    .melting_layer()
+                    .or(volume.melting_layer.map(|estimate| estimate.layer)),
+                ..Default::default()
+            };
+            classify_hydrometeors(&mut volume, &params);
+        }
+
+        if let Some(clutter_filter) = &options.clutter_filter {
+            filter_clutter(&mut volume, &clutter_filter.params());
+        }
+
+        if let Some(biological_filter) = &options.biological_filter {
+            filter_biological(&mut volume, &biological_filter.params());
+        }
+
+        apply_reflectivity_threshold(&mut volume, -5.0);
+        despeckle(&mut volume, 50);
+        dealias_region_based(&mut volume, 3, 100, 100, true);
+
+        if let Some(qpe) = &options.qpe {
+            estimate_rain_rate(&mut volume, &qpe.params());
+        }
+
+        if let Some(storm_cells) = &options.storm_cells {
+            volume.storm_cells = Some(identify_storm_cells(&volume, &storm_cells.params()));
+        }
+
+        if let (Some(hail), Some(storm_cells)) = (&options.hail, &mut volume.storm_cells) {
+            let params = hail.params();
+            for cell in storm_cells.cells.iter_mut() {
+                cell.hail = Some(storm_cell_hail(cell, &params));
+            }
+        }
+
+        if let Some(storm_relative) = &options.storm_relative {
+            let motion = storm_relative
+                .motion()
+                .or_else(|| mean_wind(&volume, &MeanWindParams::default()));
+            if let Some(motion) = motion {
+                storm_relative_velocity(&mut volume, motion);
+            }
+        }
+
+        if let Some(azimuthal_shear) = &options.azimuthal_shear {
+            estimate_azimuthal_shear(&mut volume, &azimuthal_shear.params());
+        }
 
-    apply_reflectivity_threshold(&mut volume, -5.0);
-    despeckle(&mut volume, 50);
-    dealias_region_based(&mut volume, 3, 100, 100, true);
+        if let Some(rotation) = &options.rotation {
+            volume.rotations = detect_rotation(&volume, &rotation.params());
+        }
 
-    PyLevel2File::new(volume)
+        volume
+    })
 }
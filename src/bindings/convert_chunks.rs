@@ -1,18 +1,42 @@
 use nexrad_data::{aws::realtime::Chunk, volume::Record};
-use pyo3::{pyfunction, PyResult, Python};
+use pyo3::{pyfunction, PyAny, PyResult, Python};
 
-use crate::pymodel::{py_chunk::PyChunk, py_level2_file::PyLevel2File};
+use crate::pymodel::{
+    py_chunk::PyChunk, py_convert_options::PyConvertOptions, py_level2_file::PyLevel2File,
+};
 
-use super::convert::convert_nexrad_file;
+use super::convert::convert_nexrad_file_with_options;
+use super::runtime::future_into_py;
 
 #[pyfunction]
-pub fn convert_chunks(py: Python, chunks: Vec<PyChunk>) -> PyResult<PyLevel2File> {
-    let result = py.allow_threads(move || convert_chunks_impl(chunks));
+#[pyo3(signature = (chunks, options = None))]
+pub fn convert_chunks(
+    py: Python,
+    chunks: Vec<PyChunk>,
+    options: Option<PyConvertOptions>,
+) -> PyResult<PyLevel2File> {
+    let options = options.unwrap_or_default();
+    let result = py.allow_threads(move || convert_chunks_impl(chunks, &options));
 
     Ok(result)
 }
 
-fn convert_chunks_impl(chunks: Vec<PyChunk>) -> PyLevel2File {
+#[pyfunction]
+#[pyo3(signature = (chunks, options = None))]
+pub fn convert_chunks_async<'py>(
+    py: Python<'py>,
+    chunks: Vec<PyChunk>,
+    options: Option<PyConvertOptions>,
+) -> PyResult<&'py PyAny> {
+    let options = options.unwrap_or_default();
+    future_into_py(py, async move {
+        tokio::task::spawn_blocking(move || convert_chunks_impl(chunks, &options))
+            .await
+            .expect("Should convert without error")
+    })
+}
+
+fn convert_chunks_impl(chunks: Vec<PyChunk>, options: &PyConvertOptions) -> PyLevel2File {
     let nexrad_chunks: Vec<_> = chunks
         .iter()
         .map(|chunk| Chunk::new(chunk.data.clone()).expect("Can construct chunk from data"))
@@ -31,5 +55,5 @@ fn convert_chunks_impl(chunks: Vec<PyChunk>) -> PyLevel2File {
         };
     }
 
-    convert_nexrad_file(records)
+    convert_nexrad_file_with_options(records, options)
 }
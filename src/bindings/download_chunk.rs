@@ -1,18 +1,28 @@
 use nexrad_data::aws::realtime::{
     download_chunk as nexrad_download_chunk, ChunkIdentifier, VolumeIndex,
 };
-use pyo3::{pyfunction, PyResult, Python};
+use pyo3::{pyfunction, PyAny, PyResult, Python};
 
+use super::runtime::{future_into_py, runtime};
 use crate::pymodel::{py_chunk::PyChunk, py_chunk_identifier::PyChunkIdentifier};
 
 #[pyfunction]
 pub fn download_chunk(py: Python, chunk_identifier: PyChunkIdentifier) -> PyResult<PyChunk> {
-    let result = py.allow_threads(move || download_chunk_impl(chunk_identifier));
+    let result =
+        py.allow_threads(move || runtime().block_on(download_chunk_impl(chunk_identifier)));
 
     Ok(result)
 }
 
-fn download_chunk_impl(chunk_identifier: PyChunkIdentifier) -> PyChunk {
+#[pyfunction]
+pub fn download_chunk_async<'py>(
+    py: Python<'py>,
+    chunk_identifier: PyChunkIdentifier,
+) -> PyResult<&'py PyAny> {
+    future_into_py(py, download_chunk_impl(chunk_identifier))
+}
+
+async fn download_chunk_impl(chunk_identifier: PyChunkIdentifier) -> PyChunk {
     let nexrad_chunk_identifier = ChunkIdentifier::new(
         chunk_identifier.site.clone(),
         VolumeIndex::new(chunk_identifier.volume as usize),
@@ -20,12 +30,8 @@ fn download_chunk_impl(chunk_identifier: PyChunkIdentifier) -> PyChunk {
         None,
     );
 
-    let rt = tokio::runtime::Runtime::new().unwrap();
-
-    let (_, chunk) = rt
-        .block_on(async {
-            nexrad_download_chunk(&chunk_identifier.site, &nexrad_chunk_identifier).await
-        })
+    let (_, chunk) = nexrad_download_chunk(&chunk_identifier.site, &nexrad_chunk_identifier)
+        .await
         .expect("Should find latest volume");
 
     let py_chunk = PyChunk::new(chunk_identifier, chunk.data().to_vec());
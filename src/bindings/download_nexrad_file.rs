@@ -1,23 +1,43 @@
 use nexrad_data::aws::archive::{download_file, Identifier};
-use pyo3::{pyfunction, PyResult, Python};
+use pyo3::{pyfunction, PyAny, PyResult, Python};
 
-use super::convert::convert_nexrad_file;
-use crate::pymodel::py_level2_file::PyLevel2File;
+use super::convert::convert_nexrad_file_with_options;
+use super::runtime::{future_into_py, runtime};
+use crate::pymodel::{py_convert_options::PyConvertOptions, py_level2_file::PyLevel2File};
 
 #[pyfunction]
-pub fn download_nexrad_file(py: Python, identifier: String) -> PyResult<PyLevel2File> {
-    let result = py.allow_threads(move || download_nexrad_file_impl(identifier));
+#[pyo3(signature = (identifier, options = None))]
+pub fn download_nexrad_file(
+    py: Python,
+    identifier: String,
+    options: Option<PyConvertOptions>,
+) -> PyResult<PyLevel2File> {
+    let options = options.unwrap_or_default();
+    let result = py
+        .allow_threads(move || runtime().block_on(download_nexrad_file_impl(identifier, options)));
 
     Ok(result)
 }
 
-/// Downloads and decodes a nexrad file
-fn download_nexrad_file_impl(identifier: String) -> PyLevel2File {
-    let rt = tokio::runtime::Runtime::new().unwrap();
+#[pyfunction]
+#[pyo3(signature = (identifier, options = None))]
+pub fn download_nexrad_file_async<'py>(
+    py: Python<'py>,
+    identifier: String,
+    options: Option<PyConvertOptions>,
+) -> PyResult<&'py PyAny> {
+    let options = options.unwrap_or_default();
+    future_into_py(py, download_nexrad_file_impl(identifier, options))
+}
 
-    let f = rt
-        .block_on(async { download_file(Identifier::new(identifier)).await })
+/// Downloads and decodes a nexrad file
+async fn download_nexrad_file_impl(identifier: String, options: PyConvertOptions) -> PyLevel2File {
+    let f = download_file(Identifier::new(identifier))
+        .await
         .expect("Should download without error");
 
-    convert_nexrad_file(f.records())
+    // Decoding is CPU bound so keep it off of the async worker threads
+    tokio::task::spawn_blocking(move || convert_nexrad_file_with_options(f.records(), &options))
+        .await
+        .expect("Should convert without error")
 }
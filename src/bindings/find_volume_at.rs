@@ -0,0 +1,91 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyfunction, PyAny, PyResult, Python};
+
+use super::list_records::list_records_impl;
+use super::runtime::{future_into_py, runtime};
+use super::util::parse_record_time;
+
+#[pyfunction]
+pub fn find_volume_at(
+    py: Python,
+    site: String,
+    time: i64,
+    tolerance: i64,
+) -> PyResult<Option<(String, i64)>> {
+    let days = search_days(time, tolerance)?;
+    let result = py.allow_threads(move || {
+        runtime().block_on(find_volume_at_impl(site, time, tolerance, days))
+    });
+
+    Ok(result)
+}
+
+#[pyfunction]
+pub fn find_volume_at_async<'py>(
+    py: Python<'py>,
+    site: String,
+    time: i64,
+    tolerance: i64,
+) -> PyResult<&'py PyAny> {
+    let days = search_days(time, tolerance)?;
+    future_into_py(py, find_volume_at_impl(site, time, tolerance, days))
+}
+
+// Longest tolerance in seconds, which limits the search to three days
+const MAX_TOLERANCE: i64 = 24 * 60 * 60;
+
+// First and last day with scans within the tolerance of the time
+fn search_days(time: i64, tolerance: i64) -> PyResult<(NaiveDate, NaiveDate)> {
+    if !(0..=MAX_TOLERANCE).contains(&tolerance) {
+        return Err(PyValueError::new_err(format!(
+            "tolerance must be between 0 and {} seconds",
+            MAX_TOLERANCE
+        )));
+    }
+
+    let invalid_time = || PyValueError::new_err(format!("Invalid time: {}", time));
+    let target = DateTime::<Utc>::from_timestamp(time, 0).ok_or_else(invalid_time)?;
+    let first = target
+        .checked_sub_signed(Duration::seconds(tolerance))
+        .ok_or_else(invalid_time)?;
+    let last = target
+        .checked_add_signed(Duration::seconds(tolerance))
+        .ok_or_else(invalid_time)?;
+
+    Ok((first.date_naive(), last.date_naive()))
+}
+
+/// Finds the archive record with the scan start time closest to the given
+/// time, searching the adjacent days when the tolerance crosses midnight.
+/// Returns the record name and its scan start time in seconds since epoch.
+async fn find_volume_at_impl(
+    site: String,
+    time: i64,
+    tolerance: i64,
+    (first_day, last_day): (NaiveDate, NaiveDate),
+) -> Option<(String, i64)> {
+    let mut best: Option<(String, i64)> = None;
+    let mut best_diff = i64::MAX;
+
+    for day in first_day.iter_days().take_while(|day| *day <= last_day) {
+        let records = list_records_impl(site.clone(), day.year(), day.month(), day.day()).await;
+
+        for record in records {
+            let scan_time = match parse_record_time(&record) {
+                Some(scan_time) => scan_time.timestamp(),
+                None => continue,
+            };
+
+            let diff = (scan_time - time).abs();
+            if diff > tolerance || diff >= best_diff {
+                continue;
+            }
+
+            best_diff = diff;
+            best = Some((record, scan_time));
+        }
+    }
+
+    best
+}
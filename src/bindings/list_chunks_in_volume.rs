@@ -1,6 +1,7 @@
 use nexrad_data::aws::realtime::{list_chunks_in_volume as nexrad_chunks_in_volume, VolumeIndex};
-use pyo3::{pyfunction, PyResult, Python};
+use pyo3::{pyfunction, PyAny, PyResult, Python};
 
+use super::runtime::{future_into_py, runtime};
 use crate::pymodel::py_chunk_identifier::PyChunkIdentifier;
 
 #[pyfunction]
@@ -9,18 +10,26 @@ pub fn list_chunks_in_volume(
     site: String,
     volume_id: i32,
 ) -> PyResult<Vec<PyChunkIdentifier>> {
-    let result = py.allow_threads(move || list_chunks_in_volume_impl(site, volume_id));
+    let result =
+        py.allow_threads(move || runtime().block_on(list_chunks_in_volume_impl(site, volume_id)));
 
     Ok(result)
 }
 
-fn list_chunks_in_volume_impl(site: String, volume_id: i32) -> Vec<PyChunkIdentifier> {
-    let volume_index = VolumeIndex::new(volume_id as usize);
+#[pyfunction]
+pub fn list_chunks_in_volume_async<'py>(
+    py: Python<'py>,
+    site: String,
+    volume_id: i32,
+) -> PyResult<&'py PyAny> {
+    future_into_py(py, list_chunks_in_volume_impl(site, volume_id))
+}
 
-    let rt = tokio::runtime::Runtime::new().unwrap();
+async fn list_chunks_in_volume_impl(site: String, volume_id: i32) -> Vec<PyChunkIdentifier> {
+    let volume_index = VolumeIndex::new(volume_id as usize);
 
-    let chunks_in_volume = rt
-        .block_on(async { nexrad_chunks_in_volume(&site, volume_index, 1000).await })
+    let chunks_in_volume = nexrad_chunks_in_volume(&site, volume_index, 1000)
+        .await
         .expect("Should have chunks in the volume");
 
     let chunk_identifiers = chunks_in_volume
@@ -1,6 +1,7 @@
 use nexrad_data::aws::archive::list_files;
-use pyo3::{pyfunction, PyResult, Python};
+use pyo3::{pyfunction, PyAny, PyResult, Python};
 
+use super::runtime::{future_into_py, runtime};
 use super::util::create_date;
 
 #[pyfunction]
@@ -11,17 +12,32 @@ pub fn list_records(
     month: u32,
     day: u32,
 ) -> PyResult<Vec<String>> {
-    let result = py.allow_threads(move || list_records_impl(site, year, month, day));
+    let result =
+        py.allow_threads(move || runtime().block_on(list_records_impl(site, year, month, day)));
 
     Ok(result)
 }
 
-/// Lists records from a particular site and date
-fn list_records_impl(site: String, year: i32, month: u32, day: u32) -> Vec<String> {
-    let rt = tokio::runtime::Runtime::new().unwrap();
+#[pyfunction]
+pub fn list_records_async<'py>(
+    py: Python<'py>,
+    site: String,
+    year: i32,
+    month: u32,
+    day: u32,
+) -> PyResult<&'py PyAny> {
+    future_into_py(py, list_records_impl(site, year, month, day))
+}
 
-    let files = rt
-        .block_on(async { list_files(&site, &create_date(year, month, day)).await })
+/// Lists records from a particular site and date
+pub(crate) async fn list_records_impl(
+    site: String,
+    year: i32,
+    month: u32,
+    day: u32,
+) -> Vec<String> {
+    let files = list_files(&site, &create_date(year, month, day))
+        .await
         .expect("Should download without error");
 
     let keys = files.iter().map(|id| String::from(id.name())).collect();
@@ -2,7 +2,13 @@ pub mod convert;
 pub mod convert_chunks;
 pub mod download_chunk;
 pub mod download_nexrad_file;
+pub mod find_volume_at;
 pub mod get_latest_volume;
 pub mod list_chunks_in_volume;
 pub mod list_records;
+pub mod read_nexrad_file;
+pub mod runtime;
+pub mod stack_qvps;
+pub mod thread_pool;
 pub mod util;
+pub mod write_archive;
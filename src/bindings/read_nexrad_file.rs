@@ -0,0 +1,39 @@
+use nexrad_data::volume::File;
+use pyo3::{pyfunction, PyAny, PyResult, Python};
+
+use super::convert::convert_nexrad_file_with_options;
+use super::runtime::try_future_into_py;
+use crate::pymodel::{py_convert_options::PyConvertOptions, py_level2_file::PyLevel2File};
+
+#[pyfunction]
+#[pyo3(signature = (path, options = None))]
+pub fn read_nexrad_file(
+    py: Python,
+    path: String,
+    options: Option<PyConvertOptions>,
+) -> PyResult<PyLevel2File> {
+    let options = options.unwrap_or_default();
+    py.allow_threads(move || read_nexrad_file_impl(path, &options))
+}
+
+#[pyfunction]
+#[pyo3(signature = (path, options = None))]
+pub fn read_nexrad_file_async<'py>(
+    py: Python<'py>,
+    path: String,
+    options: Option<PyConvertOptions>,
+) -> PyResult<&'py PyAny> {
+    let options = options.unwrap_or_default();
+    try_future_into_py(py, async move {
+        tokio::task::spawn_blocking(move || read_nexrad_file_impl(path, &options))
+            .await
+            .expect("Should read without panicking")
+    })
+}
+
+/// Reads and decodes a nexrad file from disk
+fn read_nexrad_file_impl(path: String, options: &PyConvertOptions) -> PyResult<PyLevel2File> {
+    let f = File::new(std::fs::read(path)?);
+
+    Ok(convert_nexrad_file_with_options(f.records(), options))
+}
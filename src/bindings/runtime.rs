@@ -0,0 +1,78 @@
+use std::future::Future;
+use std::sync::OnceLock;
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use tokio::runtime::Runtime;
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Returns the process-wide tokio runtime shared by all of the bindings
+pub fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| Runtime::new().expect("Should create tokio runtime"))
+}
+
+/// Spawns the future on the shared runtime and returns an asyncio future,
+/// bound to the running event loop, which resolves with its output.
+/// A panic in the future is raised as a RuntimeError.
+pub(crate) fn future_into_py<F, T>(py: Python<'_>, future: F) -> PyResult<&PyAny>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: IntoPy<PyObject> + Send + 'static,
+{
+    try_future_into_py(py, async move { Ok(future.await) })
+}
+
+/// As future_into_py, for futures which can fail. An error is raised from
+/// the asyncio future.
+pub(crate) fn try_future_into_py<F, T>(py: Python<'_>, future: F) -> PyResult<&PyAny>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: IntoPy<PyObject> + Send + 'static,
+{
+    let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+    let py_future = event_loop.call_method0("create_future")?;
+
+    let event_loop: PyObject = event_loop.into();
+    let result_future: PyObject = py_future.into();
+
+    runtime().spawn(async move {
+        let result = tokio::spawn(future).await;
+
+        Python::with_gil(|py| {
+            let (method, value) = match result {
+                Ok(Ok(value)) => ("set_result", value.into_py(py)),
+                Ok(Err(err)) => ("set_exception", err.into_py(py)),
+                Err(err) => (
+                    "set_exception",
+                    PyRuntimeError::new_err(err.to_string()).into_py(py),
+                ),
+            };
+
+            let resolve = wrap_pyfunction!(resolve_future, py).expect("Should wrap callback");
+
+            // This only fails if the event loop has been closed, in which
+            // case there is nobody left to receive the result
+            let _ = event_loop.call_method1(
+                py,
+                "call_soon_threadsafe",
+                (resolve, result_future, method, value),
+            );
+        });
+    });
+
+    Ok(py_future)
+}
+
+// Runs on the event loop thread. The future may have been cancelled
+// while the task was running, in which case the result is dropped.
+#[pyfunction]
+fn resolve_future(future: &PyAny, method: &str, value: &PyAny) -> PyResult<()> {
+    if future.call_method0("done")?.is_true()? {
+        return Ok(());
+    }
+
+    future.call_method1(method, (value,))?;
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::products::qvp::stack_profiles;
+use crate::pymodel::py_qvp::PyQvp;
+use crate::pymodel::py_time_height::PyTimeHeight;
+
+#[pyfunction]
+pub fn stack_qvps(qvps: Vec<PyQvp>) -> PyResult<PyTimeHeight> {
+    if qvps.iter().any(|qvp| qvp.product != qvps[0].product) {
+        return Err(PyValueError::new_err("Profiles are of different products"));
+    }
+
+    let profiles: Vec<_> = qvps.iter().map(PyQvp::profile).collect();
+
+    stack_profiles(&profiles)
+        .map(PyTimeHeight::new)
+        .map_err(PyValueError::new_err)
+}
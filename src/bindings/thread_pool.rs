@@ -0,0 +1,42 @@
+use std::sync::{Arc, RwLock};
+
+use pyo3::pyfunction;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+static THREAD_POOL: RwLock<Option<Arc<ThreadPool>>> = RwLock::new(None);
+
+/// Returns the thread pool used to process sweeps in parallel, creating
+/// one with a thread per core if the thread count has not been set
+pub fn thread_pool() -> Arc<ThreadPool> {
+    if let Some(pool) = THREAD_POOL.read().unwrap().as_ref() {
+        return pool.clone();
+    }
+
+    THREAD_POOL
+        .write()
+        .unwrap()
+        .get_or_insert_with(|| build_thread_pool(0))
+        .clone()
+}
+
+fn build_thread_pool(num_threads: usize) -> Arc<ThreadPool> {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .thread_name(|index| format!("pynexrad-{}", index))
+        .build()
+        .expect("Should create thread pool");
+
+    Arc::new(pool)
+}
+
+/// Sets the number of threads used to process sweeps, where 0 uses
+/// one thread per core. Conversions already running keep their pool.
+#[pyfunction]
+pub fn set_thread_count(num_threads: usize) {
+    *THREAD_POOL.write().unwrap() = Some(build_thread_pool(num_threads));
+}
+
+#[pyfunction]
+pub fn get_thread_count() -> usize {
+    thread_pool().current_num_threads()
+}
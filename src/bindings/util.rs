@@ -1,6 +1,19 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 
 /// Creates a date from year, month, and day
 pub fn create_date(year: i32, month: u32, day: u32) -> NaiveDate {
     NaiveDate::from_ymd_opt(year, month, day).expect("date is valid")
 }
+
+/// Parses the scan start time from an archive record name
+/// such as KDMX20220305_233003_V06. Returns None for records
+/// which are not volume scans, such as the _MDM model data files.
+pub fn parse_record_time(name: &str) -> Option<DateTime<Utc>> {
+    if name.len() < 19 || name.ends_with("_MDM") {
+        return None;
+    }
+
+    let date_time = NaiveDateTime::parse_from_str(name.get(4..19)?, "%Y%m%d_%H%M%S").ok()?;
+
+    Some(date_time.and_utc())
+}
@@ -0,0 +1,107 @@
+use nexrad_data::aws::realtime::Chunk;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyBytes;
+use pyo3::{pyfunction, PyAny, PyObject, PyResult, Python};
+
+use super::runtime::try_future_into_py;
+use crate::pymodel::py_chunk::PyChunk;
+
+#[pyfunction]
+pub fn chunks_to_archive(py: Python, chunks: Vec<PyChunk>) -> PyResult<PyObject> {
+    let data = py
+        .allow_threads(move || chunks_to_archive_impl(chunks))
+        .map_err(PyValueError::new_err)?;
+
+    Ok(PyBytes::new(py, &data).into())
+}
+
+#[pyfunction]
+pub fn write_archive(py: Python, chunks: Vec<PyChunk>, path: String) -> PyResult<()> {
+    py.allow_threads(move || write_archive_impl(chunks, path))
+}
+
+#[pyfunction]
+pub fn write_archive_async<'py>(
+    py: Python<'py>,
+    chunks: Vec<PyChunk>,
+    path: String,
+) -> PyResult<&'py PyAny> {
+    try_future_into_py(py, async move {
+        tokio::task::spawn_blocking(move || write_archive_impl(chunks, path))
+            .await
+            .expect("Should write without panicking")
+    })
+}
+
+fn write_archive_impl(chunks: Vec<PyChunk>, path: String) -> PyResult<()> {
+    let data = chunks_to_archive_impl(chunks).map_err(PyValueError::new_err)?;
+    std::fs::write(&path, data)?;
+
+    Ok(())
+}
+
+/// Reassembles the chunks of a single realtime volume into an Archive II
+/// file. The start chunk already holds the volume header and the metadata
+/// record, and every other chunk is a single compressed LDM record, so the
+/// file is the chunk data concatenated in sequence order. The chunks after
+/// the start chunk must follow it without gaps, though the volume may still
+/// be missing its last chunks.
+pub(crate) fn chunks_to_archive_impl(chunks: Vec<PyChunk>) -> Result<Vec<u8>, String> {
+    let mut chunks = chunks;
+    chunks.sort_by_key(|chunk| chunk_sequence(&chunk.chunk_identifier.name).unwrap_or(u32::MAX));
+    chunks.dedup_by(|a, b| a.chunk_identifier.name == b.chunk_identifier.name);
+
+    let first = chunks.first().ok_or("No chunks to archive")?;
+    let volume = first.chunk_identifier.volume;
+
+    let mut previous: Option<u32> = None;
+    for (index, chunk) in chunks.iter().enumerate() {
+        if chunk.chunk_identifier.volume != volume {
+            return Err(format!(
+                "Chunk {} is from volume {}, expected volume {}",
+                chunk.chunk_identifier.name, chunk.chunk_identifier.volume, volume
+            ));
+        }
+
+        let is_start = matches!(
+            Chunk::new(chunk.data.clone()).map_err(|_| format!(
+                "Chunk {} could not be decoded",
+                chunk.chunk_identifier.name
+            ))?,
+            Chunk::Start(_)
+        );
+
+        if is_start != (index == 0) {
+            return Err(format!(
+                "Chunk {} is out of place, the volume must have exactly one start chunk",
+                chunk.chunk_identifier.name
+            ));
+        }
+
+        let sequence = chunk_sequence(&chunk.chunk_identifier.name).ok_or_else(|| {
+            format!(
+                "Chunk {} has no sequence number",
+                chunk.chunk_identifier.name
+            )
+        })?;
+        if let Some(previous) = previous {
+            if previous.checked_add(1) != Some(sequence) {
+                return Err(format!(
+                    "Chunk {} does not follow chunk {}, the volume is missing chunks",
+                    sequence, previous
+                ));
+            }
+        }
+        previous = Some(sequence);
+    }
+
+    Ok(chunks.iter().flat_map(|chunk| chunk.data.clone()).collect())
+}
+
+// Chunk names look like 20240521-224629-001-S, where the third
+// field is the position of the chunk within the volume
+fn chunk_sequence(name: &str) -> Option<u32> {
+    name.split('-')
+        .nth(2)
+        .and_then(|sequence| sequence.parse().ok())
+}
@@ -3,11 +3,12 @@ use crate::model::sweep_data::SweepData;
 pub(crate) fn find_edges(
     labels: &Vec<Vec<i32>>,
     data: &SweepData,
+    wraps: bool,
     skip_between_rays: i32,
     skip_along_ray: i32,
 ) -> ((Vec<i32>, Vec<i32>), Vec<i32>, (Vec<f32>, Vec<f32>)) {
     let ((index1, index2), (vel1, vel2)) =
-        edge_finder(labels, data, skip_between_rays, skip_along_ray);
+        edge_finder(labels, data, wraps, skip_between_rays, skip_along_ray);
 
     // Return early if edges were not found
     if vel1.is_empty() {
@@ -69,9 +70,11 @@ pub(crate) fn find_edges(
     );
 }
 
+// Only a complete sweep wraps, so the first and last radials are neighbors
 fn edge_finder(
     labels: &Vec<Vec<i32>>,
     data: &SweepData,
+    wraps: bool,
     skip_between_rays: i32,
     skip_along_ray: i32,
 ) -> ((Vec<i32>, Vec<i32>), (Vec<f32>, Vec<f32>)) {
@@ -94,65 +97,81 @@ fn edge_finder(
             // left
             {
                 let mut x_check = x_index - 1;
-                if x_check == -1 {
+                if x_check == -1 && wraps {
                     x_check = right;
                 }
 
-                let mut neighbor = labels[x_check as usize][y_index as usize];
-                let mut nvel =
-                    data.get_value_with_fallback(x_check as usize, y_index as usize, 0.0);
-
-                // If the left side gate is masked, keep looking to the left
-                // until we find a valid gate or reach the maximum gap size
-                if neighbor == 0 {
-                    for _ in 0..skip_between_rays {
-                        x_check -= 1;
-                        if x_check == -1 {
-                            x_check = right;
-                        }
+                if x_check != -1 {
+                    let mut neighbor = labels[x_check as usize][y_index as usize];
+                    let mut nvel =
+                        data.get_value_with_fallback(x_check as usize, y_index as usize, 0.0);
+
+                    // If the left side gate is masked, keep looking to the left
+                    // until we find a valid gate or reach the maximum gap size
+                    if neighbor == 0 {
+                        for _ in 0..skip_between_rays {
+                            x_check -= 1;
+                            if x_check == -1 {
+                                if !wraps {
+                                    break;
+                                }
+                                x_check = right;
+                            }
 
-                        neighbor = labels[x_check as usize][y_index as usize];
-                        nvel =
-                            data.get_value_with_fallback(x_check as usize, y_index as usize, 0.0);
-                        if neighbor != 0 {
-                            break;
+                            neighbor = labels[x_check as usize][y_index as usize];
+                            nvel = data.get_value_with_fallback(
+                                x_check as usize,
+                                y_index as usize,
+                                0.0,
+                            );
+                            if neighbor != 0 {
+                                break;
+                            }
                         }
                     }
-                }
 
-                collector.add_edge(label, neighbor, vel, nvel);
+                    collector.add_edge(label, neighbor, vel, nvel);
+                }
             }
 
             // right
             {
                 let mut x_check = x_index + 1;
-                if x_check == right + 1 {
+                if x_check == right + 1 && wraps {
                     x_check = 0;
                 }
 
-                let mut neighbor = labels[x_check as usize][y_index as usize];
-                let mut nvel =
-                    data.get_value_with_fallback(x_check as usize, y_index as usize, 0.0);
-
-                // If the right side gate is masked, keep looking to the right
-                // until we find a valid gate or reach the maximum gap size
-                if neighbor == 0 {
-                    for _ in 0..skip_between_rays {
-                        x_check += 1;
-                        if x_check == right + 1 {
-                            x_check = 0;
-                        }
+                if x_check != right + 1 {
+                    let mut neighbor = labels[x_check as usize][y_index as usize];
+                    let mut nvel =
+                        data.get_value_with_fallback(x_check as usize, y_index as usize, 0.0);
+
+                    // If the right side gate is masked, keep looking to the right
+                    // until we find a valid gate or reach the maximum gap size
+                    if neighbor == 0 {
+                        for _ in 0..skip_between_rays {
+                            x_check += 1;
+                            if x_check == right + 1 {
+                                if !wraps {
+                                    break;
+                                }
+                                x_check = 0;
+                            }
 
-                        neighbor = labels[x_check as usize][y_index as usize];
-                        nvel =
-                            data.get_value_with_fallback(x_check as usize, y_index as usize, 0.0);
-                        if neighbor != 0 {
-                            break;
+                            neighbor = labels[x_check as usize][y_index as usize];
+                            nvel = data.get_value_with_fallback(
+                                x_check as usize,
+                                y_index as usize,
+                                0.0,
+                            );
+                            if neighbor != 0 {
+                                break;
+                            }
                         }
                     }
-                }
 
-                collector.add_edge(label, neighbor, vel, nvel);
+                    collector.add_edge(label, neighbor, vel, nvel);
+                }
             }
 
             // top
@@ -8,5 +8,4 @@ pub mod find_edges;
 pub mod find_regions;
 pub mod interval_limits;
 pub mod region_dealias;
-pub mod region_sizes;
 pub mod region_tracker;
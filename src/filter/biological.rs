@@ -0,0 +1,217 @@
+use crate::model::sweep::{RadialIndex, Sweep};
+use crate::model::sweep_data::SweepData;
+use crate::model::volume::Volume;
+use rayon::prelude::*;
+
+// Thresholds for the biological scatter and chaff classifier. Each test
+// scores a gate from 0 (meteorological) to 1 (non-meteorological) with a
+// linear ramp between its two thresholds.
+#[derive(Clone, Debug)]
+pub(crate) struct BiologicalFilterParams {
+    // Number of gates either side of a gate used for the textures
+    pub window_half_width: usize,
+    // Correlation coefficient ramp, gates below the upper value are
+    // candidates and gates at or above it are never masked
+    pub correlation_low: f32,
+    pub correlation_high: f32,
+    // Absolute differential reflectivity ramp in dB
+    pub zdr_low: f32,
+    pub zdr_high: f32,
+    // Differential reflectivity texture (standard deviation) ramp in dB
+    pub zdr_texture_low: f32,
+    pub zdr_texture_high: f32,
+    // Differential phase texture (standard deviation) ramp in degrees,
+    // only used when use_differential_phase is set
+    pub use_differential_phase: bool,
+    pub phidp_texture_low: f32,
+    pub phidp_texture_high: f32,
+    // Weighted score at or above which a gate is masked
+    pub score_threshold: f32,
+}
+
+impl Default for BiologicalFilterParams {
+    fn default() -> Self {
+        Self {
+            window_half_width: 4,
+            correlation_low: 0.7,
+            correlation_high: 0.9,
+            zdr_low: 1.5,
+            zdr_high: 4.0,
+            zdr_texture_low: 1.0,
+            zdr_texture_high: 2.5,
+            use_differential_phase: true,
+            phidp_texture_low: 10.0,
+            phidp_texture_high: 25.0,
+            score_threshold: 0.5,
+        }
+    }
+}
+
+// Correlation coefficient is the most reliable discriminator so it counts
+// twice as much as each of the other tests
+const CORRELATION_WEIGHT: f32 = 2.0;
+
+// Gate scores of a classified sweep, with the radial and gate geometry
+// needed to apply them to the other half of a split cut
+struct Scores {
+    data: SweepData,
+    azimuths: Vec<f32>,
+    range_first: f32,
+    range_step: f32,
+}
+
+// Masks biological scatter (birds and insects) and chaff on every moment
+// of the sweeps. Gates are classified on the sweeps with dual pol data, and
+// the Doppler half of a split cut uses the classification of its partner.
+pub(crate) fn filter_biological(volume: &mut Volume, params: &BiologicalFilterParams) {
+    let classified: Vec<Option<Scores>> = volume
+        .sweeps
+        .par_iter()
+        .map(|sweep| {
+            score_sweep(sweep, params).map(|data| Scores {
+                data,
+                azimuths: sweep.azimuths.clone(),
+                range_first: sweep.range_first,
+                range_step: sweep.range_step,
+            })
+        })
+        .collect();
+
+    let cut_indices: Vec<usize> = volume.sweeps.iter().map(|sweep| sweep.cut_index).collect();
+
+    volume
+        .sweeps
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, sweep)| {
+            let source = classified[i].as_ref().or_else(|| {
+                let paired_cut = sweep.paired_cut?;
+                let partner = cut_indices.iter().position(|cut| *cut == paired_cut)?;
+                classified[partner].as_ref()
+            });
+
+            if let Some(scores) = source {
+                mask_sweep(sweep, scores, params.score_threshold);
+            }
+        });
+}
+
+// Scores each gate of a sweep with correlation coefficient and
+// differential reflectivity, or returns None if either is missing
+fn score_sweep(sweep: &Sweep, params: &BiologicalFilterParams) -> Option<SweepData> {
+    let rho = sweep.correlation_coefficient.as_ref()?;
+    let zdr = sweep.differential_reflectivity.as_ref()?;
+    let phidp = sweep
+        .differential_phase
+        .as_ref()
+        .filter(|_| params.use_differential_phase);
+
+    let mut scores = SweepData::new(rho.radials, rho.gates);
+
+    for radial in 0..rho.radials {
+        for gate in 0..rho.gates {
+            let Some(correlation) = rho.value(radial, gate) else {
+                continue;
+            };
+
+            // Meteorological echoes have high correlation, whatever the
+            // other moments look like
+            if correlation >= params.correlation_high {
+                scores.set_value(0.0, radial, gate);
+                continue;
+            }
+
+            let mut total = CORRELATION_WEIGHT
+                * descending_ramp(correlation, params.correlation_low, params.correlation_high);
+            let mut weight = CORRELATION_WEIGHT;
+
+            if let Some(value) = zdr.value(radial, gate) {
+                total += ascending_ramp(value.abs(), params.zdr_low, params.zdr_high);
+                weight += 1.0;
+            }
+
+            if let Some(texture) = zdr.texture(radial, gate, params.window_half_width) {
+                total += ascending_ramp(texture, params.zdr_texture_low, params.zdr_texture_high);
+                weight += 1.0;
+            }
+
+            let phidp_texture =
+                phidp.and_then(|phidp| phidp.texture(radial, gate, params.window_half_width));
+            if let Some(texture) = phidp_texture {
+                total +=
+                    ascending_ramp(texture, params.phidp_texture_low, params.phidp_texture_high);
+                weight += 1.0;
+            }
+
+            scores.set_value(total / weight, radial, gate);
+        }
+    }
+
+    Some(scores)
+}
+
+// Masks the gates of every moment whose score is at or above the threshold.
+// When the scores come from the paired cut, radials are matched by azimuth
+// within one azimuth step, and gates by range when the cuts space their
+// gates differently.
+fn mask_sweep(sweep: &mut Sweep, scores: &Scores, threshold: f32) {
+    let rows: Vec<Option<usize>> = if scores.azimuths == sweep.azimuths {
+        (0..scores.data.radials).map(Some).collect()
+    } else {
+        let index = RadialIndex::new(&scores.azimuths, sweep.az_step);
+        sweep
+            .azimuths
+            .iter()
+            .map(|azimuth| index.find(*azimuth))
+            .collect()
+    };
+
+    let same_gates =
+        scores.range_first == sweep.range_first && scores.range_step == sweep.range_step;
+    let range_first = sweep.range_first;
+    let range_step = sweep.range_step;
+    let column = |gate: usize| -> Option<usize> {
+        let column = if same_gates {
+            gate
+        } else {
+            let range = range_first + gate as f32 * range_step;
+            let position = ((range - scores.range_first) / scores.range_step).round();
+            if position < 0.0 {
+                return None;
+            }
+            position as usize
+        };
+
+        (column < scores.data.gates).then_some(column)
+    };
+
+    for moment in sweep.moments_mut() {
+        let Some(data) = moment.as_mut() else {
+            continue;
+        };
+
+        for (radial, row) in rows.iter().enumerate().take(data.radials) {
+            let Some(row) = *row else {
+                continue;
+            };
+
+            let (_, mask) = data.radial_mut(radial);
+            for (gate, masked) in mask.iter_mut().enumerate() {
+                let score = column(gate).and_then(|column| scores.data.value(row, column));
+                if score.is_some_and(|score| score >= threshold) {
+                    *masked = true;
+                }
+            }
+        }
+    }
+}
+
+// 0 at or below low, 1 at or above high
+fn ascending_ramp(value: f32, low: f32, high: f32) -> f32 {
+    ((value - low) / (high - low)).clamp(0.0, 1.0)
+}
+
+// 1 at or below low, 0 at or above high
+fn descending_ramp(value: f32, low: f32, high: f32) -> f32 {
+    1.0 - ascending_ramp(value, low, high)
+}
@@ -0,0 +1,314 @@
+use crate::model::sweep::{RadialIndex, Sweep};
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::{CLUTTER, REFLECTIVITY};
+use crate::model::volume::Volume;
+use rayon::prelude::*;
+
+// Thresholds for the clutter and anomalous propagation tests
+#[derive(Clone, Debug)]
+pub(crate) struct ClutterFilterParams {
+    // Number of gates either side of a gate used for texture and spin
+    pub window_half_width: usize,
+    // Mean squared reflectivity difference between neighboring gates,
+    // in dBZ^2, above which a gate looks like clutter
+    pub texture_threshold: f32,
+    // Smallest reflectivity difference in dBZ counted as a spin change
+    pub spin_change: f32,
+    // Fraction of the gate pairs in the window which change the sign of
+    // the reflectivity gradient above which a gate looks like clutter
+    pub spin_threshold: f32,
+    // Absolute radial velocity in m/s below which a gate looks like clutter
+    pub velocity_threshold: f32,
+    // Correlation coefficient below which a gate looks like clutter
+    pub correlation_threshold: f32,
+    // Fraction of the available tests which must flag a gate to mask it
+    pub score_threshold: f32,
+}
+
+impl Default for ClutterFilterParams {
+    fn default() -> Self {
+        Self {
+            window_half_width: 4,
+            texture_threshold: 45.0,
+            spin_change: 2.0,
+            spin_threshold: 0.5,
+            velocity_threshold: 1.0,
+            correlation_threshold: 0.85,
+            score_threshold: 0.6,
+        }
+    }
+}
+
+// Masks reflectivity and velocity gates which look like ground clutter or
+// anomalous propagation. Each reflectivity gate is scored by the fraction
+// of the available tests which flag it, using reflectivity texture and
+// spin, near zero velocity and low correlation coefficient when the sweep
+// has them. The surveillance half of a split cut, which has no velocity,
+// uses the velocity of its Doppler partner. The scores are kept on the
+// sweep for diagnostics.
+pub(crate) fn filter_clutter(volume: &mut Volume, params: &ClutterFilterParams) {
+    let sweeps = &volume.sweeps;
+    let scores: Vec<Option<SweepData>> = sweeps
+        .par_iter()
+        .map(|sweep| {
+            let paired = sweep
+                .paired_cut
+                .and_then(|cut| sweeps.iter().find(|other| other.cut_index == cut));
+            score_sweep(sweep, paired, params)
+        })
+        .collect();
+
+    volume
+        .sweeps
+        .par_iter_mut()
+        .zip(scores)
+        .for_each(|(sweep, scores)| {
+            if let Some(scores) = scores {
+                mask_sweep(sweep, scores, params);
+            }
+        });
+}
+
+// Velocity at the gates of a sweep, from the sweep itself or from another
+// sweep of the same elevation. Radials are matched by azimuth within one
+// azimuth step and gates by range.
+struct Velocity<'a> {
+    data: &'a SweepData,
+    // Row of the velocity for each radial of the sweep
+    rows: Vec<Option<usize>>,
+    // Velocity gate at the range of gate 0 of the sweep, and velocity gates
+    // per gate of the sweep
+    gate_offset: f32,
+    gate_scale: f32,
+}
+
+impl<'a> Velocity<'a> {
+    fn new(sweep: &Sweep, source: &'a Sweep) -> Option<Self> {
+        let data = source.velocity.as_ref()?;
+
+        let rows = if source.azimuths == sweep.azimuths {
+            (0..data.radials).map(Some).collect()
+        } else {
+            let index = RadialIndex::new(&source.azimuths, source.az_step);
+            sweep
+                .azimuths
+                .iter()
+                .map(|azimuth| index.find(*azimuth))
+                .collect()
+        };
+
+        Some(Self {
+            data,
+            rows,
+            gate_offset: (sweep.range_first - source.range_first) / source.range_step,
+            gate_scale: sweep.range_step / source.range_step,
+        })
+    }
+
+    fn value(&self, radial: usize, gate: usize) -> Option<f32> {
+        let row = self.rows.get(radial).copied().flatten()?;
+        let column = (self.gate_offset + gate as f32 * self.gate_scale).round();
+        if column < 0.0 || column >= self.data.gates as f32 {
+            return None;
+        }
+
+        self.data.value(row, column as usize)
+    }
+}
+
+// Scores each reflectivity gate of a sweep, or returns None if it has no
+// reflectivity
+fn score_sweep(
+    sweep: &Sweep,
+    paired: Option<&Sweep>,
+    params: &ClutterFilterParams,
+) -> Option<SweepData> {
+    let refl = sweep.reflectivity.as_ref()?;
+    let velocity = Velocity::new(sweep, sweep)
+        .or_else(|| paired.and_then(|paired| Velocity::new(sweep, paired)));
+
+    let mut scores = SweepData::new(refl.radials, refl.gates);
+
+    for radial in 0..refl.radials {
+        let values = refl.radial(radial);
+        let mask = refl.radial_mask(radial);
+
+        for gate in 0..refl.gates {
+            if mask[gate] {
+                continue;
+            }
+
+            let mut tests = 0;
+            let mut flagged = 0;
+
+            if let Some((texture, spin)) = texture_and_spin(values, mask, gate, params) {
+                tests += 2;
+                flagged += (texture > params.texture_threshold) as i32;
+                flagged += (spin > params.spin_threshold) as i32;
+            }
+
+            let velocity = velocity
+                .as_ref()
+                .and_then(|velocity| velocity.value(radial, gate));
+            if let Some(velocity) = velocity {
+                tests += 1;
+                flagged += (velocity.abs() < params.velocity_threshold) as i32;
+            }
+
+            let correlation = sweep
+                .correlation_coefficient
+                .as_ref()
+                .filter(|rho| gate < rho.gates)
+                .and_then(|rho| rho.value(radial, gate));
+            if let Some(correlation) = correlation {
+                tests += 1;
+                flagged += (correlation < params.correlation_threshold) as i32;
+            }
+
+            if tests > 0 {
+                scores.set_value(flagged as f32 / tests as f32, radial, gate);
+            }
+        }
+    }
+
+    Some(scores)
+}
+
+// Masks the reflectivity and velocity gates of a sweep whose score is at or
+// above the threshold
+fn mask_sweep(sweep: &mut Sweep, scores: SweepData, params: &ClutterFilterParams) {
+    for product in [&mut sweep.reflectivity, &mut sweep.velocity] {
+        let Some(data) = product.as_mut() else {
+            continue;
+        };
+
+        for radial in 0..scores.radials {
+            let (_, mask) = data.radial_mut(radial);
+
+            // Velocity usually has fewer gates than reflectivity
+            for (gate, score) in scores.radial(radial).iter().enumerate().take(mask.len()) {
+                if !scores.get_mask(radial, gate) && *score >= params.score_threshold {
+                    mask[gate] = true;
+                }
+            }
+        }
+    }
+
+    // Only list the scores alongside the sweeps listed as reflectivity
+    sweep.clutter = Some(scores);
+    if sweep.sweep_type & REFLECTIVITY != 0 {
+        sweep.sweep_type |= CLUTTER;
+    }
+}
+
+// Texture is the mean squared difference between neighboring gates in the
+// window, and spin is the fraction of neighboring differences which reverse
+// the sign of the previous difference with both larger than spin_change.
+// Returns None if the window has too few unmasked neighbors.
+fn texture_and_spin(
+    values: &[f32],
+    mask: &[bool],
+    gate: usize,
+    params: &ClutterFilterParams,
+) -> Option<(f32, f32)> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let start = gate.saturating_sub(params.window_half_width);
+    let end = usize::min(gate + params.window_half_width, values.len() - 1);
+
+    let mut squared_sum = 0.0;
+    let mut differences = 0;
+    let mut spin_changes = 0;
+    let mut previous: Option<f32> = None;
+
+    for g in start..end {
+        if mask[g] || mask[g + 1] {
+            previous = None;
+            continue;
+        }
+
+        let difference = values[g + 1] - values[g];
+        squared_sum += difference * difference;
+        differences += 1;
+
+        if difference.abs() >= params.spin_change {
+            if let Some(previous) = previous {
+                if previous.signum() != difference.signum() {
+                    spin_changes += 1;
+                }
+            }
+            previous = Some(difference);
+        }
+    }
+
+    if differences < 2 {
+        return None;
+    }
+
+    Some((
+        squared_sum / differences as f32,
+        spin_changes as f32 / (differences - 1) as f32,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use nexrad_decode::messages::volume_coverage_pattern::WaveformType;
+
+    use super::*;
+
+    #[test]
+    fn uses_velocity_of_paired_cut() {
+        let az_step = 1.0_f32.to_radians();
+        let gates = 40;
+
+        // Smooth reflectivity, which neither texture nor spin flags
+        let azimuths: Vec<f32> = (0..360).map(|d| (d as f32 + 0.5).to_radians()).collect();
+        let mut surveillance = Sweep::synthetic(WaveformType::CS, azimuths, az_step, 2.0, gates);
+        let mut reflectivity = SweepData::new(360, gates);
+        for radial in 0..360 {
+            for gate in 0..gates {
+                reflectivity.set_value(30.0, radial, gate);
+            }
+        }
+        surveillance.reflectivity = Some(reflectivity);
+        surveillance.sweep_type = REFLECTIVITY;
+        surveillance.paired_cut = Some(1);
+
+        // The Doppler cut has offset radials and gates starting further
+        // out, with zero velocity in the first half of the sweep
+        let azimuths: Vec<f32> = (0..360).map(|d| (d as f32 + 0.8).to_radians()).collect();
+        let mut doppler = Sweep::synthetic(WaveformType::CDW, azimuths, az_step, 3.0, gates);
+        let mut velocity = SweepData::new(360, gates);
+        for radial in 0..360 {
+            for gate in 0..gates {
+                velocity.set_value(if radial < 180 { 0.0 } else { 20.0 }, radial, gate);
+            }
+        }
+        doppler.velocity = Some(velocity);
+        doppler.cut_index = 1;
+        doppler.paired_cut = Some(0);
+
+        let mut volume = Volume {
+            sweeps: vec![surveillance, doppler],
+            vcp: 212,
+            site: None,
+            melting_layer: None,
+            storm_cells: None,
+            rotations: Vec::new(),
+            storm_motion: None,
+        };
+        filter_clutter(&mut volume, &ClutterFilterParams::default());
+
+        // Texture, spin and velocity are tested where the Doppler cut has
+        // gates, 4 gates further out
+        let scores = volume.sweeps[0].clutter.as_ref().unwrap();
+        for gate in 0..gates {
+            let near_zero = if gate >= 4 { 1.0 / 3.0 } else { 0.0 };
+            assert_eq!(scores.value(10, gate), Some(near_zero), "gate {}", gate);
+            assert_eq!(scores.value(270, gate), Some(0.0), "gate {}", gate);
+        }
+    }
+}
@@ -1,2 +1,4 @@
+pub mod biological;
+pub mod clutter;
 pub mod despeckle;
 pub mod velocity_ref_threshold;
@@ -1,31 +1,32 @@
+use crate::model::sweep::Sweep;
 use crate::model::volume::Volume;
+use rayon::prelude::*;
 
 // Masks all velocity values where the corresponding reflectivity value is
 // less than the specified threshold
 pub(crate) fn apply_reflectivity_threshold(volume: &mut Volume, threshold: f32) {
-    for sweep in volume.sweeps.iter_mut() {
-        if sweep.velocity.is_none() {
-            continue;
-        }
+    volume
+        .sweeps
+        .par_iter_mut()
+        .for_each(|sweep| threshold_sweep(sweep, threshold));
+}
 
-        let vel = sweep.velocity.as_mut().unwrap();
-        let refl = sweep.reflectivity.as_ref().unwrap();
+fn threshold_sweep(sweep: &mut Sweep, threshold: f32) {
+    if sweep.velocity.is_none() {
+        return;
+    }
 
-        for radial in 0..vel.radials {
-            for gate in 0..vel.gates {
-                if vel.get_mask(radial, gate) {
-                    continue;
-                }
+    let vel = sweep.velocity.as_mut().unwrap();
+    let refl = sweep.reflectivity.as_ref().unwrap();
 
-                if refl.get_mask(radial, gate) {
-                    vel.set_mask(radial, gate);
-                    continue;
-                }
+    for radial in 0..vel.radials {
+        let refl_values = refl.radial(radial);
+        let refl_mask = refl.radial_mask(radial);
+        let (_, vel_mask) = vel.radial_mut(radial);
 
-                if refl.get_value(radial, gate) < threshold {
-                    vel.set_mask(radial, gate);
-                    continue;
-                }
+        for gate in 0..vel_mask.len() {
+            if refl_mask[gate] || refl_values[gate] < threshold {
+                vel_mask[gate] = true;
             }
         }
     }
@@ -0,0 +1,359 @@
+// Connected component labelling for sweep data, using a two pass
+// union-find over the gates. For a complete sweep the radials wrap around
+// in azimuth, so the last radial is adjacent to the first, while gates never
+// wrap in range.
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Connectivity {
+    // Gates touch along the radial and in azimuth
+    Four,
+    // Gates also touch diagonally
+    Eight,
+}
+
+pub(crate) struct Components {
+    // Label for each gate, 0 for gates without a class, otherwise 1..=count
+    pub labels: Vec<Vec<i32>>,
+    // Number of gates in each component, sizes[label - 1]
+    pub sizes: Vec<i32>,
+}
+
+// Labels the groups of touching gates which share the same class. Gates
+// where `class` returns None are left unlabelled. Labels are numbered in
+// the order their first gate appears scanning radial by radial. `wraps`
+// joins the last radial to the first, and should only be set for sweeps
+// which cover the full circle.
+pub(crate) fn label_components<F>(
+    radials: usize,
+    gates: usize,
+    connectivity: Connectivity,
+    wraps: bool,
+    class: F,
+) -> Components
+where
+    F: Fn(usize, usize) -> Option<usize>,
+{
+    let mut classes = Vec::with_capacity(radials * gates);
+    for radial in 0..radials {
+        for gate in 0..gates {
+            classes.push(class(radial, gate));
+        }
+    }
+
+    // First pass, give each gate a provisional label and record which
+    // provisional labels belong together. 0 is the unlabelled parent.
+    let mut provisional = vec![0; radials * gates];
+    let mut parents: Vec<usize> = vec![0];
+
+    for radial in 0..radials {
+        for gate in 0..gates {
+            let index = radial * gates + gate;
+            let Some(gate_class) = classes[index] else {
+                continue;
+            };
+
+            let mut label = 0;
+            for neighbor in previous_neighbors(radial, gate, gates, connectivity)
+                .into_iter()
+                .flatten()
+            {
+                if classes[neighbor] != Some(gate_class) {
+                    continue;
+                }
+
+                if label == 0 {
+                    label = provisional[neighbor];
+                } else {
+                    union(&mut parents, label, provisional[neighbor]);
+                }
+            }
+
+            if label == 0 {
+                label = parents.len();
+                parents.push(label);
+            }
+
+            provisional[index] = label;
+        }
+    }
+
+    // Join components across the seam between the last and first radials.
+    // With two or fewer radials they are already neighbors in the first pass.
+    if wraps && radials > 2 {
+        let last = (radials - 1) * gates;
+        for gate in 0..gates {
+            let Some(gate_class) = classes[last + gate] else {
+                continue;
+            };
+
+            let mut first_gates = vec![gate];
+            if connectivity == Connectivity::Eight {
+                if gate > 0 {
+                    first_gates.push(gate - 1);
+                }
+                if gate + 1 < gates {
+                    first_gates.push(gate + 1);
+                }
+            }
+
+            for first in first_gates {
+                if classes[first] == Some(gate_class) {
+                    union(&mut parents, provisional[last + gate], provisional[first]);
+                }
+            }
+        }
+    }
+
+    // Second pass, resolve the final labels and count the component sizes
+    let mut final_labels = vec![0; parents.len()];
+    let mut labels = vec![vec![0; gates]; radials];
+    let mut sizes = Vec::new();
+
+    for radial in 0..radials {
+        for gate in 0..gates {
+            let label = provisional[radial * gates + gate];
+            if label == 0 {
+                continue;
+            }
+
+            let root = find(&mut parents, label);
+            if final_labels[root] == 0 {
+                sizes.push(0);
+                final_labels[root] = sizes.len() as i32;
+            }
+
+            let label = final_labels[root];
+            labels[radial][gate] = label;
+            sizes[label as usize - 1] += 1;
+        }
+    }
+
+    Components { labels, sizes }
+}
+
+// Indices of the neighbors which have already been visited by the first pass
+fn previous_neighbors(
+    radial: usize,
+    gate: usize,
+    gates: usize,
+    connectivity: Connectivity,
+) -> [Option<usize>; 4] {
+    let mut neighbors = [None; 4];
+
+    if gate > 0 {
+        neighbors[0] = Some(radial * gates + gate - 1);
+    }
+
+    if radial > 0 {
+        let previous = (radial - 1) * gates;
+        neighbors[1] = Some(previous + gate);
+
+        if connectivity == Connectivity::Eight {
+            if gate > 0 {
+                neighbors[2] = Some(previous + gate - 1);
+            }
+            if gate + 1 < gates {
+                neighbors[3] = Some(previous + gate + 1);
+            }
+        }
+    }
+
+    neighbors
+}
+
+fn find(parents: &mut [usize], mut label: usize) -> usize {
+    while parents[label] != label {
+        parents[label] = parents[parents[label]];
+        label = parents[label];
+    }
+
+    label
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let a = find(parents, a);
+    let b = find(parents, b);
+
+    if a < b {
+        parents[b] = a;
+    } else if b < a {
+        parents[a] = b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    // Labels a grid given as strings, where '.' has no class and any other
+    // character is a class
+    fn label_grid(rows: &[&str], connectivity: Connectivity, wraps: bool) -> Components {
+        let cells: Vec<Vec<u8>> = rows.iter().map(|row| row.bytes().collect()).collect();
+
+        label_components(
+            cells.len(),
+            cells[0].len(),
+            connectivity,
+            wraps,
+            |radial, gate| match cells[radial][gate] {
+                b'.' => None,
+                class => Some(class as usize),
+            },
+        )
+    }
+
+    // The breadth first flood fill which label_components replaced, with
+    // four connectivity and the radials always wrapping
+    fn flood_fill_labels<F>(radials: usize, gates: usize, class: F) -> Components
+    where
+        F: Fn(usize, usize) -> Option<usize>,
+    {
+        let mut labels = vec![vec![0; gates]; radials];
+        let mut sizes = Vec::new();
+
+        for radial in 0..radials {
+            for gate in 0..gates {
+                let Some(seed_class) = class(radial, gate) else {
+                    continue;
+                };
+                if labels[radial][gate] != 0 {
+                    continue;
+                }
+
+                let label = sizes.len() as i32 + 1;
+                let mut size = 0;
+
+                let mut queue = VecDeque::from([(radial, gate)]);
+                while let Some((r, g)) = queue.pop_front() {
+                    if labels[r][g] != 0 || class(r, g) != Some(seed_class) {
+                        continue;
+                    }
+
+                    labels[r][g] = label;
+                    size += 1;
+
+                    queue.push_back(((r + radials - 1) % radials, g));
+                    queue.push_back(((r + 1) % radials, g));
+                    if g > 0 {
+                        queue.push_back((r, g - 1));
+                    }
+                    if g + 1 < gates {
+                        queue.push_back((r, g + 1));
+                    }
+                }
+
+                sizes.push(size);
+            }
+        }
+
+        Components { labels, sizes }
+    }
+
+    #[test]
+    fn joins_across_the_seam_when_wrapping() {
+        let rows = ["a..", "...", "...", "a.."];
+
+        let wrapped = label_grid(&rows, Connectivity::Four, true);
+        assert_eq!(wrapped.labels[0][0], wrapped.labels[3][0]);
+        assert_eq!(wrapped.sizes, vec![2]);
+
+        let unwrapped = label_grid(&rows, Connectivity::Four, false);
+        assert_ne!(unwrapped.labels[0][0], unwrapped.labels[3][0]);
+        assert_eq!(unwrapped.sizes, vec![1, 1]);
+    }
+
+    #[test]
+    fn joins_diagonals_across_the_seam_with_eight_connectivity() {
+        let rows = [".a.", "...", "...", "a.."];
+
+        let four = label_grid(&rows, Connectivity::Four, true);
+        assert_eq!(four.sizes, vec![1, 1]);
+
+        let eight = label_grid(&rows, Connectivity::Eight, true);
+        assert_eq!(eight.sizes, vec![2]);
+    }
+
+    #[test]
+    fn only_eight_connectivity_joins_diagonal_neighbors() {
+        let rows = ["a...", ".a..", "..a.", "...."];
+
+        let four = label_grid(&rows, Connectivity::Four, true);
+        assert_eq!(four.sizes, vec![1, 1, 1]);
+        assert_eq!(four.labels[0][0], 1);
+        assert_eq!(four.labels[1][1], 2);
+        assert_eq!(four.labels[2][2], 3);
+
+        let eight = label_grid(&rows, Connectivity::Eight, true);
+        assert_eq!(eight.sizes, vec![3]);
+        assert_eq!(eight.labels[2][2], 1);
+    }
+
+    #[test]
+    fn keeps_classes_apart() {
+        let rows = ["aab", "abb", "...", "..."];
+
+        let components = label_grid(&rows, Connectivity::Eight, true);
+        assert_eq!(components.sizes, vec![3, 3]);
+        assert_eq!(components.labels[0][2], 2);
+        assert_eq!(components.labels[1][0], 1);
+    }
+
+    #[test]
+    fn matches_flood_fill_on_noise() {
+        let radials = 360;
+        let gates = 240;
+
+        // Deterministic noise with a third of the gates masked, so there
+        // are many small and a few large components
+        let mut state: u32 = 12345;
+        let classes: Vec<Option<usize>> = (0..radials * gates)
+            .map(|_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                match state >> 30 {
+                    0 => None,
+                    class => Some(class as usize % 2),
+                }
+            })
+            .collect();
+        let class = |radial: usize, gate: usize| classes[radial * gates + gate];
+
+        let expected = flood_fill_labels(radials, gates, class);
+        let components = label_components(radials, gates, Connectivity::Four, true, class);
+
+        assert_eq!(components.labels, expected.labels);
+        assert_eq!(components.sizes, expected.sizes);
+    }
+
+    // Needs the volume downloaded by `cargo run --example download_nexrad`
+    #[test]
+    #[ignore]
+    fn matches_flood_fill_on_a_real_sweep() {
+        use crate::model::volume::Volume;
+        use nexrad_data::volume::File;
+
+        let bytes = std::fs::read("examples/KDMX20240521_224629_V06").expect("file exists");
+        let volume = Volume::new(File::new(bytes).records());
+
+        for sweep in volume.sweeps.iter() {
+            let Some(velocity) = sweep.velocity.as_ref() else {
+                continue;
+            };
+
+            let class = |radial, gate| (!velocity.get_mask(radial, gate)).then_some(0);
+
+            let expected = flood_fill_labels(velocity.radials, velocity.gates, class);
+            let components = label_components(
+                velocity.radials,
+                velocity.gates,
+                Connectivity::Four,
+                true,
+                class,
+            );
+
+            assert_eq!(components.labels, expected.labels);
+            assert_eq!(components.sizes, expected.sizes);
+        }
+    }
+}
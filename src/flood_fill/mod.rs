@@ -1,2 +1 @@
-pub mod flood_fill;
-pub mod flood_filler;
+pub mod label_components;
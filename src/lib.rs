@@ -3,5 +3,7 @@ pub mod dealias_region;
 pub mod filter;
 pub mod flood_fill;
 pub mod model;
+pub mod products;
 pub mod pymodel;
 pub mod pynexrad;
+pub mod render;
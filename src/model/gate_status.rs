@@ -0,0 +1,5 @@
+pub type GateStatus = u8;
+
+pub const GATE_VALID: GateStatus = 0;
+pub const GATE_NO_DATA: GateStatus = 1;
+pub const GATE_RANGE_FOLDED: GateStatus = 2;
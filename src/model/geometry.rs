@@ -0,0 +1,26 @@
+// Beam propagation with the standard 4/3 effective earth radius model.
+// Ranges and heights are in km and elevations are in radians.
+
+pub const EARTH_RADIUS: f32 = 6371.0;
+pub const EFFECTIVE_EARTH_RADIUS: f32 = EARTH_RADIUS * 4.0 / 3.0;
+
+// Height of the beam center above the antenna at a slant range
+pub fn beam_height(range: f32, elevation: f32) -> f32 {
+    let r = EFFECTIVE_EARTH_RADIUS;
+    (range * range + r * r + 2.0 * range * r * elevation.sin()).sqrt() - r
+}
+
+// Distance along the earth's surface from the radar to the point below
+// the beam center at a slant range
+pub fn ground_range(range: f32, elevation: f32) -> f32 {
+    let r = EFFECTIVE_EARTH_RADIUS;
+    r * (range * elevation.cos() / (r + beam_height(range, elevation))).asin()
+}
+
+// Slant range at which the beam center is above a point at a distance
+// along the earth's surface, the inverse of ground_range
+pub fn slant_range(ground_range: f32, elevation: f32) -> f32 {
+    let r = EFFECTIVE_EARTH_RADIUS;
+    let angle = ground_range / r;
+    r * angle.sin() / (elevation + angle).cos()
+}
@@ -0,0 +1,79 @@
+use std::f32::consts::PI;
+
+use crate::model::geometry::slant_range;
+use crate::model::sweep::{RadialIndex, Sweep};
+use crate::model::sweep_data::SweepData;
+
+// Square cartesian grid centered on the radar. Cells are stored row-major
+// with row 0 along the northern edge and column 0 along the western edge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grid {
+    // Number of cells along each side
+    pub size: usize,
+    // Width of each cell in km
+    pub spacing: f32,
+}
+
+impl Default for Grid {
+    // 1 km cells out to 230 km from the radar
+    fn default() -> Self {
+        Self {
+            size: 460,
+            spacing: 1.0,
+        }
+    }
+}
+
+impl Grid {
+    pub fn new(size: usize, spacing: f32) -> Self {
+        Self { size, spacing }
+    }
+
+    pub fn cells(&self) -> usize {
+        self.size * self.size
+    }
+
+    // Distance of the center of a cell east and north of the radar, in km
+    pub fn cell_center(&self, row: usize, col: usize) -> (f32, f32) {
+        let center = (self.size as f32 - 1.0) / 2.0;
+        (
+            (col as f32 - center) * self.spacing,
+            (center - row as f32) * self.spacing,
+        )
+    }
+
+    // Azimuth in radians clockwise from north and distance along the
+    // ground in km from the radar to the center of a cell
+    pub fn cell_polar(&self, row: usize, col: usize) -> (f32, f32) {
+        let (x, y) = self.cell_center(row, col);
+        (x.atan2(y).rem_euclid(2.0 * PI), x.hypot(y))
+    }
+}
+
+// Samples a product of a sweep at the center of each grid cell from the
+// nearest gate below the beam, with NaN where there is no data
+pub(crate) fn grid_sweep(grid: &Grid, sweep: &Sweep, data: &SweepData) -> Vec<f32> {
+    let index = RadialIndex::new(&sweep.azimuths, sweep.az_step);
+    let mut values = vec![f32::NAN; grid.cells()];
+
+    for row in 0..grid.size {
+        for col in 0..grid.size {
+            let (azimuth, distance) = grid.cell_polar(row, col);
+            let Some(radial) = index.find(azimuth) else {
+                continue;
+            };
+
+            let range = slant_range(distance, sweep.elevations[radial]);
+            let gate = ((range - sweep.range_first) / sweep.range_step).round();
+            if gate < 0.0 || gate >= data.gates as f32 {
+                continue;
+            }
+
+            if let Some(value) = data.value(radial, gate as usize) {
+                values[row * grid.size + col] = value;
+            }
+        }
+    }
+
+    values
+}
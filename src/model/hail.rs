@@ -0,0 +1,11 @@
+// Hail detection algorithm products for a storm cell or a grid cell
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HailEstimate {
+    // Severe hail index in J/m/s
+    pub shi: f32,
+    // Probability of hail and of severe hail, in percent
+    pub poh: f32,
+    pub posh: f32,
+    // Maximum expected hail size in mm
+    pub mesh: f32,
+}
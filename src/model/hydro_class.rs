@@ -0,0 +1,14 @@
+pub type HydroClass = u8;
+
+// Gates with echo which did not match any class well enough
+pub const HC_UNKNOWN: HydroClass = 0;
+pub const HC_RAIN: HydroClass = 1;
+pub const HC_HEAVY_RAIN: HydroClass = 2;
+// Hail, possibly mixed with rain
+pub const HC_HAIL: HydroClass = 3;
+pub const HC_GRAUPEL: HydroClass = 4;
+pub const HC_DRY_SNOW: HydroClass = 5;
+pub const HC_WET_SNOW: HydroClass = 6;
+pub const HC_ICE_CRYSTALS: HydroClass = 7;
+pub const HC_BIOLOGICAL: HydroClass = 8;
+pub const HC_CLUTTER: HydroClass = 9;
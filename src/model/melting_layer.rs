@@ -0,0 +1,15 @@
+// Heights of the bottom and top of the melting layer above mean sea
+// level, in km
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeltingLayer {
+    pub bottom: f32,
+    pub top: f32,
+}
+
+// A melting layer detected from the radar data, with a confidence from 0
+// to 1 based on how clear its signature is
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeltingLayerEstimate {
+    pub layer: MeltingLayer,
+    pub confidence: f32,
+}
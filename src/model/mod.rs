@@ -1,3 +1,12 @@
+pub mod gate_status;
+pub mod geometry;
+pub mod grid;
+pub mod hail;
+pub mod hydro_class;
+pub mod melting_layer;
+pub mod rotation;
+pub mod site;
+pub mod storm_cell;
 pub mod sweep;
 pub mod sweep_data;
 pub mod sweep_type;
@@ -0,0 +1,28 @@
+// A rotation detected on consecutive tilts of dealiased velocity.
+// Positions are in km east and north of the radar and heights are in km
+// above mean sea level.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rotation {
+    // Center of the rotation on its lowest tilt
+    pub x: f32,
+    pub y: f32,
+    pub base: f32,
+    pub top: f32,
+    // Half the largest velocity difference across the rotation, in m/s,
+    // over all tilts and on the lowest tilt
+    pub rotational_velocity: f32,
+    pub low_level_rotational_velocity: f32,
+    // Distance between the velocity peaks where the rotational velocity is
+    // strongest, in km
+    pub diameter: f32,
+    // Largest velocity difference between adjacent radials, in m/s
+    pub gate_to_gate_delta_v: f32,
+    // Rotational velocity in steps of 5 m/s, where 5 and above are
+    // mesocyclone strength
+    pub strength_rank: u8,
+    // True for a tornado vortex signature: a strong, deep gate to gate
+    // shear at the lowest tilt
+    pub is_tvs: bool,
+    // Number of tilts with a feature of the rotation
+    pub features: usize,
+}
@@ -0,0 +1,65 @@
+use nexrad_decode::messages::digital_radar_data::VolumeDataBlock;
+
+use crate::model::geometry::EARTH_RADIUS;
+
+// Location of the radar which collected a volume
+#[derive(Clone, Copy, Debug)]
+pub struct Site {
+    // Degrees north and east
+    pub latitude: f32,
+    pub longitude: f32,
+    // Height of the antenna above mean sea level, in km
+    pub height: f32,
+}
+
+impl Site {
+    pub(crate) fn new(volume_data: &VolumeDataBlock) -> Self {
+        Self {
+            latitude: volume_data.latitude,
+            longitude: volume_data.longitude,
+            height: (volume_data.site_height as f32 + volume_data.feedhorn_height as f32) / 1000.0,
+        }
+    }
+
+    // Latitude and longitude of a point at a distance in km east and north
+    // of the radar along the earth's surface
+    pub fn offset(&self, x: f32, y: f32) -> (f32, f32) {
+        let distance = (x as f64).hypot(y as f64) / EARTH_RADIUS as f64;
+        let bearing = (x as f64).atan2(y as f64);
+        let latitude = (self.latitude as f64).to_radians();
+        let longitude = (self.longitude as f64).to_radians();
+
+        let target_latitude = (latitude.sin() * distance.cos()
+            + latitude.cos() * distance.sin() * bearing.cos())
+        .asin();
+        let target_longitude = longitude
+            + (bearing.sin() * distance.sin() * latitude.cos())
+                .atan2(distance.cos() - latitude.sin() * target_latitude.sin());
+
+        (
+            target_latitude.to_degrees() as f32,
+            target_longitude.to_degrees() as f32,
+        )
+    }
+
+    // Distance in km east and north of the radar along the earth's surface
+    // to a latitude and longitude, the inverse of offset
+    pub fn local(&self, latitude: f32, longitude: f32) -> (f32, f32) {
+        let latitude_a = (self.latitude as f64).to_radians();
+        let latitude_b = (latitude as f64).to_radians();
+        let delta_longitude = (longitude as f64 - self.longitude as f64).to_radians();
+
+        let a = ((latitude_b - latitude_a) / 2.0).sin().powi(2)
+            + latitude_a.cos() * latitude_b.cos() * (delta_longitude / 2.0).sin().powi(2);
+        let distance = 2.0 * EARTH_RADIUS as f64 * a.sqrt().atan2((1.0 - a).sqrt());
+        let bearing = (delta_longitude.sin() * latitude_b.cos()).atan2(
+            latitude_a.cos() * latitude_b.sin()
+                - latitude_a.sin() * latitude_b.cos() * delta_longitude.cos(),
+        );
+
+        (
+            (distance * bearing.sin()) as f32,
+            (distance * bearing.cos()) as f32,
+        )
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+
+use crate::model::hail::HailEstimate;
+
+// A three dimensional storm cell made of reflectivity components on
+// consecutive tilts. Positions are in km east and north of the radar and
+// heights are in km above mean sea level.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StormCell {
+    // Mass weighted centroid of the components
+    pub x: f32,
+    pub y: f32,
+    pub height: f32,
+    // Heights of the lowest and highest components
+    pub base: f32,
+    pub top: f32,
+    pub max_reflectivity: f32,
+    pub max_reflectivity_height: f32,
+    // Cell based vertically integrated liquid in kg/m²
+    pub vil: f32,
+    // Number of tilts with a component of the cell
+    pub components: usize,
+    // Height and largest reflectivity of each component, lowest first
+    pub levels: Vec<(f32, f32)>,
+    // Set when hail detection has been run
+    pub hail: Option<HailEstimate>,
+}
+
+// The storm cells identified in a volume, at the start time of the volume
+#[derive(Clone, Debug)]
+pub struct StormCells {
+    pub time: DateTime<Utc>,
+    pub cells: Vec<StormCell>,
+}
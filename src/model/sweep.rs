@@ -2,7 +2,7 @@ use std::f32::consts::PI;
 
 use chrono::{DateTime, Utc};
 use nexrad_decode::messages::{
-    digital_radar_data::{Message, ScaledMomentValue},
+    digital_radar_data::{DataMoment, Message, ScaledMomentValue},
     volume_coverage_pattern::{ElevationDataBlock, WaveformType},
 };
 
@@ -17,11 +17,26 @@ use uom::si::{
 };
 
 pub struct Sweep {
+    // Index of the cut in the volume coverage pattern
+    pub cut_index: usize,
+    pub waveform_type: WaveformType,
+    // Index of the other half of a split cut (CS/CD pair at one elevation)
+    pub paired_cut: Option<usize>,
+    // True if this is a supplemental (SAILS) repeat of a lower elevation
+    pub is_sails: bool,
+
     pub elevation: f32,
 
     pub az_first: f32,
     pub az_step: f32,
     pub az_count: i32,
+    // Number of radials in a complete sweep at this azimuth resolution
+    pub nominal_az_count: i32,
+    // Actual antenna angles and collection time of each radial,
+    // in the order the radials are stored
+    pub azimuths: Vec<f32>,
+    pub elevations: Vec<f32>,
+    pub times: Vec<DateTime<Utc>>,
 
     pub range_first: f32,
     pub range_step: f32,
@@ -32,16 +47,53 @@ pub struct Sweep {
     pub start_time: DateTime<Utc>,
     pub end_time: DateTime<Utc>,
 
-    pub sweep_type: u8,
+    pub sweep_type: SweepType,
 
     pub reflectivity: Option<SweepData>,
     pub velocity: Option<SweepData>,
+    pub correlation_coefficient: Option<SweepData>,
+    pub differential_reflectivity: Option<SweepData>,
+    pub differential_phase: Option<SweepData>,
+    // Level II does not carry specific differential phase, so this is only
+    // set once it has been estimated from the differential phase, along
+    // with the filtered differential phase it was estimated from
+    pub specific_differential_phase: Option<SweepData>,
+    pub filtered_differential_phase: Option<SweepData>,
+
+    // Fraction of the clutter tests which flagged each reflectivity gate,
+    // set when the clutter filter has been run
+    pub clutter: Option<SweepData>,
+    // Hydrometeor class code of each gate, set when classification has
+    // been run on a sweep with dual pol data
+    pub hydrometeor_class: Option<SweepData>,
+    // Rain rate in mm/h, set on the lowest tilt when QPE has been run
+    pub rain_rate: Option<SweepData>,
+    // Azimuthal shear and radial divergence in s⁻¹, set on the velocity
+    // sweeps when LLSD derivatives have been estimated
+    pub azimuthal_shear: Option<SweepData>,
+    pub divergence: Option<SweepData>,
+    // Velocity with the storm motion removed, set on the velocity sweeps
+    // when storm-relative velocity has been computed
+    pub storm_relative_velocity: Option<SweepData>,
+}
+
+fn data_moment<'a>(radial: &'a Message, data_type: &str) -> &'a Option<DataMoment> {
+    match data_type {
+        "ref" => &radial.reflectivity_data_block,
+        "vel" => &radial.velocity_data_block,
+        "rho" => &radial.correlation_coefficient_data_block,
+        "zdr" => &radial.differential_reflectivity_data_block,
+        "phi" => &radial.differential_phase_data_block,
+        _ => panic!("Unexpected product: {}", data_type),
+    }
 }
 
 fn extract_data(
     radials: &Vec<Box<Message>>,
     data_type: &str,
     az_count: usize,
+    range_first: f32,
+    range_step: f32,
     range_count: usize,
 ) -> Option<SweepData> {
     if !validate_sweep(radials, data_type) {
@@ -50,25 +102,30 @@ fn extract_data(
 
     let mut data = SweepData::new(az_count as usize, range_count as usize);
 
-    let mut sorted_radials: Vec<_> = radials.iter().collect();
-    sorted_radials.sort_by(|a, b| {
-        a.header
-            .azimuth_angle
-            .partial_cmp(&b.header.azimuth_angle)
-            .unwrap()
-    });
-    for (radial_index, radial) in sorted_radials.iter().enumerate() {
-        let data_moment = match data_type {
-            "ref" => radial.reflectivity_data_block.as_ref().unwrap(),
-            "vel" => radial.velocity_data_block.as_ref().unwrap(),
-            _ => panic!("Unexpected product: {}", data_type),
-        };
+    for (radial_index, radial) in sorted_by_azimuth(radials).iter().enumerate() {
+        let data_moment = data_moment(radial, data_type).as_ref().unwrap();
+
+        // Dual pol moments may start at a different gate than reflectivity
+        let (moment_first, moment_step, _) = moment_range_info(data_moment);
+        if (moment_step - range_step).abs() > 0.001 {
+            return None;
+        }
+        let gate_offset = ((moment_first - range_first) / range_step).round() as isize;
+
+        for (moment_gate, gate_value) in data_moment.decoded_values().iter().enumerate() {
+            let gate_index = moment_gate as isize + gate_offset;
+            if gate_index < 0 || gate_index >= range_count as isize {
+                continue;
+            }
+            let gate_index = gate_index as usize;
 
-        for (gate_index, gate_value) in data_moment.decoded_values().iter().enumerate() {
             match gate_value {
                 ScaledMomentValue::Value(value) => {
                     data.set_value(*value, radial_index, gate_index);
                 }
+                ScaledMomentValue::RangeFolded => {
+                    data.set_range_folded(radial_index, gate_index);
+                }
                 _ => {}
             }
         }
@@ -77,6 +134,71 @@ fn extract_data(
     return Some(data);
 }
 
+// Radials are stored in order of increasing azimuth
+fn sorted_by_azimuth(radials: &[Box<Message>]) -> Vec<&Message> {
+    let mut sorted_radials: Vec<&Message> = radials.iter().map(|radial| radial.as_ref()).collect();
+    sorted_radials.sort_by(|a, b| {
+        a.header
+            .azimuth_angle
+            .partial_cmp(&b.header.azimuth_angle)
+            .unwrap()
+    });
+
+    sorted_radials
+}
+
+// Smallest angle between two azimuths, in radians
+pub(crate) fn angular_distance(a: f32, b: f32) -> f32 {
+    let difference = (a - b).rem_euclid(2.0 * PI);
+    f32::min(difference, 2.0 * PI - difference)
+}
+
+// Looks up the radial closest to an azimuth, ignoring radials further
+// than max_distance away. Azimuths are in radians.
+pub(crate) struct RadialIndex {
+    sorted: Vec<(f32, usize)>,
+    max_distance: f32,
+}
+
+impl RadialIndex {
+    pub(crate) fn new(azimuths: &[f32], max_distance: f32) -> Self {
+        let mut sorted: Vec<(f32, usize)> = azimuths
+            .iter()
+            .enumerate()
+            .map(|(radial, azimuth)| (azimuth.rem_euclid(2.0 * PI), radial))
+            .collect();
+        sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        Self {
+            sorted,
+            max_distance,
+        }
+    }
+
+    pub(crate) fn find(&self, azimuth: f32) -> Option<usize> {
+        if self.sorted.is_empty() {
+            return None;
+        }
+
+        // The nearest radial is on one side or the other of the insertion
+        // point, wrapping around north
+        let azimuth = azimuth.rem_euclid(2.0 * PI);
+        let len = self.sorted.len();
+        let index = self.sorted.partition_point(|(a, _)| *a < azimuth);
+        let candidates = [
+            self.sorted[(index + len - 1) % len],
+            self.sorted[index % len],
+        ];
+
+        candidates
+            .iter()
+            .map(|(a, radial)| (angular_distance(*a, azimuth), *radial))
+            .filter(|(distance, _)| *distance < self.max_distance)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, radial)| radial)
+    }
+}
+
 fn extract_nyquist_vel(radials: &Vec<Box<Message>>) -> f32 {
     let nyquist_vel = radials[0]
         .radial_data_block
@@ -101,13 +223,7 @@ fn extract_nyquist_vel(radials: &Vec<Box<Message>>) -> f32 {
 
 fn validate_sweep(radials: &Vec<Box<Message>>, data_type: &str) -> bool {
     for radial in radials {
-        let data_moment = match data_type {
-            "ref" => &radial.reflectivity_data_block,
-            "vel" => &radial.velocity_data_block,
-            _ => panic!("Unexpected product: {}", data_type),
-        };
-
-        if data_moment.is_none() {
+        if data_moment(radial, data_type).is_none() {
             return false;
         }
     }
@@ -121,21 +237,23 @@ fn extract_range_info(radial: &Message, data_type: &str) -> (f32, f32, i32) {
         sample_data_moment = radial.velocity_data_block.as_ref().unwrap();
     }
 
-    let range_step = sample_data_moment
+    moment_range_info(sample_data_moment)
+}
+
+fn moment_range_info(data_moment: &DataMoment) -> (f32, f32, i32) {
+    let range_step = data_moment
         .header
         .data_moment_range_sample_interval()
         .get::<kilometer>() as f32;
-    let range_first = sample_data_moment
-        .header
-        .data_moment_range()
-        .get::<kilometer>() as f32;
-    let range_count = sample_data_moment.header.number_of_data_moment_gates as i32;
+    let range_first = data_moment.header.data_moment_range().get::<kilometer>() as f32;
+    let range_count = data_moment.header.number_of_data_moment_gates as i32;
 
-    return (range_first, range_step, range_count);
+    (range_first, range_step, range_count)
 }
 
 impl Sweep {
     pub(crate) fn new(
+        cut_index: usize,
         elevation_meta: &ElevationDataBlock,
         radials: &Vec<Box<Message>>,
     ) -> Option<Self> {
@@ -153,11 +271,23 @@ impl Sweep {
             .get::<radian>() as f32;
         let az_count = radials.len() as i32;
         let az_step = rad_hdr.azimuth_resolution_spacing().get::<radian>() as f32;
+        let nominal_az_count = ((2.0 * PI) / az_step).round() as i32;
 
-        // Verify that there are the expected number of radials to make the sweep
-        if ((2.0 * PI) / az_step).round() != az_count as f32 {
-            return None;
-        }
+        // Incomplete sweeps (e.g. radar outages or realtime volumes which are
+        // still in progress) are kept with only the radials which were received
+        let sorted_radials = sorted_by_azimuth(radials);
+        let azimuths = sorted_radials
+            .iter()
+            .map(|radial| radial.header.azimuth_angle.to_radians())
+            .collect();
+        let elevations = sorted_radials
+            .iter()
+            .map(|radial| radial.header.elevation_angle.to_radians())
+            .collect();
+        let times = sorted_radials
+            .iter()
+            .map(|radial| radial.header.date_time().unwrap())
+            .collect();
 
         let (r_first, r_step, r_count) = extract_range_info(&radials[0], "ref");
         let (v_first, v_step, v_count) = extract_range_info(&radials[0], "vel");
@@ -176,8 +306,21 @@ impl Sweep {
 
         let nyquist_vel = extract_nyquist_vel(radials);
 
-        let reflectivity = extract_data(radials, "ref", az_count as usize, range_count as usize);
-        let velocity = extract_data(radials, "vel", az_count as usize, range_count as usize);
+        let extract = |data_type| {
+            extract_data(
+                radials,
+                data_type,
+                az_count as usize,
+                range_first,
+                range_step,
+                range_count as usize,
+            )
+        };
+        let reflectivity = extract("ref");
+        let velocity = extract("vel");
+        let correlation_coefficient = extract("rho");
+        let differential_reflectivity = extract("zdr");
+        let differential_phase = extract("phi");
 
         let start_time = radials
             .iter()
@@ -191,23 +334,43 @@ impl Sweep {
             .max()
             .unwrap();
 
-        let is_reflectivity =
-            reflectivity.is_some() && elevation_meta.waveform_type() != WaveformType::CDW;
-        let is_velocity = velocity.is_some() && elevation_meta.waveform_type() != WaveformType::CS;
+        let waveform_type = elevation_meta.waveform_type();
 
-        let mut sweep_type: u8 = 0;
+        let is_reflectivity = reflectivity.is_some() && waveform_type != WaveformType::CDW;
+        let is_velocity = velocity.is_some() && waveform_type != WaveformType::CS;
+
+        let mut sweep_type: SweepType = 0;
         if is_reflectivity {
             sweep_type |= REFLECTIVITY;
         }
         if is_velocity {
             sweep_type |= VELOCITY;
         }
+        if waveform_type != WaveformType::CDW {
+            if correlation_coefficient.is_some() {
+                sweep_type |= CORRELATION_COEFFICIENT;
+            }
+            if differential_reflectivity.is_some() {
+                sweep_type |= DIFFERENTIAL_REFLECTIVITY;
+            }
+            if differential_phase.is_some() {
+                sweep_type |= DIFFERENTIAL_PHASE;
+            }
+        }
 
         return Some(Self {
+            cut_index,
+            waveform_type,
+            paired_cut: None,
+            is_sails: false,
             elevation,
             az_first,
             az_step,
             az_count,
+            nominal_az_count,
+            azimuths,
+            elevations,
+            times,
             range_first,
             range_step,
             range_count,
@@ -217,14 +380,205 @@ impl Sweep {
             sweep_type,
             reflectivity,
             velocity,
+            correlation_coefficient,
+            differential_reflectivity,
+            differential_phase,
+            specific_differential_phase: None,
+            filtered_differential_phase: None,
+            clutter: None,
+            hydrometeor_class: None,
+            rain_rate: None,
+            azimuthal_shear: None,
+            divergence: None,
+            storm_relative_velocity: None,
         });
     }
 
+    // Slant range to the center of a gate, in km
+    pub fn gate_range(&self, gate: usize) -> f32 {
+        self.range_first + gate as f32 * self.range_step
+    }
+
+    // Fraction of the nominal number of radials which are present
+    pub fn completeness(&self) -> f32 {
+        f32::min(self.az_count as f32 / self.nominal_az_count as f32, 1.0)
+    }
+
+    // A sweep is complete when it covers the full circle, which some
+    // sweeps do with a radial to spare
+    pub fn is_complete(&self) -> bool {
+        self.az_count >= self.nominal_az_count
+    }
+
+    // Moves the radials onto the nominal azimuth grid, the same grid as
+    // resample_uniform where radial i is centered at (i + 0.5) * az_step.
+    // Each radial falls in the grid cell containing its azimuth, so there
+    // are no ties between cells. Cells without a radial are masked, and
+    // where two radials fall in one cell the one closest to its center is
+    // kept. Radials which are present keep their actual angles.
+    pub(crate) fn regrid(&mut self) {
+        let n = self.nominal_az_count as usize;
+        let mut rows: Vec<Option<usize>> = vec![None; n];
+        let mut offsets = vec![f32::INFINITY; n];
+
+        for (radial, azimuth) in self.azimuths.iter().enumerate() {
+            let position = azimuth.rem_euclid(2.0 * PI) / self.az_step;
+            let row = position.floor() as usize % n;
+            let offset = (position.fract() - 0.5).abs();
+
+            if offset < offsets[row] {
+                rows[row] = Some(radial);
+                offsets[row] = offset;
+            }
+        }
+
+        let azimuths = rows
+            .iter()
+            .enumerate()
+            .map(|(row, radial)| match radial {
+                Some(radial) => self.azimuths[*radial],
+                None => (row as f32 + 0.5) * self.az_step,
+            })
+            .collect();
+
+        self.az_first = 0.5 * self.az_step;
+        self.select_radials(&rows, azimuths);
+    }
+
+    // Resamples the sweep onto an exact uniform grid of nominal_az_count
+    // radials, where radial i is centered at (i + 0.5) * az_step. Each grid
+    // radial takes the data of the nearest actual radial, or is masked if
+    // no radial is within az_step of it.
+    pub(crate) fn resample_uniform(&mut self) {
+        let n = self.nominal_az_count as usize;
+
+        let index = RadialIndex::new(&self.azimuths, self.az_step);
+        let grid: Vec<f32> = (0..n).map(|i| (i as f32 + 0.5) * self.az_step).collect();
+        let rows: Vec<Option<usize>> = grid.iter().map(|azimuth| index.find(*azimuth)).collect();
+
+        self.az_first = 0.5 * self.az_step;
+        self.select_radials(&rows, grid);
+    }
+
+    // Rebuilds the sweep so that radial i is a copy of radial rows[i], or
+    // masked if rows[i] is None. Missing radials are given the nominal
+    // elevation and the start time of the sweep.
+    fn select_radials(&mut self, rows: &[Option<usize>], azimuths: Vec<f32>) {
+        for product in [
+            &mut self.reflectivity,
+            &mut self.velocity,
+            &mut self.correlation_coefficient,
+            &mut self.differential_reflectivity,
+            &mut self.differential_phase,
+            &mut self.specific_differential_phase,
+            &mut self.filtered_differential_phase,
+            &mut self.clutter,
+            &mut self.hydrometeor_class,
+            &mut self.rain_rate,
+            &mut self.azimuthal_shear,
+            &mut self.divergence,
+            &mut self.storm_relative_velocity,
+        ] {
+            *product = product.as_ref().map(|data| data.select_radials(rows));
+        }
+
+        self.elevations = rows
+            .iter()
+            .map(|row| row.map_or(self.elevation, |radial| self.elevations[radial]))
+            .collect();
+        self.times = rows
+            .iter()
+            .map(|row| row.map_or(self.start_time, |radial| self.times[radial]))
+            .collect();
+
+        self.azimuths = azimuths;
+        self.az_count = rows.len() as i32;
+    }
+
+    pub fn product(&self, product: SweepType) -> Option<&SweepData> {
+        match product {
+            REFLECTIVITY => self.reflectivity.as_ref(),
+            VELOCITY => self.velocity.as_ref(),
+            CORRELATION_COEFFICIENT => self.correlation_coefficient.as_ref(),
+            DIFFERENTIAL_REFLECTIVITY => self.differential_reflectivity.as_ref(),
+            DIFFERENTIAL_PHASE => self.differential_phase.as_ref(),
+            CLUTTER => self.clutter.as_ref(),
+            HYDROMETEOR_CLASS => self.hydrometeor_class.as_ref(),
+            SPECIFIC_DIFFERENTIAL_PHASE => self.specific_differential_phase.as_ref(),
+            FILTERED_DIFFERENTIAL_PHASE => self.filtered_differential_phase.as_ref(),
+            RAIN_RATE => self.rain_rate.as_ref(),
+            AZIMUTHAL_SHEAR => self.azimuthal_shear.as_ref(),
+            DIVERGENCE => self.divergence.as_ref(),
+            STORM_RELATIVE_VELOCITY => self.storm_relative_velocity.as_ref(),
+            _ => None,
+        }
+    }
+
+    // Every moment of the sweep including the estimated KDP, excluding the
+    // clutter and classification products
+    pub(crate) fn moments_mut(&mut self) -> [&mut Option<SweepData>; 7] {
+        [
+            &mut self.reflectivity,
+            &mut self.velocity,
+            &mut self.correlation_coefficient,
+            &mut self.differential_reflectivity,
+            &mut self.differential_phase,
+            &mut self.specific_differential_phase,
+            &mut self.filtered_differential_phase,
+        ]
+    }
+
     pub(crate) fn has_product(&self, product: SweepType) -> bool {
-        return match product {
-            REFLECTIVITY => self.reflectivity.is_some(),
-            VELOCITY => self.velocity.is_some(),
-            _ => false,
-        };
+        self.product(product).is_some()
+    }
+}
+
+#[cfg(test)]
+impl Sweep {
+    // A sweep without data whose radials are at the given azimuths in
+    // radians, with gates every 250 m from range_first km
+    pub(crate) fn synthetic(
+        waveform_type: WaveformType,
+        azimuths: Vec<f32>,
+        az_step: f32,
+        range_first: f32,
+        gates: usize,
+    ) -> Self {
+        let time = DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+
+        Self {
+            cut_index: 0,
+            waveform_type,
+            paired_cut: None,
+            is_sails: false,
+            elevation: 0.5_f32.to_radians(),
+            az_first: azimuths[0],
+            az_step,
+            az_count: azimuths.len() as i32,
+            nominal_az_count: ((2.0 * PI) / az_step).round() as i32,
+            elevations: vec![0.5_f32.to_radians(); azimuths.len()],
+            times: vec![time; azimuths.len()],
+            azimuths,
+            range_first,
+            range_step: 0.25,
+            range_count: gates as i32,
+            nyquist_vel: 30.0,
+            start_time: time,
+            end_time: time,
+            sweep_type: 0,
+            reflectivity: None,
+            velocity: None,
+            correlation_coefficient: None,
+            differential_reflectivity: None,
+            differential_phase: None,
+            specific_differential_phase: None,
+            filtered_differential_phase: None,
+            clutter: None,
+            hydrometeor_class: None,
+            rain_rate: None,
+            azimuthal_shear: None,
+            divergence: None,
+            storm_relative_velocity: None,
+        }
     }
 }
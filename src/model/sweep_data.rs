@@ -1,8 +1,16 @@
+use crate::model::gate_status::*;
+
+// Gate values for a single product of a sweep. The values and mask are
+// stored in flat row-major buffers, so each radial is a contiguous slice
+// of `gates` values which can be handed off without copying.
+// Range folded gates are masked, and are also flagged so they can be told
+// apart from gates without data.
 pub struct SweepData {
     pub radials: usize,
     pub gates: usize,
-    data: Vec<Vec<f32>>,
-    mask: Vec<Vec<bool>>,
+    data: Vec<f32>,
+    mask: Vec<bool>,
+    range_folded: Vec<bool>,
 }
 
 impl SweepData {
@@ -10,73 +18,197 @@ impl SweepData {
         Self {
             radials,
             gates,
-            data: vec![vec![0.0 as f32; gates]; radials],
-            mask: vec![vec![true; gates]; radials],
+            data: vec![0.0; radials * gates],
+            mask: vec![true; radials * gates],
+            range_folded: vec![false; radials * gates],
         }
     }
 
+    #[inline]
+    fn index(&self, radial: usize, gate: usize) -> usize {
+        debug_assert!(radial < self.radials && gate < self.gates);
+        radial * self.gates + gate
+    }
+
+    #[inline]
     pub(crate) fn set_value(&mut self, value: f32, radial: usize, gate: usize) {
-        self.data[radial][gate] = value;
-        self.mask[radial][gate] = false;
+        let index = self.index(radial, gate);
+        self.data[index] = value;
+        self.mask[index] = false;
+        self.range_folded[index] = false;
     }
 
+    #[inline]
+    pub(crate) fn set_range_folded(&mut self, radial: usize, gate: usize) {
+        let index = self.index(radial, gate);
+        self.mask[index] = true;
+        self.range_folded[index] = true;
+    }
+
+    #[inline]
     pub(crate) fn set_mask(&mut self, radial: usize, gate: usize) {
-        self.mask[radial][gate] = true;
+        let index = self.index(radial, gate);
+        self.mask[index] = true;
     }
 
+    // Callers must check the mask first, masked gates hold stale values
+    #[inline]
     pub(crate) fn get_value(&self, radial: usize, gate: usize) -> f32 {
-        if self.mask[radial][gate] {
-            panic!("Value at {} {} is masked", radial, gate)
+        let index = self.index(radial, gate);
+        if self.mask[index] {
+            panic!("Value at {} {} is masked", radial, gate);
         }
 
-        return self.data[radial][gate];
+        self.data[index]
     }
 
+    #[inline]
     pub(crate) fn get_value_with_fallback(&self, radial: usize, gate: usize, fallback: f32) -> f32 {
-        if self.mask[radial][gate] {
-            return fallback;
-        }
-
-        return self.data[radial][gate];
+        self.value(radial, gate).unwrap_or(fallback)
     }
 
+    #[inline]
     pub(crate) fn get_mask(&self, radial: usize, gate: usize) -> bool {
-        return self.mask[radial][gate];
+        self.mask[self.index(radial, gate)]
     }
 
-    pub(crate) fn min(&self) -> (f32, bool) {
-        let mut min = f32::INFINITY;
-        let mut has_value = false;
+    #[inline]
+    pub fn is_range_folded(&self, radial: usize, gate: usize) -> bool {
+        self.range_folded[self.index(radial, gate)]
+    }
 
-        for r in 0..self.radials {
-            for g in 0..self.gates {
-                if self.mask[r][g] {
-                    continue;
-                }
+    #[inline]
+    pub fn status(&self, radial: usize, gate: usize) -> GateStatus {
+        let index = self.index(radial, gate);
+        if self.range_folded[index] {
+            GATE_RANGE_FOLDED
+        } else if self.mask[index] {
+            GATE_NO_DATA
+        } else {
+            GATE_VALID
+        }
+    }
 
-                has_value = true;
-                min = f32::min(min, self.data[r][g]);
-            }
+    // Returns the value of the gate, or None if it is masked
+    #[inline]
+    pub fn value(&self, radial: usize, gate: usize) -> Option<f32> {
+        let index = self.index(radial, gate);
+        if self.mask[index] {
+            return None;
         }
 
-        return (min, has_value);
+        Some(self.data[index])
     }
 
-    pub(crate) fn max(&self) -> (f32, bool) {
-        let mut max = f32::NEG_INFINITY;
-        let mut has_value = false;
+    // Values of a single radial. Masked gates hold stale values.
+    pub fn radial(&self, radial: usize) -> &[f32] {
+        &self.data[radial * self.gates..(radial + 1) * self.gates]
+    }
+
+    pub fn radial_mask(&self, radial: usize) -> &[bool] {
+        &self.mask[radial * self.gates..(radial + 1) * self.gates]
+    }
+
+    pub fn radial_range_folded(&self, radial: usize) -> &[bool] {
+        &self.range_folded[radial * self.gates..(radial + 1) * self.gates]
+    }
+
+    pub(crate) fn radial_mut(&mut self, radial: usize) -> (&mut [f32], &mut [bool]) {
+        let range = radial * self.gates..(radial + 1) * self.gates;
+        (&mut self.data[range.clone()], &mut self.mask[range])
+    }
+
+    // Iterates over the radials as (values, mask) slices
+    pub fn radials_iter(&self) -> impl Iterator<Item = (&[f32], &[bool])> {
+        self.data
+            .chunks_exact(self.gates.max(1))
+            .zip(self.mask.chunks_exact(self.gates.max(1)))
+    }
+
+    // Iterates over the values of all unmasked gates
+    pub fn values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.data
+            .iter()
+            .zip(self.mask.iter())
+            .filter(|(_, masked)| !**masked)
+            .map(|(value, _)| *value)
+    }
 
-        for r in 0..self.radials {
-            for g in 0..self.gates {
-                if self.mask[r][g] {
-                    continue;
-                }
+    // The full row-major buffers, radials * gates long
+    pub fn as_slice(&self) -> &[f32] {
+        &self.data
+    }
+
+    pub fn mask_slice(&self) -> &[bool] {
+        &self.mask
+    }
+
+    pub fn into_raw(self) -> (Vec<f32>, Vec<bool>) {
+        (self.data, self.mask)
+    }
+
+    // Builds a new SweepData where radial i is a copy of radial rows[i],
+    // or fully masked if rows[i] is None
+    pub(crate) fn select_radials(&self, rows: &[Option<usize>]) -> Self {
+        let mut result = SweepData::new(rows.len(), self.gates);
+
+        for (radial, source) in rows.iter().enumerate() {
+            let Some(source) = *source else {
+                continue;
+            };
+
+            let (data, mask) = result.radial_mut(radial);
+            data.copy_from_slice(self.radial(source));
+            mask.copy_from_slice(self.radial_mask(source));
+
+            let range = radial * self.gates..(radial + 1) * self.gates;
+            result.range_folded[range].copy_from_slice(self.radial_range_folded(source));
+        }
+
+        result
+    }
 
-                has_value = true;
-                max = f32::max(max, self.data[r][g]);
+    // Standard deviation of the unmasked values within half_width gates
+    // along the radial, or None if fewer than three of them have values
+    pub(crate) fn texture(&self, radial: usize, gate: usize, half_width: usize) -> Option<f32> {
+        let values = self.radial(radial);
+        let mask = self.radial_mask(radial);
+
+        let start = gate.saturating_sub(half_width);
+        let end = usize::min(gate + half_width + 1, values.len());
+
+        let mut count = 0;
+        let mut sum = 0.0;
+        let mut squared_sum = 0.0;
+        for g in start..end {
+            if !mask[g] {
+                count += 1;
+                sum += values[g];
+                squared_sum += values[g] * values[g];
             }
         }
 
-        return (max, has_value);
+        if count < 3 {
+            return None;
+        }
+
+        let mean = sum / count as f32;
+        Some(f32::max(squared_sum / count as f32 - mean * mean, 0.0).sqrt())
+    }
+
+    pub(crate) fn min(&self) -> (f32, bool) {
+        let min = self.values().fold(f32::INFINITY, f32::min);
+
+        (min, self.has_values())
+    }
+
+    pub(crate) fn max(&self) -> (f32, bool) {
+        let max = self.values().fold(f32::NEG_INFINITY, f32::max);
+
+        (max, self.has_values())
+    }
+
+    pub(crate) fn has_values(&self) -> bool {
+        self.mask.iter().any(|masked| !masked)
     }
 }
@@ -1,4 +1,78 @@
-pub type SweepType = u8;
+pub type SweepType = u16;
 
 pub const REFLECTIVITY: SweepType = 1 << 0;
 pub const VELOCITY: SweepType = 1 << 1;
+pub const CORRELATION_COEFFICIENT: SweepType = 1 << 2;
+// Clutter score from the clutter filter, 0 to 1
+pub const CLUTTER: SweepType = 1 << 3;
+pub const DIFFERENTIAL_REFLECTIVITY: SweepType = 1 << 4;
+pub const DIFFERENTIAL_PHASE: SweepType = 1 << 5;
+// Hydrometeor class codes from model::hydro_class
+pub const HYDROMETEOR_CLASS: SweepType = 1 << 6;
+// Estimated from the differential phase, in degrees per km
+pub const SPECIFIC_DIFFERENTIAL_PHASE: SweepType = 1 << 7;
+// Unfolded and smoothed differential phase used to estimate KDP
+pub const FILTERED_DIFFERENTIAL_PHASE: SweepType = 1 << 8;
+// Rain rate in mm/h, only on the lowest tilt
+pub const RAIN_RATE: SweepType = 1 << 9;
+// Azimuthal shear and radial divergence of the dealiased velocity, in s⁻¹
+pub const AZIMUTHAL_SHEAR: SweepType = 1 << 10;
+pub const DIVERGENCE: SweepType = 1 << 11;
+// Dealiased velocity with the storm motion removed, in m/s
+pub const STORM_RELATIVE_VELOCITY: SweepType = 1 << 12;
+
+// Range of values of a product which is scaled to bytes for Python and
+// covered by the default colormaps
+pub fn product_range(product: SweepType) -> (f32, f32) {
+    match product {
+        REFLECTIVITY => (-20.0, 80.0),
+        VELOCITY => (-100.0, 100.0),
+        CORRELATION_COEFFICIENT => (0.2, 1.05),
+        DIFFERENTIAL_REFLECTIVITY => (-8.0, 8.0),
+        DIFFERENTIAL_PHASE => (0.0, 360.0),
+        CLUTTER => (0.0, 1.0),
+        // Class codes are stored as code + 1
+        HYDROMETEOR_CLASS => (0.0, 254.0),
+        SPECIFIC_DIFFERENTIAL_PHASE => (-2.0, 10.0),
+        FILTERED_DIFFERENTIAL_PHASE => (0.0, 720.0),
+        RAIN_RATE => (0.0, 200.0),
+        AZIMUTHAL_SHEAR => (-0.02, 0.02),
+        DIVERGENCE => (-0.02, 0.02),
+        STORM_RELATIVE_VELOCITY => (-100.0, 100.0),
+        _ => panic!("Unexpected product: {}", product),
+    }
+}
+
+// Names of the products used by the Python bindings
+const PRODUCT_NAMES: &[(&str, SweepType)] = &[
+    ("reflectivity", REFLECTIVITY),
+    ("velocity", VELOCITY),
+    ("correlation_coefficient", CORRELATION_COEFFICIENT),
+    ("clutter", CLUTTER),
+    ("differential_reflectivity", DIFFERENTIAL_REFLECTIVITY),
+    ("differential_phase", DIFFERENTIAL_PHASE),
+    ("hydrometeor_class", HYDROMETEOR_CLASS),
+    ("specific_differential_phase", SPECIFIC_DIFFERENTIAL_PHASE),
+    ("filtered_differential_phase", FILTERED_DIFFERENTIAL_PHASE),
+    ("rain_rate", RAIN_RATE),
+    ("azimuthal_shear", AZIMUTHAL_SHEAR),
+    ("divergence", DIVERGENCE),
+    ("storm_relative_velocity", STORM_RELATIVE_VELOCITY),
+];
+
+// Product with the name used by the Python bindings, such as "reflectivity"
+pub fn product_from_name(name: &str) -> Option<SweepType> {
+    PRODUCT_NAMES
+        .iter()
+        .find(|(product_name, _)| *product_name == name)
+        .map(|(_, product)| *product)
+}
+
+// Name used by the Python bindings for a single product
+pub fn product_name(product: SweepType) -> &'static str {
+    PRODUCT_NAMES
+        .iter()
+        .find(|(_, named)| *named == product)
+        .map(|(name, _)| *name)
+        .unwrap_or_else(|| panic!("Unexpected product: {}", product))
+}
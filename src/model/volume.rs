@@ -1,10 +1,36 @@
 use nexrad_data::volume::Record;
-use nexrad_decode::messages::{digital_radar_data, volume_coverage_pattern, MessageContents};
+use nexrad_decode::messages::{
+    digital_radar_data,
+    volume_coverage_pattern::{self, ElevationDataBlock, WaveformType},
+    MessageContents,
+};
+use uom::si::angle::radian;
 
+use crate::model::melting_layer::MeltingLayerEstimate;
+use crate::model::rotation::Rotation;
+use crate::model::site::Site;
+use crate::model::storm_cell::StormCells;
 use crate::model::sweep::Sweep;
+use crate::model::sweep_type::SweepType;
+
+// Cuts whose elevation angles differ by less than this are considered
+// to be at the same elevation (about 0.05 degrees)
+pub(crate) const SAME_ELEVATION_TOLERANCE: f32 = 0.0009;
 
 pub struct Volume {
     pub sweeps: Vec<Sweep>,
+    // Volume coverage pattern number, e.g. 212
+    pub vcp: u16,
+    pub site: Option<Site>,
+    // Set when melting layer detection has been run and found a layer
+    pub melting_layer: Option<MeltingLayerEstimate>,
+    // Set when storm cell identification has been run
+    pub storm_cells: Option<StormCells>,
+    // Mesocyclones and tornado vortex signatures, when rotation detection
+    // has been run
+    pub rotations: Vec<Rotation>,
+    // Storm motion (u, v) in m/s removed from the storm-relative velocity
+    pub storm_motion: Option<(f32, f32)>,
 }
 
 impl Volume {
@@ -33,6 +59,11 @@ impl Volume {
             }
         }
 
+        let site = radials
+            .iter()
+            .find_map(|radial| radial.volume_data_block.as_ref())
+            .map(Site::new);
+
         let mut sweeps: Vec<Vec<Box<digital_radar_data::Message>>> = Vec::new();
         for _ in 0..vcp.as_ref().unwrap().header.number_of_elevation_cuts {
             sweeps.push(Vec::new());
@@ -42,16 +73,117 @@ impl Volume {
             sweeps[radial.header.elevation_number as usize - 1].push(radial);
         }
 
+        let elevations = &vcp.as_ref().unwrap().elevations;
+
         let mut result_sweeps: Vec<Sweep> = Vec::new();
         for (i, radials) in sweeps.iter().enumerate() {
-            let sweep = Sweep::new(&vcp.as_ref().unwrap().elevations[i], radials);
-            if sweep.is_some() {
-                result_sweeps.push(sweep.unwrap());
+            if let Some(mut sweep) = Sweep::new(i, &elevations[i], radials) {
+                sweep.paired_cut = find_paired_cut(elevations, i);
+                sweep.is_sails = is_sails_cut(elevations, i);
+                result_sweeps.push(sweep);
             }
         }
 
         Self {
             sweeps: result_sweeps,
+            vcp: vcp.as_ref().unwrap().header.pattern_number,
+            site,
+            melting_layer: None,
+            storm_cells: None,
+            rotations: Vec::new(),
+            storm_motion: None,
         }
     }
+
+    // Returns the most recent scan of the lowest elevation which has the product.
+    // With SAILS enabled this is the last supplemental low level scan in the volume.
+    pub fn latest_low_level_sweep(&self, product: SweepType) -> Option<&Sweep> {
+        self.latest_low_level_index(product)
+            .map(|index| &self.sweeps[index])
+    }
+
+    // Index in sweeps of the sweep returned by latest_low_level_sweep
+    pub fn latest_low_level_index(&self, product: SweepType) -> Option<usize> {
+        let candidates =
+            self.sweeps.iter().enumerate().filter(|(_, sweep)| {
+                sweep.has_product(product) && (sweep.sweep_type & product) != 0
+            });
+
+        let lowest = candidates
+            .clone()
+            .map(|(_, sweep)| sweep.elevation)
+            .min_by(|a, b| a.total_cmp(b))?;
+
+        candidates
+            .filter(|(_, sweep)| same_elevation(sweep.elevation, lowest))
+            .max_by_key(|(_, sweep)| sweep.start_time)
+            .map(|(index, _)| index)
+    }
+
+    // Returns the sweep with the product closest to an elevation in
+    // radians, the latest one when the elevation was scanned more than once
+    pub fn closest_sweep(&self, product: SweepType, elevation: f32) -> Option<&Sweep> {
+        let candidates = self
+            .sweeps
+            .iter()
+            .filter(|sweep| sweep.has_product(product) && (sweep.sweep_type & product) != 0);
+
+        let closest = candidates
+            .clone()
+            .min_by(|a, b| {
+                (a.elevation - elevation)
+                    .abs()
+                    .total_cmp(&(b.elevation - elevation).abs())
+            })?
+            .elevation;
+
+        candidates
+            .filter(|sweep| same_elevation(sweep.elevation, closest))
+            .max_by_key(|sweep| sweep.start_time)
+    }
+}
+
+fn elevation_angle(elevation: &ElevationDataBlock) -> f32 {
+    elevation.elevation_angle().get::<radian>() as f32
+}
+
+fn same_elevation(a: f32, b: f32) -> bool {
+    (a - b).abs() < SAME_ELEVATION_TOLERANCE
+}
+
+// A split cut is a surveillance (CS) cut immediately followed by a doppler
+// (CDW/CDWO) cut at the same elevation. Returns the index of the other cut.
+fn find_paired_cut(elevations: &[ElevationDataBlock], cut: usize) -> Option<usize> {
+    let is_split_pair = |surveillance: usize, doppler: usize| {
+        elevations[surveillance].waveform_type() == WaveformType::CS
+            && matches!(
+                elevations[doppler].waveform_type(),
+                WaveformType::CDW | WaveformType::CDWO
+            )
+            && same_elevation(
+                elevation_angle(&elevations[surveillance]),
+                elevation_angle(&elevations[doppler]),
+            )
+    };
+
+    if cut + 1 < elevations.len() && is_split_pair(cut, cut + 1) {
+        return Some(cut + 1);
+    }
+
+    if cut > 0 && is_split_pair(cut - 1, cut) {
+        return Some(cut - 1);
+    }
+
+    None
+}
+
+// A SAILS (or MESO-SAILS) cut repeats the elevation of an earlier cut
+// which is not its own split cut partner
+fn is_sails_cut(elevations: &[ElevationDataBlock], cut: usize) -> bool {
+    let paired_cut = find_paired_cut(elevations, cut);
+    let angle = elevation_angle(&elevations[cut]);
+
+    (0..cut).any(|earlier| {
+        Some(earlier) != paired_cut && same_elevation(elevation_angle(&elevations[earlier]), angle)
+    })
 }
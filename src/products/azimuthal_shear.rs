@@ -0,0 +1,277 @@
+// Azimuthal shear and radial divergence by linear least squares
+// derivatives (LLSD). At each gate a plane v = a + b·s + c·r is fitted to
+// the dealiased velocity of the gates in a kernel, where s is the distance
+// across the beam and r the distance along it from the gate. b is the
+// azimuthal shear and c the radial divergence, both in s⁻¹. The kernel is
+// sized in km, so it covers fewer radials far from the radar. Distances
+// across the beam come from the actual azimuths of the radials, and the
+// kernel only wraps from the last radial to the first on complete sweeps.
+
+use rayon::prelude::*;
+
+use crate::model::geometry::{beam_height, slant_range};
+use crate::model::grid::{grid_sweep, Grid};
+use crate::model::sweep::{angular_distance, Sweep};
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::{SweepType, AZIMUTHAL_SHEAR, DIVERGENCE, VELOCITY};
+use crate::model::volume::Volume;
+
+#[derive(Clone, Debug)]
+pub(crate) struct AzimuthalShearParams {
+    // Width of the kernel across and along the beam, in km
+    pub azimuthal_width: f32,
+    pub radial_width: f32,
+    // Fraction of the gates of the kernel which must have data
+    pub min_fraction: f32,
+}
+
+impl Default for AzimuthalShearParams {
+    fn default() -> Self {
+        Self {
+            azimuthal_width: 2.5,
+            radial_width: 0.75,
+            min_fraction: 0.3,
+        }
+    }
+}
+
+// Fewest gates a plane is fitted to
+const MIN_POINTS: usize = 5;
+
+pub(crate) fn estimate_azimuthal_shear(volume: &mut Volume, params: &AzimuthalShearParams) {
+    volume
+        .sweeps
+        .par_iter_mut()
+        .for_each(|sweep| estimate_sweep(sweep, params));
+}
+
+fn estimate_sweep(sweep: &mut Sweep, params: &AzimuthalShearParams) {
+    let Some(velocity) = sweep.velocity.as_ref() else {
+        return;
+    };
+
+    let radials = velocity.radials;
+    let gates = velocity.gates;
+    let wraps = sweep.is_complete();
+
+    let half_gates = (params.radial_width / 2.0 / sweep.range_step).round() as isize;
+    let max_half_radials = (radials / 4) as isize;
+
+    let mut shear = SweepData::new(radials, gates);
+    let mut divergence = SweepData::new(radials, gates);
+
+    for gate in 0..gates {
+        let range = sweep.gate_range(gate);
+        if range <= 0.0 {
+            continue;
+        }
+
+        // Radials either side of the gate covering half the kernel width
+        let half_radials = ((params.azimuthal_width / 2.0) / (range * sweep.az_step)).round();
+        let half_radials = (half_radials as isize).clamp(1, max_half_radials.max(1));
+        // Radials further than the kernel are across a gap in the sweep
+        let max_angle = (half_radials as f32 + 0.5) * sweep.az_step;
+
+        let kernel_size = ((2 * half_radials + 1) * (2 * half_gates + 1)) as f32;
+        let min_points = usize::max(MIN_POINTS, (params.min_fraction * kernel_size) as usize);
+
+        for radial in 0..radials {
+            if velocity.value(radial, gate).is_none() {
+                continue;
+            }
+
+            let mut sums = PlaneSums::default();
+            for radial_offset in -half_radials..=half_radials {
+                let kernel_radial = radial as isize + radial_offset;
+                let kernel_radial = if wraps {
+                    kernel_radial.rem_euclid(radials as isize) as usize
+                } else if kernel_radial < 0 || kernel_radial >= radials as isize {
+                    continue;
+                } else {
+                    kernel_radial as usize
+                };
+
+                // Radials are in azimuth order, so the offset gives the side
+                let angle = angular_distance(sweep.azimuths[kernel_radial], sweep.azimuths[radial]);
+                if angle > max_angle {
+                    continue;
+                }
+                let s = angle.copysign(radial_offset as f32) * range * 1000.0;
+
+                for gate_offset in -half_gates..=half_gates {
+                    let kernel_gate = gate as isize + gate_offset;
+                    if kernel_gate < 0 || kernel_gate >= gates as isize {
+                        continue;
+                    }
+
+                    if let Some(value) = velocity.value(kernel_radial, kernel_gate as usize) {
+                        let r = gate_offset as f32 * sweep.range_step * 1000.0;
+                        sums.add(s, r, value);
+                    }
+                }
+            }
+
+            if sums.count < min_points {
+                continue;
+            }
+
+            if let Some((shear_value, divergence_value)) = sums.solve() {
+                shear.set_value(shear_value, radial, gate);
+                divergence.set_value(divergence_value, radial, gate);
+            }
+        }
+    }
+
+    sweep.azimuthal_shear = Some(shear);
+    sweep.divergence = Some(divergence);
+    if sweep.sweep_type & VELOCITY != 0 {
+        sweep.sweep_type |= AZIMUTHAL_SHEAR | DIVERGENCE;
+    }
+}
+
+// Sums for the normal equations of the least squares plane fit
+#[derive(Default)]
+struct PlaneSums {
+    count: usize,
+    s: f32,
+    r: f32,
+    ss: f32,
+    rr: f32,
+    sr: f32,
+    v: f32,
+    sv: f32,
+    rv: f32,
+}
+
+impl PlaneSums {
+    fn add(&mut self, s: f32, r: f32, v: f32) {
+        self.count += 1;
+        self.s += s;
+        self.r += r;
+        self.ss += s * s;
+        self.rr += r * r;
+        self.sr += s * r;
+        self.v += v;
+        self.sv += s * v;
+        self.rv += r * v;
+    }
+
+    // The slopes of the plane across and along the beam, or None when the
+    // gates do not span both directions
+    fn solve(&self) -> Option<(f32, f32)> {
+        let n = self.count as f32;
+
+        // Centering removes the intercept, leaving a 2x2 system
+        let ss = self.ss - self.s * self.s / n;
+        let rr = self.rr - self.r * self.r / n;
+        let sr = self.sr - self.s * self.r / n;
+        let sv = self.sv - self.s * self.v / n;
+        let rv = self.rv - self.r * self.v / n;
+
+        let determinant = ss * rr - sr * sr;
+        if determinant.abs() <= f32::EPSILON * ss * rr {
+            return None;
+        }
+
+        Some((
+            (sv * rr - rv * sr) / determinant,
+            (rv * ss - sv * sr) / determinant,
+        ))
+    }
+}
+
+// Largest value of a product over the sweeps at each cell of a grid, using
+// only the gates whose beam height above the radar is within the layer
+// from bottom to top in km. Cells without data in the layer are NaN.
+pub(crate) fn layer_maximum(
+    volume: &Volume,
+    grid: &Grid,
+    product: SweepType,
+    bottom: f32,
+    top: f32,
+) -> Vec<f32> {
+    let mut maximum = vec![f32::NAN; grid.cells()];
+
+    for sweep in volume.sweeps.iter() {
+        if sweep.sweep_type & product == 0 {
+            continue;
+        }
+        let Some(data) = sweep.product(product) else {
+            continue;
+        };
+
+        let values = grid_sweep(grid, sweep, data);
+        for row in 0..grid.size {
+            for col in 0..grid.size {
+                let index = row * grid.size + col;
+                let value = values[index];
+                if value.is_nan() {
+                    continue;
+                }
+
+                let (_, distance) = grid.cell_polar(row, col);
+                let height = beam_height(slant_range(distance, sweep.elevation), sweep.elevation);
+                if height < bottom || height > top {
+                    continue;
+                }
+
+                // max ignores the NaN of cells without a value yet
+                maximum[index] = f32::max(maximum[index], value);
+            }
+        }
+    }
+
+    maximum
+}
+
+#[cfg(test)]
+mod tests {
+    use nexrad_decode::messages::volume_coverage_pattern::WaveformType;
+
+    use super::*;
+
+    #[test]
+    fn uses_actual_azimuths_across_gaps() {
+        // Half a sweep in two quarters with a gap between them, so neither
+        // the gap nor the seam between the last and first radials joins
+        // neighbors
+        let az_step = 1.0_f32.to_radians();
+        let azimuths: Vec<f32> = (10..100)
+            .chain(200..290)
+            .map(|degrees| (degrees as f32 + 0.5).to_radians())
+            .collect();
+        let gates = 40;
+        let mut sweep = Sweep::synthetic(WaveformType::CDW, azimuths, az_step, 20.0, gates);
+
+        // Velocity increasing with azimuth has a shear of dv/ds = k / range
+        let k = 20.0;
+        let mut velocity = SweepData::new(sweep.azimuths.len(), gates);
+        for (radial, azimuth) in sweep.azimuths.iter().enumerate() {
+            for gate in 0..gates {
+                velocity.set_value(k * azimuth, radial, gate);
+            }
+        }
+        sweep.velocity = Some(velocity);
+        sweep.sweep_type = VELOCITY;
+
+        estimate_sweep(&mut sweep, &AzimuthalShearParams::default());
+
+        let shear = sweep.azimuthal_shear.as_ref().unwrap();
+        let divergence = sweep.divergence.as_ref().unwrap();
+        for radial in [0, 1, 88, 89, 90, 91, 178, 179] {
+            for gate in 0..gates {
+                let expected = k / (sweep.gate_range(gate) * 1000.0);
+                let value = shear.value(radial, gate).expect("shear is estimated");
+                assert!(
+                    (value - expected).abs() < 0.01 * expected,
+                    "radial {} gate {}: {} != {}",
+                    radial,
+                    gate,
+                    value,
+                    expected
+                );
+                assert!(divergence.value(radial, gate).unwrap().abs() < 0.01 * expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,238 @@
+// Hail detection in the manner of the WSR-88D hail detection algorithm
+// (Witt et al. 1998). The severe hail index integrates the hail kinetic
+// energy flux of reflectivity above 40 dBZ from the freezing level up,
+// weighted towards the heights colder than -20°C. The probability of
+// severe hail and the maximum expected hail size follow from it, while the
+// probability of hail follows from the height of the 45 dBZ echo above the
+// freezing level. Heights are in km above mean sea level.
+
+use chrono::{DateTime, Utc};
+
+use crate::model::geometry::{beam_height, slant_range};
+use crate::model::grid::{grid_sweep, Grid};
+use crate::model::hail::HailEstimate;
+use crate::model::storm_cell::StormCell;
+use crate::model::sweep::Sweep;
+use crate::model::sweep_type::REFLECTIVITY;
+use crate::model::volume::{Volume, SAME_ELEVATION_TOLERANCE};
+
+#[derive(Clone, Debug)]
+pub(crate) struct HailParams {
+    // Heights of the 0°C and -20°C levels
+    pub freezing_level: f32,
+    pub minus_20_level: f32,
+}
+
+// Reflectivity limits of the hail kinetic energy weighting, in dBZ
+const HAIL_LOWER_REFLECTIVITY: f32 = 40.0;
+const HAIL_UPPER_REFLECTIVITY: f32 = 50.0;
+
+// Reflectivity of the echo top used for the probability of hail
+const POH_REFLECTIVITY: f32 = 45.0;
+
+// Hail products of a column from the reflectivity at increasing heights,
+// with NaN where a level has no data
+pub(crate) fn hail_estimate(levels: &[(f32, f32)], params: &HailParams) -> HailEstimate {
+    let mut shi = 0.0;
+    for pair in levels.windows(2) {
+        let (lower_height, lower) = pair[0];
+        let (upper_height, upper) = pair[1];
+
+        let lower = temperature_weight(lower_height, params) * kinetic_energy_flux(lower);
+        let upper = temperature_weight(upper_height, params) * kinetic_energy_flux(upper);
+        shi += 0.1 * 0.5 * (lower + upper) * (upper_height - lower_height) * 1000.0;
+    }
+
+    let echo_top = levels
+        .iter()
+        .filter(|(_, reflectivity)| *reflectivity >= POH_REFLECTIVITY)
+        .map(|(height, _)| *height)
+        .fold(f32::NAN, f32::max);
+
+    // Warning threshold in J/m/s, which rises with the freezing level
+    let warning_threshold = f32::max(57.5 * params.freezing_level - 121.0, 20.0);
+    let posh = match shi {
+        shi if shi > 0.0 => (29.0 * (shi / warning_threshold).ln() + 50.0).clamp(0.0, 100.0),
+        _ => 0.0,
+    };
+
+    HailEstimate {
+        shi,
+        poh: probability_of_hail(echo_top - params.freezing_level),
+        posh,
+        mesh: 2.54 * shi.sqrt(),
+    }
+}
+
+// Hail kinetic energy flux in J/m²/s, 0 for missing reflectivity
+fn kinetic_energy_flux(reflectivity: f32) -> f32 {
+    if reflectivity.is_nan() || reflectivity <= HAIL_LOWER_REFLECTIVITY {
+        return 0.0;
+    }
+
+    let weight = f32::min(
+        (reflectivity - HAIL_LOWER_REFLECTIVITY)
+            / (HAIL_UPPER_REFLECTIVITY - HAIL_LOWER_REFLECTIVITY),
+        1.0,
+    );
+    5.0e-6 * 10.0_f32.powf(0.084 * reflectivity) * weight
+}
+
+// Weight of a height, rising from 0 at the freezing level to 1 at the
+// -20°C level
+fn temperature_weight(height: f32, params: &HailParams) -> f32 {
+    if height <= params.freezing_level {
+        return 0.0;
+    }
+
+    let depth = params.minus_20_level - params.freezing_level;
+    if depth <= 0.0 {
+        return 1.0;
+    }
+
+    f32::min((height - params.freezing_level) / depth, 1.0)
+}
+
+// Probability of hail in percent from the height in km of the 45 dBZ echo
+// top above the freezing level (Waldvogel et al. 1979)
+fn probability_of_hail(height: f32) -> f32 {
+    if height.is_nan() || height <= 1.625 {
+        return 0.0;
+    }
+    if height >= 5.5 {
+        return 100.0;
+    }
+
+    let probability =
+        -1.20231 + 1.00184 * height - 0.17018 * height.powi(2) + 0.01086 * height.powi(3);
+    (100.0 * probability).clamp(0.0, 100.0)
+}
+
+pub(crate) fn storm_cell_hail(cell: &StormCell, params: &HailParams) -> HailEstimate {
+    hail_estimate(&cell.levels, params)
+}
+
+// Hail products gridded from the reflectivity of every tilt
+pub(crate) struct HailGrid {
+    pub grid: Grid,
+    // Values of each cell, row-major from the north west corner, with NaN
+    // where no tilt has data
+    pub shi: Vec<f32>,
+    pub poh: Vec<f32>,
+    pub posh: Vec<f32>,
+    pub mesh: Vec<f32>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+pub(crate) fn hail_grid(volume: &Volume, grid: &Grid, params: &HailParams) -> Option<HailGrid> {
+    let site_height = volume.site.map_or(0.0, |site| site.height);
+
+    // One sweep per elevation, skipping the supplemental low level scans
+    let mut sweeps: Vec<&Sweep> = volume
+        .sweeps
+        .iter()
+        .filter(|sweep| !sweep.is_sails && (sweep.sweep_type & REFLECTIVITY) != 0)
+        .filter(|sweep| sweep.reflectivity.is_some())
+        .collect();
+    sweeps.sort_by(|a, b| a.elevation.total_cmp(&b.elevation));
+    sweeps.dedup_by(|a, b| (a.elevation - b.elevation).abs() < SAME_ELEVATION_TOLERANCE);
+
+    let start_time = sweeps.iter().map(|sweep| sweep.start_time).min()?;
+    let end_time = sweeps.iter().map(|sweep| sweep.end_time).max()?;
+
+    let tilts: Vec<Vec<f32>> = sweeps
+        .iter()
+        .map(|sweep| grid_sweep(grid, sweep, sweep.reflectivity.as_ref().unwrap()))
+        .collect();
+
+    let mut hail = HailGrid {
+        grid: *grid,
+        shi: vec![f32::NAN; grid.cells()],
+        poh: vec![f32::NAN; grid.cells()],
+        posh: vec![f32::NAN; grid.cells()],
+        mesh: vec![f32::NAN; grid.cells()],
+        start_time,
+        end_time,
+    };
+
+    let mut levels = Vec::with_capacity(sweeps.len());
+    for row in 0..grid.size {
+        for col in 0..grid.size {
+            let index = row * grid.size + col;
+            if tilts.iter().all(|tilt| tilt[index].is_nan()) {
+                continue;
+            }
+
+            let (_, distance) = grid.cell_polar(row, col);
+            levels.clear();
+            levels.extend(sweeps.iter().zip(tilts.iter()).map(|(sweep, tilt)| {
+                let range = slant_range(distance, sweep.elevation);
+                (
+                    site_height + beam_height(range, sweep.elevation),
+                    tilt[index],
+                )
+            }));
+
+            let estimate = hail_estimate(&levels, params);
+            hail.shi[index] = estimate.shi;
+            hail.poh[index] = estimate.poh;
+            hail.posh[index] = estimate.posh;
+            hail.mesh[index] = estimate.mesh;
+        }
+    }
+
+    Some(hail)
+}
+
+// Accumulates gridded MESH into a swath of the largest MESH of each cell
+// over a sequence of volumes
+pub struct MeshAccumulator {
+    pub grid: Grid,
+    // Largest MESH in mm of each cell, NaN where no volume had data
+    pub maximum: Vec<f32>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+impl MeshAccumulator {
+    pub fn new(grid: Grid) -> Self {
+        Self {
+            grid,
+            maximum: vec![f32::NAN; grid.cells()],
+            start_time: None,
+            end_time: None,
+        }
+    }
+
+    // Adds the MESH grid of a volume collected between start_time and
+    // end_time. Volumes must be added in time order.
+    pub fn add(
+        &mut self,
+        mesh: &[f32],
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<(), String> {
+        if mesh.len() != self.grid.cells() {
+            return Err(format!(
+                "Expected {} grid cells but got {}",
+                self.grid.cells(),
+                mesh.len()
+            ));
+        }
+
+        if self.end_time.is_some_and(|previous| start_time < previous) {
+            return Err("Volumes must be added in time order".to_string());
+        }
+
+        for (maximum, value) in self.maximum.iter_mut().zip(mesh.iter()) {
+            // max ignores the NaN of cells without a value
+            *maximum = f32::max(*maximum, *value);
+        }
+
+        self.start_time.get_or_insert(start_time);
+        self.end_time = Some(end_time);
+
+        Ok(())
+    }
+}
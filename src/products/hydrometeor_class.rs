@@ -0,0 +1,330 @@
+// Fuzzy logic hydrometeor classification, following the NSSL algorithm
+// described by Park et al. 2009, Wea. Forecasting, doi: 10.1175/2008WAF2222205.1
+
+use crate::model::geometry::beam_height;
+use crate::model::hydro_class::*;
+use crate::model::melting_layer::MeltingLayer;
+use crate::model::sweep::Sweep;
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::{DIFFERENTIAL_REFLECTIVITY, HYDROMETEOR_CLASS};
+use crate::model::volume::Volume;
+use rayon::prelude::*;
+
+#[derive(Clone, Debug)]
+pub(crate) struct HydroClassParams {
+    // Limits the classes by the height of the beam relative to the melting
+    // layer, all classes are considered everywhere when it is unknown
+    pub melting_layer: Option<MeltingLayer>,
+    // Gates whose best aggregate score is below this are unknown
+    pub min_score: f32,
+    // Number of gates either side of a gate for the reflectivity and
+    // differential phase textures
+    pub z_texture_half_width: usize,
+    pub phidp_texture_half_width: usize,
+}
+
+impl Default for HydroClassParams {
+    fn default() -> Self {
+        Self {
+            melting_layer: None,
+            min_score: 0.4,
+            z_texture_half_width: 2,
+            phidp_texture_half_width: 4,
+        }
+    }
+}
+
+// Inputs in the order of the weights: reflectivity, differential
+// reflectivity, correlation coefficient, 10 log10(KDP), and the textures
+// of reflectivity and differential phase
+const INPUTS: usize = 6;
+
+type Trapezoid = [f32; 4];
+
+// Membership functions of a class. Some limits depend on reflectivity,
+// so each input maps reflectivity to the corners of a trapezoid.
+struct ClassMembership {
+    class: HydroClass,
+    weights: [f32; INPUTS],
+    limits: [fn(f32) -> Trapezoid; INPUTS],
+}
+
+fn f1(z: f32) -> f32 {
+    -0.50 + 2.50e-3 * z + 7.50e-4 * z * z
+}
+
+fn f2(z: f32) -> f32 {
+    0.68 - 4.81e-2 * z + 2.92e-3 * z * z
+}
+
+fn g1(z: f32) -> f32 {
+    -44.0 + 0.8 * z
+}
+
+fn g2(z: f32) -> f32 {
+    -22.0 + 0.5 * z
+}
+
+fn memberships() -> [ClassMembership; 9] {
+    // Textures of precipitation are the same for every precipitation class
+    let sd_z: fn(f32) -> Trapezoid = |_| [0.0, 0.5, 3.0, 6.0];
+    let sd_phidp: fn(f32) -> Trapezoid = |_| [0.0, 1.0, 15.0, 30.0];
+    let lkdp_any: fn(f32) -> Trapezoid = |_| [-30.0, -25.0, 10.0, 20.0];
+
+    [
+        ClassMembership {
+            class: HC_CLUTTER,
+            weights: [0.2, 0.4, 1.0, 0.0, 0.6, 0.8],
+            limits: [
+                |_| [15.0, 20.0, 70.0, 80.0],
+                |_| [-4.0, -2.0, 1.0, 2.0],
+                |_| [0.5, 0.6, 0.9, 0.95],
+                lkdp_any,
+                |_| [2.0, 4.0, 10.0, 15.0],
+                |_| [30.0, 40.0, 50.0, 60.0],
+            ],
+        },
+        ClassMembership {
+            class: HC_BIOLOGICAL,
+            weights: [0.4, 0.6, 1.0, 0.0, 0.8, 0.8],
+            limits: [
+                |_| [5.0, 10.0, 20.0, 30.0],
+                |_| [0.0, 2.0, 10.0, 12.0],
+                |_| [0.3, 0.5, 0.8, 0.83],
+                |_| [-30.0, -25.0, 10.0, 10.0],
+                |_| [1.0, 2.0, 4.0, 7.0],
+                |_| [8.0, 10.0, 40.0, 60.0],
+            ],
+        },
+        ClassMembership {
+            class: HC_DRY_SNOW,
+            weights: [1.0, 0.8, 0.6, 0.0, 0.2, 0.2],
+            limits: [
+                |_| [5.0, 10.0, 35.0, 40.0],
+                |_| [-0.3, 0.0, 0.3, 0.6],
+                |_| [0.95, 0.98, 1.0, 1.01],
+                lkdp_any,
+                sd_z,
+                sd_phidp,
+            ],
+        },
+        ClassMembership {
+            class: HC_WET_SNOW,
+            weights: [0.6, 0.8, 1.0, 0.0, 0.2, 0.2],
+            limits: [
+                |_| [25.0, 30.0, 40.0, 50.0],
+                |_| [0.5, 1.0, 2.0, 3.0],
+                |_| [0.88, 0.92, 0.95, 0.985],
+                lkdp_any,
+                sd_z,
+                sd_phidp,
+            ],
+        },
+        ClassMembership {
+            class: HC_ICE_CRYSTALS,
+            weights: [1.0, 0.6, 0.4, 0.5, 0.2, 0.2],
+            limits: [
+                |_| [0.0, 5.0, 20.0, 25.0],
+                |_| [0.1, 0.4, 3.0, 3.3],
+                |_| [0.95, 0.98, 1.0, 1.01],
+                |_| [-5.0, 0.0, 10.0, 15.0],
+                sd_z,
+                sd_phidp,
+            ],
+        },
+        ClassMembership {
+            class: HC_GRAUPEL,
+            weights: [0.8, 1.0, 0.4, 0.0, 0.2, 0.2],
+            limits: [
+                |_| [25.0, 35.0, 50.0, 55.0],
+                |z| [-0.3, 0.0, f1(z), f1(z) + 0.3],
+                |_| [0.9, 0.97, 1.0, 1.01],
+                lkdp_any,
+                sd_z,
+                sd_phidp,
+            ],
+        },
+        ClassMembership {
+            class: HC_RAIN,
+            weights: [1.0, 0.8, 0.6, 0.0, 0.2, 0.2],
+            limits: [
+                |_| [5.0, 10.0, 45.0, 50.0],
+                |z| [f1(z) - 0.3, f1(z), f2(z), f2(z) + 0.5],
+                |_| [0.95, 0.97, 1.0, 1.01],
+                |z| [g1(z) - 1.0, g1(z), g2(z), g2(z) + 1.0],
+                sd_z,
+                sd_phidp,
+            ],
+        },
+        ClassMembership {
+            class: HC_HEAVY_RAIN,
+            weights: [1.0, 0.8, 0.6, 1.0, 0.2, 0.2],
+            limits: [
+                |_| [40.0, 45.0, 55.0, 60.0],
+                |z| [f1(z) - 0.3, f1(z), f2(z), f2(z) + 0.5],
+                |_| [0.92, 0.95, 1.0, 1.01],
+                |z| [g1(z) - 1.0, g1(z), g2(z), g2(z) + 1.0],
+                sd_z,
+                sd_phidp,
+            ],
+        },
+        ClassMembership {
+            class: HC_HAIL,
+            weights: [1.0, 0.8, 0.6, 1.0, 0.2, 0.2],
+            limits: [
+                |_| [45.0, 50.0, 75.0, 80.0],
+                |z| [-0.3, 0.0, f1(z), f1(z) + 0.5],
+                |_| [0.75, 0.85, 0.95, 1.01],
+                |z| [-10.0, -4.0, g1(z), g1(z) + 1.0],
+                sd_z,
+                sd_phidp,
+            ],
+        },
+    ]
+}
+
+// Classifies every gate of the sweeps with dual pol data, storing the class
+// codes on the sweeps. Heights are relative to the melting layer using the
+// antenna height of the site when it is known.
+pub(crate) fn classify_hydrometeors(volume: &mut Volume, params: &HydroClassParams) {
+    let site_height = volume.site.map_or(0.0, |site| site.height);
+
+    volume
+        .sweeps
+        .par_iter_mut()
+        .for_each(|sweep| classify_sweep(sweep, site_height, params));
+}
+
+fn classify_sweep(sweep: &mut Sweep, site_height: f32, params: &HydroClassParams) {
+    let (Some(refl), Some(zdr), Some(rho)) = (
+        sweep.reflectivity.as_ref(),
+        sweep.differential_reflectivity.as_ref(),
+        sweep.correlation_coefficient.as_ref(),
+    ) else {
+        return;
+    };
+    let kdp = sweep.specific_differential_phase.as_ref();
+    let phidp = sweep.differential_phase.as_ref();
+
+    let memberships = memberships();
+    let mut classes = SweepData::new(refl.radials, refl.gates);
+
+    for radial in 0..refl.radials {
+        let elevation = sweep.elevations[radial];
+
+        for gate in 0..refl.gates {
+            let Some(z) = refl.value(radial, gate) else {
+                continue;
+            };
+
+            let inputs: [Option<f32>; INPUTS] = [
+                Some(z),
+                zdr.value(radial, gate),
+                rho.value(radial, gate),
+                kdp.and_then(|kdp| kdp.value(radial, gate))
+                    .map(|kdp| 10.0 * f32::max(kdp, 1e-3).log10()),
+                refl.texture(radial, gate, params.z_texture_half_width),
+                phidp
+                    .and_then(|phidp| phidp.texture(radial, gate, params.phidp_texture_half_width)),
+            ];
+
+            // Without either polarimetric moment the class can't be told
+            if inputs[1].is_none() && inputs[2].is_none() {
+                classes.set_value(HC_UNKNOWN as f32, radial, gate);
+                continue;
+            }
+
+            let height = site_height + beam_height(sweep.gate_range(gate), elevation);
+
+            let best = memberships
+                .iter()
+                .filter(|membership| {
+                    is_allowed(membership.class, height, params.melting_layer.as_ref())
+                })
+                .map(|membership| (membership.class, aggregate(membership, &inputs, z)))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            let class = match best {
+                Some((class, score)) if score >= params.min_score => class,
+                _ => HC_UNKNOWN,
+            };
+
+            classes.set_value(class as f32, radial, gate);
+        }
+    }
+
+    sweep.hydrometeor_class = Some(classes);
+    if sweep.sweep_type & DIFFERENTIAL_REFLECTIVITY != 0 {
+        sweep.sweep_type |= HYDROMETEOR_CLASS;
+    }
+}
+
+// Weighted mean of the memberships of the available inputs
+fn aggregate(membership: &ClassMembership, inputs: &[Option<f32>; INPUTS], z: f32) -> f32 {
+    let mut total = 0.0;
+    let mut weight = 0.0;
+
+    for (i, input) in inputs.iter().enumerate() {
+        let Some(value) = input else {
+            continue;
+        };
+
+        total += membership.weights[i] * trapezoid(*value, (membership.limits[i])(z));
+        weight += membership.weights[i];
+    }
+
+    if weight == 0.0 {
+        return 0.0;
+    }
+
+    total / weight
+}
+
+// 1 between the inner corners, falling linearly to 0 at the outer corners
+fn trapezoid(value: f32, [x1, x2, x3, x4]: Trapezoid) -> f32 {
+    let rise = if x2 > x1 {
+        (value - x1) / (x2 - x1)
+    } else if value >= x1 {
+        1.0
+    } else {
+        0.0
+    };
+
+    let fall = if x4 > x3 {
+        (x4 - value) / (x4 - x3)
+    } else if value <= x4 {
+        1.0
+    } else {
+        0.0
+    };
+
+    f32::min(rise, fall).clamp(0.0, 1.0)
+}
+
+// Frozen classes only occur above the bottom of the melting layer and
+// liquid classes only occur below its top
+fn is_allowed(class: HydroClass, height: f32, melting_layer: Option<&MeltingLayer>) -> bool {
+    let Some(melting_layer) = melting_layer else {
+        return true;
+    };
+
+    if height > melting_layer.top {
+        matches!(class, HC_DRY_SNOW | HC_ICE_CRYSTALS | HC_GRAUPEL | HC_HAIL)
+    } else if height >= melting_layer.bottom {
+        matches!(
+            class,
+            HC_CLUTTER
+                | HC_BIOLOGICAL
+                | HC_WET_SNOW
+                | HC_GRAUPEL
+                | HC_RAIN
+                | HC_HEAVY_RAIN
+                | HC_HAIL
+        )
+    } else {
+        matches!(
+            class,
+            HC_CLUTTER | HC_BIOLOGICAL | HC_GRAUPEL | HC_RAIN | HC_HEAVY_RAIN | HC_HAIL
+        )
+    }
+}
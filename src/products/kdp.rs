@@ -0,0 +1,204 @@
+// Specific differential phase (KDP) estimation. Differential phase is
+// unfolded along each radial, interpolated over noisy gates and smoothed,
+// and KDP is half the range derivative of the filtered phase, fitted over
+// a shorter window in heavy precipitation and a longer one elsewhere.
+
+use crate::model::sweep::Sweep;
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::{
+    DIFFERENTIAL_PHASE, FILTERED_DIFFERENTIAL_PHASE, SPECIFIC_DIFFERENTIAL_PHASE,
+};
+use crate::model::volume::Volume;
+use rayon::prelude::*;
+
+#[derive(Clone, Debug)]
+pub(crate) struct KdpParams {
+    // Gates with correlation coefficient below this are too noisy to use
+    // and are interpolated over
+    pub correlation_threshold: f32,
+    // Reflectivity in dBZ at or above which the short window is used
+    pub reflectivity_threshold: f32,
+    // Lengths of the fitting windows in km
+    pub short_window: f32,
+    pub long_window: f32,
+    // Number of usable gates at the start of a radial whose median is the
+    // reference the phase is unfolded from
+    pub system_phase_gates: usize,
+}
+
+impl Default for KdpParams {
+    fn default() -> Self {
+        Self {
+            correlation_threshold: 0.9,
+            reflectivity_threshold: 40.0,
+            short_window: 2.0,
+            long_window: 6.0,
+            system_phase_gates: 10,
+        }
+    }
+}
+
+pub(crate) fn estimate_kdp(volume: &mut Volume, params: &KdpParams) {
+    volume
+        .sweeps
+        .par_iter_mut()
+        .for_each(|sweep| estimate_sweep(sweep, params));
+}
+
+fn estimate_sweep(sweep: &mut Sweep, params: &KdpParams) {
+    let (Some(phidp), Some(rho)) = (
+        sweep.differential_phase.as_ref(),
+        sweep.correlation_coefficient.as_ref(),
+    ) else {
+        return;
+    };
+    let refl = sweep.reflectivity.as_ref();
+
+    let short_window = window_gates(params.short_window, sweep.range_step);
+    let long_window = window_gates(params.long_window, sweep.range_step);
+
+    let mut kdp = SweepData::new(phidp.radials, phidp.gates);
+    let mut filtered = SweepData::new(phidp.radials, phidp.gates);
+
+    for radial in 0..phidp.radials {
+        let usable: Vec<bool> = (0..phidp.gates)
+            .map(|gate| {
+                phidp.value(radial, gate).is_some()
+                    && rho
+                        .value(radial, gate)
+                        .is_some_and(|cc| cc >= params.correlation_threshold)
+            })
+            .collect();
+
+        let Some((start, profile)) =
+            unfold_profile(phidp.radial(radial), &usable, params.system_phase_gates)
+        else {
+            continue;
+        };
+
+        let smoothed = moving_average(&profile, short_window);
+
+        for (i, phase) in smoothed.iter().enumerate() {
+            let gate = start + i;
+
+            // Only keep gates which have echo
+            let z = refl.and_then(|refl| refl.value(radial, gate));
+            if z.is_none() && phidp.value(radial, gate).is_none() {
+                continue;
+            }
+
+            let window = match z {
+                Some(z) if z >= params.reflectivity_threshold => short_window,
+                _ => long_window,
+            };
+
+            let slope = fit_slope(&smoothed, i, window);
+            kdp.set_value(0.5 * slope / sweep.range_step, radial, gate);
+            filtered.set_value(*phase, radial, gate);
+        }
+    }
+
+    sweep.specific_differential_phase = Some(kdp);
+    sweep.filtered_differential_phase = Some(filtered);
+    if sweep.sweep_type & DIFFERENTIAL_PHASE != 0 {
+        sweep.sweep_type |= SPECIFIC_DIFFERENTIAL_PHASE | FILTERED_DIFFERENTIAL_PHASE;
+    }
+}
+
+// Odd number of gates covering a window length in km
+fn window_gates(length: f32, range_step: f32) -> usize {
+    let gates = (length / range_step).round() as usize;
+    gates / 2 * 2 + 1
+}
+
+// Unfolds the usable gates of a radial into a continuous phase profile,
+// folding each gate to within 180 degrees of the previous one, starting
+// from the median of the first usable gates. The profile keeps the system
+// differential phase as its offset, which KDP is unaffected by. Gaps
+// between usable gates are linearly interpolated. Returns the index of the
+// first usable gate and the profile up to the last usable gate, or None if
+// there are too few usable gates.
+fn unfold_profile(
+    phase: &[f32],
+    usable: &[bool],
+    system_phase_gates: usize,
+) -> Option<(usize, Vec<f32>)> {
+    let gates: Vec<usize> = (0..phase.len()).filter(|gate| usable[*gate]).collect();
+    if gates.len() < usize::max(system_phase_gates, 2) {
+        return None;
+    }
+
+    let mut initial: Vec<f32> = gates[..system_phase_gates.max(1)]
+        .iter()
+        .map(|gate| phase[*gate])
+        .collect();
+    initial.sort_by(|a, b| a.total_cmp(b));
+    let mut previous = initial[initial.len() / 2];
+
+    let start = gates[0];
+    let end = gates[gates.len() - 1];
+    let mut profile = vec![0.0; end - start + 1];
+
+    let mut last: Option<usize> = None;
+    for gate in gates {
+        let mut value = phase[gate];
+        while value - previous < -180.0 {
+            value += 360.0;
+        }
+        while value - previous > 180.0 {
+            value -= 360.0;
+        }
+
+        profile[gate - start] = value;
+
+        if let Some(last) = last {
+            let from = profile[last - start];
+            for between in (last + 1)..gate {
+                let fraction = (between - last) as f32 / (gate - last) as f32;
+                profile[between - start] = from + fraction * (value - from);
+            }
+        }
+
+        last = Some(gate);
+        previous = value;
+    }
+
+    Some((start, profile))
+}
+
+fn moving_average(values: &[f32], window: usize) -> Vec<f32> {
+    let half = window / 2;
+
+    (0..values.len())
+        .map(|i| {
+            let start = i.saturating_sub(half);
+            let end = usize::min(i + half + 1, values.len());
+            values[start..end].iter().sum::<f32>() / (end - start) as f32
+        })
+        .collect()
+}
+
+// Least squares slope per gate of the values in a window centered on i,
+// clipped to the ends of the profile
+fn fit_slope(values: &[f32], i: usize, window: usize) -> f32 {
+    let half = window / 2;
+    let start = i.saturating_sub(half);
+    let end = usize::min(i + half + 1, values.len());
+    let n = (end - start) as f32;
+    if end - start < 3 {
+        return 0.0;
+    }
+
+    let mean_x = (start + end - 1) as f32 / 2.0;
+    let mean_y = values[start..end].iter().sum::<f32>() / n;
+
+    let mut covariance = 0.0;
+    let mut variance = 0.0;
+    for (x, y) in (start..end).zip(&values[start..end]) {
+        let dx = x as f32 - mean_x;
+        covariance += dx * (y - mean_y);
+        variance += dx * dx;
+    }
+
+    covariance / variance
+}
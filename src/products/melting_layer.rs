@@ -0,0 +1,199 @@
+// Melting layer detection from quasi-vertical profiles of the higher tilts.
+// The melting layer shows as a dip in correlation coefficient, with peaks
+// in differential reflectivity and reflectivity (the bright band). Its top
+// and bottom are where the correlation coefficient recovers above and below
+// the dip.
+
+use crate::model::melting_layer::{MeltingLayer, MeltingLayerEstimate};
+use crate::model::volume::Volume;
+use crate::products::qvp::{mean_profile, quasi_vertical_profile, Qvp, QvpParams, QvpStatistic};
+
+#[derive(Clone, Debug)]
+pub(crate) struct MeltingLayerParams {
+    // Range of elevations in radians used for the profiles. Low tilts
+    // smear the layer over too wide a range of heights.
+    pub min_elevation: f32,
+    pub max_elevation: f32,
+    pub qvp: QvpParams,
+    // Correlation coefficient below which a bin is inside the layer
+    pub correlation_threshold: f32,
+    // Correlation coefficient below which echoes are non-meteorological
+    pub min_correlation: f32,
+    // Smallest differential reflectivity and reflectivity at the dip
+    pub min_zdr: f32,
+    pub min_reflectivity: f32,
+    // Number of consecutive bins without data the layer may extend across
+    pub max_missing_bins: usize,
+}
+
+impl Default for MeltingLayerParams {
+    fn default() -> Self {
+        Self {
+            min_elevation: 4.0_f32.to_radians(),
+            max_elevation: 20.0_f32.to_radians(),
+            qvp: QvpParams {
+                max_height: 6.0,
+                statistic: QvpStatistic::Mean,
+                ..Default::default()
+            },
+            correlation_threshold: 0.97,
+            min_correlation: 0.8,
+            min_zdr: 0.5,
+            min_reflectivity: 15.0,
+            max_missing_bins: 2,
+        }
+    }
+}
+
+// Thickness of the layer just above the melting layer compared against its
+// peaks, in km
+const ABOVE_LAYER_DEPTH: f32 = 0.5;
+
+pub(crate) fn detect_melting_layer(
+    volume: &Volume,
+    params: &MeltingLayerParams,
+) -> Option<MeltingLayerEstimate> {
+    let site_height = volume.site.map_or(0.0, |site| site.height);
+
+    let mut z_profiles = Vec::new();
+    let mut zdr_profiles = Vec::new();
+    let mut cc_profiles = Vec::new();
+
+    for sweep in volume.sweeps.iter() {
+        if sweep.elevation < params.min_elevation || sweep.elevation > params.max_elevation {
+            continue;
+        }
+
+        let (Some(refl), Some(zdr), Some(rho)) = (
+            sweep.reflectivity.as_ref(),
+            sweep.differential_reflectivity.as_ref(),
+            sweep.correlation_coefficient.as_ref(),
+        ) else {
+            continue;
+        };
+
+        z_profiles.push(quasi_vertical_profile(
+            sweep,
+            refl,
+            site_height,
+            &params.qvp,
+        ));
+        zdr_profiles.push(quasi_vertical_profile(sweep, zdr, site_height, &params.qvp));
+        cc_profiles.push(quasi_vertical_profile(sweep, rho, site_height, &params.qvp));
+    }
+
+    let z = mean_profile(&z_profiles)?;
+    let zdr = mean_profile(&zdr_profiles)?;
+    let cc = mean_profile(&cc_profiles)?;
+
+    find_melting_layer(&z, &zdr, &cc, params)
+}
+
+fn find_melting_layer(
+    z: &Qvp,
+    zdr: &Qvp,
+    cc: &Qvp,
+    params: &MeltingLayerParams,
+) -> Option<MeltingLayerEstimate> {
+    let bins = cc.values.len();
+
+    // The dip is the bin with the lowest meteorological correlation which
+    // also has the reflectivity and differential reflectivity of melting snow
+    let dip = (0..bins)
+        .filter(|bin| {
+            let cc = cc.values[*bin];
+            cc >= params.min_correlation
+                && cc < params.correlation_threshold
+                && zdr.values[*bin] >= params.min_zdr
+                && z.values[*bin] >= params.min_reflectivity
+        })
+        .min_by(|a, b| cc.values[*a].total_cmp(&cc.values[*b]))?;
+
+    let bottom = extend_layer(&cc.values, dip, (0..dip).rev(), params);
+    let top = extend_layer(&cc.values, dip, (dip + 1)..bins, params);
+
+    let half_bin = params.qvp.bin_size / 2.0;
+    let layer = MeltingLayer {
+        bottom: cc.heights[bottom] - half_bin,
+        top: cc.heights[top] + half_bin,
+    };
+
+    // Bins just above the layer, in snow
+    let above_bins = (ABOVE_LAYER_DEPTH / params.qvp.bin_size).ceil() as usize;
+    let above = (top + 1)..usize::min(top + 1 + above_bins, bins);
+
+    let depth = ramp(params.correlation_threshold - cc.values[dip], 0.05);
+    let zdr_prominence = ramp(
+        peak(&zdr.values[bottom..=top]) - mean(&zdr.values[above.clone()]),
+        1.0,
+    );
+    let z_prominence = ramp(peak(&z.values[bottom..=top]) - mean(&z.values[above]), 5.0);
+
+    // Melting layers are rarely thinner than 100 m or thicker than 1.5 km
+    let thickness = layer.top - layer.bottom;
+    let plausibility = if (0.1..=1.5).contains(&thickness) {
+        1.0
+    } else {
+        0.5
+    };
+
+    Some(MeltingLayerEstimate {
+        layer,
+        confidence: plausibility * (depth + zdr_prominence + z_prominence) / 3.0,
+    })
+}
+
+// Extends the layer from the dip over the bins in order while the
+// correlation stays low, stepping over at most max_missing_bins missing
+// bins at a time. Returns the last bin with low correlation.
+fn extend_layer(
+    cc: &[f32],
+    dip: usize,
+    bins: impl Iterator<Item = usize>,
+    params: &MeltingLayerParams,
+) -> usize {
+    let mut edge = dip;
+    let mut missing = 0;
+
+    for bin in bins {
+        let value = cc[bin];
+        if value.is_nan() {
+            missing += 1;
+            if missing > params.max_missing_bins {
+                break;
+            }
+        } else if value < params.correlation_threshold {
+            edge = bin;
+            missing = 0;
+        } else {
+            break;
+        }
+    }
+
+    edge
+}
+
+// 0 for values at or below 0, rising to 1 at scale
+fn ramp(value: f32, scale: f32) -> f32 {
+    if value.is_nan() {
+        return 0.0;
+    }
+
+    (value / scale).clamp(0.0, 1.0)
+}
+
+fn peak(values: &[f32]) -> f32 {
+    values
+        .iter()
+        .filter(|value| !value.is_nan())
+        .fold(f32::NAN, |peak, value| f32::max(peak, *value))
+}
+
+fn mean(values: &[f32]) -> f32 {
+    let values: Vec<f32> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    if values.is_empty() {
+        return f32::NAN;
+    }
+
+    values.iter().sum::<f32>() / values.len() as f32
+}
@@ -0,0 +1,13 @@
+// Derived products computed from the moments of a volume
+
+pub mod azimuthal_shear;
+pub mod hail;
+pub mod hydrometeor_class;
+pub mod kdp;
+pub mod melting_layer;
+pub mod qpe;
+pub mod qvp;
+pub mod rotation;
+pub mod storm_cells;
+pub mod storm_relative;
+pub mod storm_tracking;
@@ -0,0 +1,219 @@
+// Quantitative precipitation estimation. Rain rates are estimated on the
+// lowest tilt from reflectivity, optionally using KDP in heavy rain and
+// ZDR elsewhere, and accumulated over a sequence of volumes on a grid.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::model::grid::Grid;
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::{RAIN_RATE, REFLECTIVITY};
+use crate::model::volume::Volume;
+
+// Z-R relationships of the form Z = a R^b
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZrRelationship {
+    // Z = 200 R^1.6, for stratiform rain
+    MarshallPalmer,
+    // Z = 250 R^1.2, for tropical rain
+    Tropical,
+    // Z = 300 R^1.4, the default WSR-88D relationship for convective rain
+    Convective,
+}
+
+impl ZrRelationship {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "marshall_palmer" => Some(Self::MarshallPalmer),
+            "tropical" => Some(Self::Tropical),
+            "convective" => Some(Self::Convective),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MarshallPalmer => "marshall_palmer",
+            Self::Tropical => "tropical",
+            Self::Convective => "convective",
+        }
+    }
+
+    // Coefficients a and b
+    pub fn coefficients(&self) -> (f32, f32) {
+        match self {
+            Self::MarshallPalmer => (200.0, 1.6),
+            Self::Tropical => (250.0, 1.2),
+            Self::Convective => (300.0, 1.4),
+        }
+    }
+
+    // Rain rate in mm/h from reflectivity in dBZ
+    pub fn rain_rate(&self, reflectivity: f32) -> f32 {
+        let (a, b) = self.coefficients();
+        (10.0_f32.powf(reflectivity / 10.0) / a).powf(1.0 / b)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct QpeParams {
+    pub relationship: ZrRelationship,
+    // Use R(KDP) where KDP and reflectivity are above their thresholds,
+    // which needs KDP to have been estimated
+    pub use_kdp: bool,
+    pub kdp_threshold: f32,
+    pub kdp_reflectivity_threshold: f32,
+    // Use R(Z, ZDR) where ZDR is at least zdr_threshold
+    pub use_zdr: bool,
+    pub zdr_threshold: f32,
+    // Reflectivity is capped at this in dBZ to limit the effect of hail
+    pub max_reflectivity: f32,
+}
+
+impl Default for QpeParams {
+    fn default() -> Self {
+        Self {
+            relationship: ZrRelationship::Convective,
+            use_kdp: false,
+            kdp_threshold: 0.3,
+            kdp_reflectivity_threshold: 35.0,
+            use_zdr: false,
+            zdr_threshold: 0.25,
+            max_reflectivity: 53.0,
+        }
+    }
+}
+
+// Estimates the rain rate of the lowest tilt with reflectivity and stores
+// it on that sweep. Returns the index of the sweep.
+pub(crate) fn estimate_rain_rate(volume: &mut Volume, params: &QpeParams) -> Option<usize> {
+    let index = volume.latest_low_level_index(REFLECTIVITY)?;
+    let sweep = &mut volume.sweeps[index];
+    let refl = sweep.reflectivity.as_ref()?;
+
+    let kdp = sweep
+        .specific_differential_phase
+        .as_ref()
+        .filter(|_| params.use_kdp);
+    let zdr = sweep
+        .differential_reflectivity
+        .as_ref()
+        .filter(|_| params.use_zdr);
+
+    let mut rates = SweepData::new(refl.radials, refl.gates);
+    for radial in 0..refl.radials {
+        for gate in 0..refl.gates {
+            let Some(z) = refl.value(radial, gate) else {
+                continue;
+            };
+
+            let rate = rain_rate(
+                z,
+                zdr.and_then(|zdr| zdr.value(radial, gate)),
+                kdp.and_then(|kdp| kdp.value(radial, gate)),
+                params,
+            );
+            rates.set_value(rate, radial, gate);
+        }
+    }
+
+    sweep.rain_rate = Some(rates);
+    sweep.sweep_type |= RAIN_RATE;
+
+    Some(index)
+}
+
+// Rain rate in mm/h, using the S band relationships R(KDP) = 44 KDP^0.822
+// and R(Z, ZDR) = 0.0067 Z^0.927 ZDR^-3.43 of Ryzhkov et al. 2005,
+// doi: 10.1175/JAM2213.1, with Z and ZDR in linear units
+fn rain_rate(reflectivity: f32, zdr: Option<f32>, kdp: Option<f32>, params: &QpeParams) -> f32 {
+    let reflectivity = f32::min(reflectivity, params.max_reflectivity);
+
+    if let Some(kdp) = kdp {
+        if kdp >= params.kdp_threshold && reflectivity >= params.kdp_reflectivity_threshold {
+            return 44.0 * kdp.powf(0.822);
+        }
+    }
+
+    if let Some(zdr) = zdr {
+        if zdr >= params.zdr_threshold {
+            let z = 10.0_f32.powf(reflectivity / 10.0);
+            let zdr = 10.0_f32.powf(zdr / 10.0);
+            return 0.0067 * z.powf(0.927) * zdr.powf(-3.43);
+        }
+    }
+
+    params.relationship.rain_rate(reflectivity)
+}
+
+// Accumulates gridded rain rates into rainfall totals. Each interval between
+// consecutive scans adds the mean of the rates at either end, so a scan's
+// rate is weighted by the time to its neighbors. Intervals longer than
+// max_gap, such as radar outages, are skipped.
+pub struct RainAccumulator {
+    pub grid: Grid,
+    // Rainfall in mm for each cell of the grid
+    pub totals: Vec<f32>,
+    // Total time covered by the accumulated intervals
+    pub duration: Duration,
+    pub max_gap: Duration,
+    previous: Option<(Vec<f32>, DateTime<Utc>)>,
+}
+
+impl RainAccumulator {
+    pub fn new(grid: Grid, max_gap: Duration) -> Self {
+        Self {
+            grid,
+            totals: vec![0.0; grid.cells()],
+            duration: Duration::zero(),
+            max_gap,
+            previous: None,
+        }
+    }
+
+    // Adds a grid of rain rates in mm/h, with NaN for cells without data,
+    // for a scan collected between start_time and end_time. Scans must be
+    // added in time order.
+    pub fn add(
+        &mut self,
+        rates: Vec<f32>,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<(), String> {
+        if rates.len() != self.grid.cells() {
+            return Err(format!(
+                "Expected {} grid cells but got {}",
+                self.grid.cells(),
+                rates.len()
+            ));
+        }
+
+        let time = start_time + (end_time - start_time) / 2;
+
+        if let Some((previous_rates, previous_time)) = &self.previous {
+            if time <= *previous_time {
+                return Err("Scans must be added in time order".to_string());
+            }
+
+            let interval = time - *previous_time;
+            if interval <= self.max_gap {
+                let hours = interval.num_milliseconds() as f32 / 3_600_000.0;
+
+                for (total, (previous, rate)) in self
+                    .totals
+                    .iter_mut()
+                    .zip(previous_rates.iter().zip(rates.iter()))
+                {
+                    let previous = if previous.is_nan() { 0.0 } else { *previous };
+                    let rate = if rate.is_nan() { 0.0 } else { *rate };
+                    *total += 0.5 * (previous + rate) * hours;
+                }
+
+                self.duration += interval;
+            }
+        }
+
+        self.previous = Some((rates, time));
+
+        Ok(())
+    }
+}
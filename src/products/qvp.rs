@@ -0,0 +1,206 @@
+// Quasi-vertical profiles, following Ryzhkov et al. 2016,
+// doi: 10.1175/JTECH-D-15-0020.1. Each range gate of a sweep is averaged
+// over azimuth and placed at the height of the beam, then the gates are
+// averaged into height bins.
+
+use chrono::{DateTime, Utc};
+
+use crate::model::geometry::beam_height;
+use crate::model::sweep::Sweep;
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::SweepType;
+use crate::model::volume::Volume;
+
+// How the gates of each range are combined over azimuth
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QvpStatistic {
+    Mean,
+    // Less sensitive to isolated contamination, at a higher cost
+    Median,
+}
+
+impl QvpStatistic {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "mean" => Some(Self::Mean),
+            "median" => Some(Self::Median),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Mean => "mean",
+            Self::Median => "median",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Qvp {
+    // Height of the center of each bin above mean sea level, in km
+    pub heights: Vec<f32>,
+    // Value of each bin, NaN where no gates had enough data
+    pub values: Vec<f32>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct QvpParams {
+    // Height of each bin in km
+    pub bin_size: f32,
+    // Height above the radar of the top of the highest bin, in km
+    pub max_height: f32,
+    // Fraction of the radials which must have data for a gate to be used
+    pub min_fraction: f32,
+    pub statistic: QvpStatistic,
+}
+
+impl Default for QvpParams {
+    fn default() -> Self {
+        Self {
+            bin_size: 0.05,
+            max_height: 8.0,
+            min_fraction: 0.3,
+            statistic: QvpStatistic::Median,
+        }
+    }
+}
+
+pub(crate) fn quasi_vertical_profile(
+    sweep: &Sweep,
+    data: &SweepData,
+    site_height: f32,
+    params: &QvpParams,
+) -> Qvp {
+    let bins = (params.max_height / params.bin_size).ceil() as usize;
+    let mut sums = vec![0.0; bins];
+    let mut counts = vec![0; bins];
+
+    let min_count = f32::max(params.min_fraction * data.radials as f32, 1.0);
+
+    let mut values = Vec::with_capacity(data.radials);
+    for gate in 0..data.gates {
+        values.clear();
+        values.extend((0..data.radials).filter_map(|radial| data.value(radial, gate)));
+
+        if (values.len() as f32) < min_count {
+            continue;
+        }
+
+        let value = match params.statistic {
+            QvpStatistic::Mean => values.iter().sum::<f32>() / values.len() as f32,
+            QvpStatistic::Median => median(&mut values),
+        };
+
+        let height = beam_height(sweep.gate_range(gate), sweep.elevation);
+        let bin = (height / params.bin_size).floor();
+        if bin < 0.0 || bin >= bins as f32 {
+            continue;
+        }
+
+        sums[bin as usize] += value;
+        counts[bin as usize] += 1;
+    }
+
+    Qvp {
+        heights: (0..bins)
+            .map(|bin| site_height + (bin as f32 + 0.5) * params.bin_size)
+            .collect(),
+        values: sums
+            .iter()
+            .zip(counts.iter())
+            .map(|(sum, count)| match count {
+                0 => f32::NAN,
+                count => sum / *count as f32,
+            })
+            .collect(),
+    }
+}
+
+// Profile of a product from the sweep closest to an elevation in radians,
+// using the latest scan when the elevation was scanned more than once.
+// Returns the profile with the start time and elevation of the sweep.
+pub(crate) fn volume_profile(
+    volume: &Volume,
+    product: SweepType,
+    elevation: f32,
+    params: &QvpParams,
+) -> Option<(Qvp, DateTime<Utc>, f32)> {
+    let sweep = volume.closest_sweep(product, elevation)?;
+
+    let site_height = volume.site.map_or(0.0, |site| site.height);
+    let profile = quasi_vertical_profile(sweep, sweep.product(product)?, site_height, params);
+
+    Some((profile, sweep.start_time, sweep.elevation))
+}
+
+// Profiles of a sequence of volumes as a time by height array
+#[derive(Clone, Debug)]
+pub struct TimeHeight {
+    pub times: Vec<DateTime<Utc>>,
+    pub heights: Vec<f32>,
+    // Row-major with one row of heights per time, NaN where missing
+    pub values: Vec<f32>,
+}
+
+// Stacks profiles with the same height bins in time order
+pub(crate) fn stack_profiles(profiles: &[(Qvp, DateTime<Utc>)]) -> Result<TimeHeight, String> {
+    let Some((first, _)) = profiles.first() else {
+        return Err("No profiles to stack".to_string());
+    };
+
+    if profiles
+        .iter()
+        .any(|(profile, _)| profile.heights != first.heights)
+    {
+        return Err("Profiles have different height bins".to_string());
+    }
+
+    let mut sorted: Vec<&(Qvp, DateTime<Utc>)> = profiles.iter().collect();
+    sorted.sort_by_key(|(_, time)| *time);
+
+    Ok(TimeHeight {
+        times: sorted.iter().map(|(_, time)| *time).collect(),
+        heights: first.heights.clone(),
+        values: sorted
+            .iter()
+            .flat_map(|(profile, _)| profile.values.iter().copied())
+            .collect(),
+    })
+}
+
+fn median(values: &mut [f32]) -> f32 {
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    let middle = values.len() / 2;
+    if values.len() & 1 == 1 {
+        values[middle]
+    } else {
+        (values[middle - 1] + values[middle]) / 2.0
+    }
+}
+
+// Averages profiles with the same bins, ignoring missing values
+pub(crate) fn mean_profile(profiles: &[Qvp]) -> Option<Qvp> {
+    let first = profiles.first()?;
+
+    let values = (0..first.values.len())
+        .map(|bin| {
+            let values: Vec<f32> = profiles
+                .iter()
+                .map(|profile| profile.values[bin])
+                .filter(|value| !value.is_nan())
+                .collect();
+
+            match values.len() {
+                0 => f32::NAN,
+                count => values.iter().sum::<f32>() / count as f32,
+            }
+        })
+        .collect();
+
+    Some(Qvp {
+        heights: first.heights.clone(),
+        values,
+    })
+}
@@ -0,0 +1,361 @@
+// Mesocyclone and tornado vortex signature detection from dealiased
+// velocity. Each tilt is scanned along azimuth for pattern vectors, runs
+// of increasing velocity with the cyclonic sense of rotation. Pattern
+// vectors on touching gates are grouped into 2D features, which are then
+// associated across consecutive tilts into 3D rotations.
+
+use crate::flood_fill::label_components::{label_components, Connectivity};
+use crate::model::geometry::{beam_height, ground_range};
+use crate::model::rotation::Rotation;
+use crate::model::sweep::{angular_distance, Sweep};
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::VELOCITY;
+use crate::model::volume::{Volume, SAME_ELEVATION_TOLERANCE};
+
+#[derive(Clone, Debug)]
+pub(crate) struct RotationParams {
+    // Smallest velocity difference in m/s across a pattern vector
+    pub min_delta_v: f32,
+    // Smallest azimuthal shear in s⁻¹ across a pattern vector
+    pub min_shear: f32,
+    // Largest distance in km across a pattern vector
+    pub max_diameter: f32,
+    // Pattern vectors are not searched beyond this range in km, where the
+    // beam is too wide to resolve rotation
+    pub max_range: f32,
+    // Highest elevation searched, in radians
+    pub max_elevation: f32,
+    // Number of pattern vectors needed for a 2D feature
+    pub min_vectors: usize,
+    // Largest horizontal distance in km between the features of a rotation
+    // on consecutive tilts
+    pub max_association_distance: f32,
+    // Number of tilts a rotation must span
+    pub min_features: usize,
+    // Gate to gate velocity difference in m/s and depth in km needed for a
+    // tornado vortex signature
+    pub tvs_delta_v: f32,
+    pub tvs_min_depth: f32,
+}
+
+impl Default for RotationParams {
+    fn default() -> Self {
+        Self {
+            min_delta_v: 10.0,
+            min_shear: 0.002,
+            max_diameter: 10.0,
+            max_range: 200.0,
+            max_elevation: 20.0_f32.to_radians(),
+            min_vectors: 3,
+            max_association_distance: 5.0,
+            min_features: 2,
+            tvs_delta_v: 25.0,
+            tvs_min_depth: 1.5,
+        }
+    }
+}
+
+// Decrease in velocity in m/s allowed within a pattern vector, so that a
+// single noisy gate does not split it
+const VELOCITY_TOLERANCE: f32 = 2.0;
+
+// Tilts a rotation may skip between two of its features
+const MAX_TILT_GAP: usize = 1;
+
+// Rotational velocity in m/s of each step of the strength rank
+const RANK_STEP: f32 = 5.0;
+
+// A run of increasing velocity along azimuth at one gate, from the first
+// radial to the last radial
+struct PatternVector {
+    gate: usize,
+    first: usize,
+    last: usize,
+    delta_v: f32,
+    gate_to_gate: f32,
+    // Distance between the first and last radials in km
+    length: f32,
+}
+
+#[derive(Clone, Debug)]
+struct Feature {
+    tilt: usize,
+    x: f32,
+    y: f32,
+    height: f32,
+    delta_v: f32,
+    gate_to_gate: f32,
+    diameter: f32,
+}
+
+pub(crate) fn detect_rotation(volume: &Volume, params: &RotationParams) -> Vec<Rotation> {
+    let site_height = volume.site.map_or(0.0, |site| site.height);
+    // Cyclonic rotation is clockwise in the southern hemisphere
+    let sense = match volume.site {
+        Some(site) if site.latitude < 0.0 => -1.0,
+        _ => 1.0,
+    };
+
+    // One sweep per elevation, skipping the supplemental low level scans
+    let mut sweeps: Vec<&Sweep> = volume
+        .sweeps
+        .iter()
+        .filter(|sweep| !sweep.is_sails && (sweep.sweep_type & VELOCITY) != 0)
+        .filter(|sweep| sweep.velocity.is_some() && sweep.elevation <= params.max_elevation)
+        .collect();
+    sweeps.sort_by(|a, b| a.elevation.total_cmp(&b.elevation));
+    sweeps.dedup_by(|a, b| (a.elevation - b.elevation).abs() < SAME_ELEVATION_TOLERANCE);
+
+    let mut features = Vec::new();
+    for (tilt, sweep) in sweeps.iter().enumerate() {
+        let velocity = sweep.velocity.as_ref().unwrap();
+        let vectors = find_pattern_vectors(sweep, velocity, sense, params);
+        features.extend(
+            group_features(sweep, velocity, &vectors, params)
+                .into_iter()
+                .map(|mut feature| {
+                    feature.tilt = tilt;
+                    feature.height += site_height;
+                    feature
+                }),
+        );
+    }
+
+    associate_features(features, params)
+        .iter()
+        .map(|features| rotation(features, params))
+        .collect()
+}
+
+fn find_pattern_vectors(
+    sweep: &Sweep,
+    velocity: &SweepData,
+    sense: f32,
+    params: &RotationParams,
+) -> Vec<PatternVector> {
+    let radials = velocity.radials;
+    let value = |radial: usize, gate: usize| {
+        velocity
+            .value(radial % radials, gate)
+            .map(|value| sense * value)
+    };
+    // Radials are adjacent when they follow each other within a little
+    // more than one azimuth step, which breaks runs at gaps in the sweep
+    let adjacent = |radial: usize| {
+        angular_distance(
+            sweep.azimuths[radial % radials],
+            sweep.azimuths[(radial + 1) % radials],
+        ) < 1.5 * sweep.az_step
+    };
+
+    let mut vectors = Vec::new();
+
+    for gate in 0..velocity.gates {
+        let range = sweep.gate_range(gate);
+        if range > params.max_range {
+            break;
+        }
+
+        let distance = ground_range(range, sweep.elevation);
+        if distance <= 0.0 {
+            continue;
+        }
+
+        for first in 0..radials {
+            let Some(start) = value(first, gate) else {
+                continue;
+            };
+
+            // Only start at the bottom of a run
+            let previous = first + radials - 1;
+            if adjacent(previous) && value(previous, gate).is_some_and(|value| value < start) {
+                continue;
+            }
+
+            let mut last = first;
+            let mut peak = start;
+            let mut current = first;
+            let mut gate_to_gate: f32 = 0.0;
+            let mut previous_value = start;
+
+            while current + 1 < first + radials && adjacent(current) {
+                let Some(next) = value(current + 1, gate) else {
+                    break;
+                };
+                if next < peak - VELOCITY_TOLERANCE {
+                    break;
+                }
+
+                let length = distance * (current + 1 - first) as f32 * sweep.az_step;
+                if length > params.max_diameter {
+                    break;
+                }
+
+                current += 1;
+                gate_to_gate = f32::max(gate_to_gate, next - previous_value);
+                previous_value = next;
+                if next > peak {
+                    peak = next;
+                    last = current;
+                }
+            }
+
+            let delta_v = peak - start;
+            let length = distance * (last - first) as f32 * sweep.az_step;
+            if last == first || delta_v < params.min_delta_v {
+                continue;
+            }
+
+            if delta_v / (length * 1000.0) < params.min_shear {
+                continue;
+            }
+
+            vectors.push(PatternVector {
+                gate,
+                first,
+                last,
+                delta_v,
+                gate_to_gate,
+                length,
+            });
+        }
+    }
+
+    vectors
+}
+
+// Groups the pattern vectors whose gates touch into 2D features
+fn group_features(
+    sweep: &Sweep,
+    velocity: &SweepData,
+    vectors: &[PatternVector],
+    params: &RotationParams,
+) -> Vec<Feature> {
+    let radials = velocity.radials;
+    let gates = velocity.gates;
+
+    let mut covered = vec![false; radials * gates];
+    for vector in vectors {
+        for radial in vector.first..=vector.last {
+            covered[(radial % radials) * gates + vector.gate] = true;
+        }
+    }
+
+    let components = label_components(
+        radials,
+        gates,
+        Connectivity::Four,
+        sweep.is_complete(),
+        |radial, gate| covered[radial * gates + gate].then_some(0),
+    );
+
+    let mut groups: Vec<Vec<&PatternVector>> = vec![Vec::new(); components.sizes.len()];
+    for vector in vectors {
+        let label = components.labels[vector.first][vector.gate];
+        groups[label as usize - 1].push(vector);
+    }
+
+    groups
+        .iter()
+        .filter(|group| group.len() >= params.min_vectors)
+        .map(|group| {
+            let total: f32 = group.iter().map(|vector| vector.delta_v).sum();
+            let mut x = 0.0;
+            let mut y = 0.0;
+            let mut height = 0.0;
+
+            for vector in group.iter() {
+                let first = sweep.azimuths[vector.first];
+                let last = sweep.azimuths[vector.last % radials];
+                let azimuth = first + angular_distance(first, last) / 2.0;
+                let range = sweep.gate_range(vector.gate);
+                let distance = ground_range(range, sweep.elevation);
+
+                let weight = vector.delta_v / total;
+                x += weight * distance * azimuth.sin();
+                y += weight * distance * azimuth.cos();
+                height += weight * beam_height(range, sweep.elevation);
+            }
+
+            let strongest = group
+                .iter()
+                .max_by(|a, b| a.delta_v.total_cmp(&b.delta_v))
+                .unwrap();
+
+            Feature {
+                tilt: 0,
+                x,
+                y,
+                height,
+                delta_v: strongest.delta_v,
+                gate_to_gate: group
+                    .iter()
+                    .map(|vector| vector.gate_to_gate)
+                    .fold(0.0, f32::max),
+                diameter: strongest.length,
+            }
+        })
+        .collect()
+}
+
+// Stacks the features into rotations from the lowest tilt up. Each feature
+// extends the nearest rotation which ends on one of the tilts just below
+// it, with the strongest features choosing first.
+fn associate_features(mut features: Vec<Feature>, params: &RotationParams) -> Vec<Vec<Feature>> {
+    features.sort_by(|a, b| a.tilt.cmp(&b.tilt).then(b.delta_v.total_cmp(&a.delta_v)));
+
+    let mut rotations: Vec<Vec<Feature>> = Vec::new();
+
+    for feature in features {
+        let nearest = rotations
+            .iter()
+            .enumerate()
+            .filter_map(|(index, rotation)| {
+                let top = rotation.last().unwrap();
+                if top.tilt >= feature.tilt || feature.tilt - top.tilt > MAX_TILT_GAP + 1 {
+                    return None;
+                }
+
+                let distance = (top.x - feature.x).hypot(top.y - feature.y);
+                (distance <= params.max_association_distance).then_some((index, distance))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        match nearest {
+            Some((index, _)) => rotations[index].push(feature),
+            None => rotations.push(vec![feature]),
+        }
+    }
+
+    rotations.retain(|rotation| rotation.len() >= params.min_features);
+    rotations
+}
+
+fn rotation(features: &[Feature], params: &RotationParams) -> Rotation {
+    let lowest = features.first().unwrap();
+    let highest = features.last().unwrap();
+    let strongest = features
+        .iter()
+        .max_by(|a, b| a.delta_v.total_cmp(&b.delta_v))
+        .unwrap();
+
+    let rotational_velocity = strongest.delta_v / 2.0;
+    let depth = highest.height - lowest.height;
+
+    Rotation {
+        x: lowest.x,
+        y: lowest.y,
+        base: lowest.height,
+        top: highest.height,
+        rotational_velocity,
+        low_level_rotational_velocity: lowest.delta_v / 2.0,
+        diameter: strongest.diameter,
+        gate_to_gate_delta_v: features
+            .iter()
+            .map(|feature| feature.gate_to_gate)
+            .fold(0.0, f32::max),
+        strength_rank: (rotational_velocity / RANK_STEP).floor() as u8,
+        is_tvs: lowest.gate_to_gate >= params.tvs_delta_v && depth >= params.tvs_min_depth,
+        features: features.len(),
+    }
+}
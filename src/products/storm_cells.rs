@@ -0,0 +1,290 @@
+// Storm cell identification in the style of SCIT. Each tilt is segmented
+// into connected components of reflectivity above a set of thresholds,
+// keeping the highest threshold components which are large enough so
+// that cores embedded in a larger echo are separated. The components of
+// consecutive tilts are then associated vertically into cells by the
+// horizontal distance between their centroids.
+
+use crate::flood_fill::label_components::{label_components, Connectivity};
+use crate::model::geometry::{beam_height, ground_range};
+use crate::model::storm_cell::{StormCell, StormCells};
+use crate::model::sweep::Sweep;
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::REFLECTIVITY;
+use crate::model::volume::{Volume, SAME_ELEVATION_TOLERANCE};
+
+#[derive(Clone, Debug)]
+pub(crate) struct StormCellParams {
+    // Reflectivity thresholds in dBZ used to segment each tilt
+    pub thresholds: Vec<f32>,
+    // Smallest area of a component in km²
+    pub min_area: f32,
+    // Largest horizontal distance in km between the centroids of the
+    // components of a cell on consecutive tilts
+    pub max_association_distance: f32,
+    // Number of tilts a cell must span
+    pub min_components: usize,
+}
+
+impl Default for StormCellParams {
+    fn default() -> Self {
+        Self {
+            thresholds: vec![30.0, 35.0, 40.0, 45.0, 50.0, 55.0, 60.0],
+            min_area: 10.0,
+            max_association_distance: 5.0,
+            min_components: 2,
+        }
+    }
+}
+
+// Reflectivity above which the VIL is capped to limit the effect of hail
+const VIL_MAX_REFLECTIVITY: f32 = 56.0;
+
+// Tilts a cell may skip between two of its components
+const MAX_TILT_GAP: usize = 1;
+
+#[derive(Clone, Debug)]
+struct Component {
+    tilt: usize,
+    x: f32,
+    y: f32,
+    height: f32,
+    max_reflectivity: f32,
+    // Liquid water weighted area, used to weight the cell centroid
+    mass: f32,
+}
+
+#[derive(Default)]
+struct ComponentSums {
+    area: f32,
+    mass: f32,
+    x: f32,
+    y: f32,
+    height: f32,
+    max_reflectivity: f32,
+    // Contains gates of a component kept at a higher threshold
+    claimed: bool,
+}
+
+pub(crate) fn identify_storm_cells(volume: &Volume, params: &StormCellParams) -> StormCells {
+    let site_height = volume.site.map_or(0.0, |site| site.height);
+
+    // One sweep per elevation, skipping the supplemental low level scans
+    let mut sweeps: Vec<&Sweep> = volume
+        .sweeps
+        .iter()
+        .filter(|sweep| !sweep.is_sails && (sweep.sweep_type & REFLECTIVITY) != 0)
+        .filter(|sweep| sweep.reflectivity.is_some())
+        .collect();
+    sweeps.sort_by(|a, b| a.elevation.total_cmp(&b.elevation));
+    sweeps.dedup_by(|a, b| (a.elevation - b.elevation).abs() < SAME_ELEVATION_TOLERANCE);
+
+    let mut thresholds = params.thresholds.clone();
+    thresholds.sort_by(|a, b| b.total_cmp(a));
+
+    let mut components = Vec::new();
+    for (tilt, sweep) in sweeps.iter().enumerate() {
+        let reflectivity = sweep.reflectivity.as_ref().unwrap();
+        components.extend(
+            find_components(sweep, reflectivity, &thresholds, params.min_area)
+                .into_iter()
+                .map(|mut component| {
+                    component.tilt = tilt;
+                    component.height += site_height;
+                    component
+                }),
+        );
+    }
+
+    let time = volume
+        .sweeps
+        .iter()
+        .map(|sweep| sweep.start_time)
+        .min()
+        .unwrap_or_default();
+
+    StormCells {
+        time,
+        cells: associate_components(components, params)
+            .iter()
+            .map(|components| storm_cell(components))
+            .collect(),
+    }
+}
+
+// Finds the components of one tilt, from the highest threshold down. A
+// component is dropped when it contains a component already kept.
+fn find_components(
+    sweep: &Sweep,
+    reflectivity: &SweepData,
+    thresholds: &[f32],
+    min_area: f32,
+) -> Vec<Component> {
+    let radials = reflectivity.radials;
+    let gates = reflectivity.gates;
+
+    let mut claimed = vec![false; radials * gates];
+    let mut components = Vec::new();
+
+    for threshold in thresholds {
+        let labelled = label_components(
+            radials,
+            gates,
+            Connectivity::Eight,
+            sweep.is_complete(),
+            |radial, gate| match reflectivity.value(radial, gate) {
+                Some(value) if value >= *threshold => Some(0),
+                _ => None,
+            },
+        );
+
+        let mut sums: Vec<ComponentSums> = (0..labelled.sizes.len())
+            .map(|_| ComponentSums::default())
+            .collect();
+
+        for radial in 0..radials {
+            let azimuth = sweep.azimuths[radial];
+            let elevation = sweep.elevations[radial];
+
+            for gate in 0..gates {
+                let label = labelled.labels[radial][gate];
+                if label == 0 {
+                    continue;
+                }
+
+                let value = reflectivity.get_value(radial, gate);
+                let range = sweep.gate_range(gate);
+                let distance = ground_range(range, elevation);
+                let area = distance * sweep.az_step * sweep.range_step;
+                let mass = liquid_water(value) * area;
+
+                let component = &mut sums[label as usize - 1];
+                component.area += area;
+                component.mass += mass;
+                component.x += mass * distance * azimuth.sin();
+                component.y += mass * distance * azimuth.cos();
+                component.height += mass * beam_height(range, elevation);
+                component.max_reflectivity = f32::max(component.max_reflectivity, value);
+                component.claimed |= claimed[radial * gates + gate];
+            }
+        }
+
+        let kept: Vec<bool> = sums
+            .iter()
+            .map(|sums| sums.area >= min_area && !sums.claimed && sums.mass > 0.0)
+            .collect();
+
+        for radial in 0..radials {
+            for gate in 0..gates {
+                let label = labelled.labels[radial][gate];
+                if label != 0 && kept[label as usize - 1] {
+                    claimed[radial * gates + gate] = true;
+                }
+            }
+        }
+
+        components.extend(sums.iter().zip(kept.iter()).filter(|(_, kept)| **kept).map(
+            |(sums, _)| Component {
+                tilt: 0,
+                x: sums.x / sums.mass,
+                y: sums.y / sums.mass,
+                height: sums.height / sums.mass,
+                max_reflectivity: sums.max_reflectivity,
+                mass: sums.mass,
+            },
+        ));
+    }
+
+    components
+}
+
+// Stacks the components into cells from the lowest tilt up. Each component
+// extends the nearest cell which ends on one of the tilts just below it,
+// with the most massive components choosing first.
+fn associate_components(
+    mut components: Vec<Component>,
+    params: &StormCellParams,
+) -> Vec<Vec<Component>> {
+    components.sort_by(|a, b| a.tilt.cmp(&b.tilt).then(b.mass.total_cmp(&a.mass)));
+
+    let mut cells: Vec<Vec<Component>> = Vec::new();
+
+    for component in components {
+        let nearest = cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| {
+                let top = cell.last().unwrap();
+                if top.tilt >= component.tilt || component.tilt - top.tilt > MAX_TILT_GAP + 1 {
+                    return None;
+                }
+
+                let distance = (top.x - component.x).hypot(top.y - component.y);
+                (distance <= params.max_association_distance).then_some((index, distance))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        match nearest {
+            Some((index, _)) => cells[index].push(component),
+            None => cells.push(vec![component]),
+        }
+    }
+
+    cells.retain(|cell| cell.len() >= params.min_components);
+    cells
+}
+
+fn storm_cell(components: &[Component]) -> StormCell {
+    let mass: f32 = components.iter().map(|component| component.mass).sum();
+    let weighted = |value: fn(&Component) -> f32| {
+        components
+            .iter()
+            .map(|component| component.mass * value(component))
+            .sum::<f32>()
+            / mass
+    };
+
+    let strongest = components
+        .iter()
+        .max_by(|a, b| a.max_reflectivity.total_cmp(&b.max_reflectivity))
+        .unwrap();
+
+    // Integrate the liquid water of the strongest reflectivity of each tilt
+    // between consecutive components, in kg/m²
+    let vil = components
+        .windows(2)
+        .map(|pair| {
+            let lower = capped_reflectivity(pair[0].max_reflectivity);
+            let upper = capped_reflectivity(pair[1].max_reflectivity);
+            let depth = (pair[1].height - pair[0].height) * 1000.0;
+            3.44e-6 * ((lower + upper) / 2.0).powf(4.0 / 7.0) * depth
+        })
+        .sum();
+
+    StormCell {
+        x: weighted(|component| component.x),
+        y: weighted(|component| component.y),
+        height: weighted(|component| component.height),
+        base: components.first().unwrap().height,
+        top: components.last().unwrap().height,
+        max_reflectivity: strongest.max_reflectivity,
+        max_reflectivity_height: strongest.height,
+        vil,
+        components: components.len(),
+        levels: components
+            .iter()
+            .map(|component| (component.height, component.max_reflectivity))
+            .collect(),
+        hail: None,
+    }
+}
+
+// Linear reflectivity in mm⁶/m³, capped at the VIL maximum
+fn capped_reflectivity(reflectivity: f32) -> f32 {
+    10.0_f32.powf(f32::min(reflectivity, VIL_MAX_REFLECTIVITY) / 10.0)
+}
+
+// Relative liquid water content of a gate, proportional to Z^(4/7)
+fn liquid_water(reflectivity: f32) -> f32 {
+    capped_reflectivity(reflectivity).powf(4.0 / 7.0)
+}
@@ -0,0 +1,139 @@
+// Storm-relative velocity, the dealiased radial velocity with the radial
+// component of the storm motion removed, which makes rotation within a
+// moving storm easier to see. Motions are (u, v) in m/s towards the east
+// and north.
+
+use rayon::prelude::*;
+
+use crate::model::geometry::beam_height;
+use crate::model::sweep::Sweep;
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::{STORM_RELATIVE_VELOCITY, VELOCITY};
+use crate::model::volume::Volume;
+
+#[derive(Clone, Debug)]
+pub(crate) struct MeanWindParams {
+    // Top of the layer in km above the radar
+    pub max_height: f32,
+    // Gates closer than this in km are skipped
+    pub min_range: f32,
+    // Highest elevation used, in radians, where the radial velocity still
+    // mostly measures the horizontal wind
+    pub max_elevation: f32,
+}
+
+impl Default for MeanWindParams {
+    fn default() -> Self {
+        Self {
+            max_height: 6.0,
+            min_range: 10.0,
+            max_elevation: 20.0_f32.to_radians(),
+        }
+    }
+}
+
+// Fewest gates the mean wind is fitted to
+const MIN_WIND_GATES: usize = 1000;
+
+// Motion in m/s of a storm moving from a direction in degrees clockwise
+// from north, as storm motions are usually reported
+pub(crate) fn motion_from_direction(direction: f32, speed: f32) -> (f32, f32) {
+    let towards = (direction + 180.0).to_radians();
+    (speed * towards.sin(), speed * towards.cos())
+}
+
+// Estimates the mean wind of the layer from the radar to max_height by
+// fitting a uniform wind to the dealiased radial velocity of every gate in
+// the layer, in the manner of a velocity azimuth display
+pub(crate) fn mean_wind(volume: &Volume, params: &MeanWindParams) -> Option<(f32, f32)> {
+    let mut count = 0;
+    let (mut ss, mut cc, mut sc, mut sv, mut cv) = (0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64);
+
+    for sweep in volume.sweeps.iter() {
+        if sweep.sweep_type & VELOCITY == 0 || sweep.elevation > params.max_elevation {
+            continue;
+        }
+        let Some(velocity) = sweep.velocity.as_ref() else {
+            continue;
+        };
+
+        for radial in 0..velocity.radials {
+            let azimuth = sweep.azimuths[radial];
+            let elevation = sweep.elevations[radial];
+            let s = (azimuth.sin() * elevation.cos()) as f64;
+            let c = (azimuth.cos() * elevation.cos()) as f64;
+
+            for gate in 0..velocity.gates {
+                let range = sweep.gate_range(gate);
+                if range < params.min_range {
+                    continue;
+                }
+                if beam_height(range, elevation) > params.max_height {
+                    break;
+                }
+
+                let Some(value) = velocity.value(radial, gate) else {
+                    continue;
+                };
+                let value = value as f64;
+
+                count += 1;
+                ss += s * s;
+                cc += c * c;
+                sc += s * c;
+                sv += s * value;
+                cv += c * value;
+            }
+        }
+    }
+
+    // Gates from only a narrow range of azimuths cannot resolve both
+    // components
+    let determinant = ss * cc - sc * sc;
+    if count < MIN_WIND_GATES || determinant <= 1e-6 * ss * cc {
+        return None;
+    }
+
+    Some((
+        ((sv * cc - cv * sc) / determinant) as f32,
+        ((cv * ss - sv * sc) / determinant) as f32,
+    ))
+}
+
+pub(crate) fn storm_relative_velocity(volume: &mut Volume, motion: (f32, f32)) {
+    volume
+        .sweeps
+        .par_iter_mut()
+        .for_each(|sweep| storm_relative_sweep(sweep, motion));
+    volume.storm_motion = Some(motion);
+}
+
+fn storm_relative_sweep(sweep: &mut Sweep, (u, v): (f32, f32)) {
+    let Some(velocity) = sweep.velocity.as_ref() else {
+        return;
+    };
+
+    let mut relative = SweepData::new(velocity.radials, velocity.gates);
+    for radial in 0..velocity.radials {
+        let azimuth = sweep.azimuths[radial];
+        let elevation = sweep.elevations[radial];
+        // Component of the storm motion along the beam, positive outbound
+        // like the radial velocity
+        let outbound = (u * azimuth.sin() + v * azimuth.cos()) * elevation.cos();
+
+        for gate in 0..velocity.gates {
+            match velocity.value(radial, gate) {
+                Some(value) => relative.set_value(value - outbound, radial, gate),
+                None if velocity.is_range_folded(radial, gate) => {
+                    relative.set_range_folded(radial, gate)
+                }
+                None => {}
+            }
+        }
+    }
+
+    sweep.storm_relative_velocity = Some(relative);
+    if sweep.sweep_type & VELOCITY != 0 {
+        sweep.sweep_type |= STORM_RELATIVE_VELOCITY;
+    }
+}
@@ -0,0 +1,175 @@
+// Tracks storm cells across consecutive volumes. The cells of the previous
+// volume are moved along their motion to the time of the new volume and
+// matched to the nearest new cells within the distance a storm could have
+// travelled, closest pairs first. Matched cells keep their id and get a
+// motion vector from their displacement, which is used to forecast their
+// positions.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::model::storm_cell::{StormCell, StormCells};
+
+#[derive(Clone, Debug)]
+pub struct TrackedCell {
+    pub id: u32,
+    pub cell: StormCell,
+    // Motion in m/s towards the east and north, once the cell has been
+    // matched across two volumes
+    pub motion: Option<(f32, f32)>,
+    // Forecast positions in km east and north of the radar, one for each
+    // of the tracker's forecast intervals when the motion is known
+    pub forecast: Vec<(f32, f32)>,
+    // Positions of the cell in the previous volumes, oldest first
+    pub track: Vec<(f32, f32)>,
+}
+
+pub struct StormTracker {
+    // Fastest storm motion in m/s considered when matching cells
+    pub max_speed: f32,
+    // Volumes further apart than this start new tracks
+    pub max_gap: Duration,
+    pub forecast_intervals: Vec<Duration>,
+    next_id: u32,
+    previous: Option<(Vec<TrackedCell>, DateTime<Utc>)>,
+}
+
+// Weight of the newest displacement in the smoothed motion of a cell
+const MOTION_SMOOTHING: f32 = 0.5;
+
+impl StormTracker {
+    pub fn new(max_speed: f32, max_gap: Duration, forecast_intervals: Vec<Duration>) -> Self {
+        Self {
+            max_speed,
+            max_gap,
+            forecast_intervals,
+            next_id: 1,
+            previous: None,
+        }
+    }
+
+    // Matches the cells of a volume to the tracked cells of the previous
+    // volume. Volumes must be added in time order.
+    pub fn update(&mut self, storm_cells: &StormCells) -> Result<Vec<TrackedCell>, String> {
+        let time = storm_cells.time;
+
+        let mut matches: Vec<Option<&TrackedCell>> = vec![None; storm_cells.cells.len()];
+        let mut seconds = 0.0;
+
+        if let Some((previous_cells, previous_time)) = &self.previous {
+            if time <= *previous_time {
+                return Err("Volumes must be added in time order".to_string());
+            }
+
+            let interval = time - *previous_time;
+            if interval <= self.max_gap {
+                seconds = interval.num_milliseconds() as f32 / 1000.0;
+                let max_distance = self.max_speed * seconds / 1000.0;
+
+                let mut pairs = Vec::new();
+                for (previous_index, previous) in previous_cells.iter().enumerate() {
+                    let (x, y) = predict(previous, seconds);
+                    for (index, cell) in storm_cells.cells.iter().enumerate() {
+                        let distance = (cell.x - x).hypot(cell.y - y);
+                        if distance <= max_distance {
+                            pairs.push((distance, previous_index, index));
+                        }
+                    }
+                }
+                pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                let mut previous_matched = vec![false; previous_cells.len()];
+                for (_, previous_index, index) in pairs {
+                    if previous_matched[previous_index] || matches[index].is_some() {
+                        continue;
+                    }
+
+                    previous_matched[previous_index] = true;
+                    matches[index] = Some(&previous_cells[previous_index]);
+                }
+            }
+        }
+
+        let mut tracked = Vec::with_capacity(storm_cells.cells.len());
+        for (cell, previous) in storm_cells.cells.iter().zip(matches) {
+            let tracked_cell = match previous {
+                Some(previous) => {
+                    let displacement = (
+                        (cell.x - previous.cell.x) * 1000.0 / seconds,
+                        (cell.y - previous.cell.y) * 1000.0 / seconds,
+                    );
+                    let motion = match previous.motion {
+                        Some((u, v)) => (
+                            MOTION_SMOOTHING * displacement.0 + (1.0 - MOTION_SMOOTHING) * u,
+                            MOTION_SMOOTHING * displacement.1 + (1.0 - MOTION_SMOOTHING) * v,
+                        ),
+                        None => displacement,
+                    };
+
+                    let mut track = previous.track.clone();
+                    track.push((previous.cell.x, previous.cell.y));
+
+                    TrackedCell {
+                        id: previous.id,
+                        cell: cell.clone(),
+                        motion: Some(motion),
+                        forecast: Vec::new(),
+                        track,
+                    }
+                }
+                None => {
+                    self.next_id += 1;
+                    TrackedCell {
+                        id: self.next_id - 1,
+                        cell: cell.clone(),
+                        motion: None,
+                        forecast: Vec::new(),
+                        track: Vec::new(),
+                    }
+                }
+            };
+
+            tracked.push(tracked_cell);
+        }
+
+        for tracked_cell in tracked.iter_mut() {
+            if tracked_cell.motion.is_none() {
+                continue;
+            }
+
+            tracked_cell.forecast = self
+                .forecast_intervals
+                .iter()
+                .map(|interval| predict(tracked_cell, interval.num_milliseconds() as f32 / 1000.0))
+                .collect();
+        }
+
+        self.previous = Some((tracked.clone(), time));
+
+        Ok(tracked)
+    }
+
+    // Mean motion of the cells of the latest volume whose motion is known
+    pub fn mean_motion(&self) -> Option<(f32, f32)> {
+        let (cells, _) = self.previous.as_ref()?;
+        let motions: Vec<(f32, f32)> = cells.iter().filter_map(|cell| cell.motion).collect();
+        if motions.is_empty() {
+            return None;
+        }
+
+        let count = motions.len() as f32;
+        Some((
+            motions.iter().map(|(u, _)| u).sum::<f32>() / count,
+            motions.iter().map(|(_, v)| v).sum::<f32>() / count,
+        ))
+    }
+}
+
+// Position of a cell in km after moving along its motion for a number of
+// seconds, or its current position when the motion is unknown
+fn predict(tracked_cell: &TrackedCell, seconds: f32) -> (f32, f32) {
+    let (u, v) = tracked_cell.motion.unwrap_or((0.0, 0.0));
+    (
+        tracked_cell.cell.x + u * seconds / 1000.0,
+        tracked_cell.cell.y + v * seconds / 1000.0,
+    )
+}
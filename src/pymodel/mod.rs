@@ -1,4 +1,36 @@
+pub mod py_azimuthal_shear_options;
+pub mod py_band_options;
+pub mod py_biological_filter_options;
 pub mod py_chunk;
 pub mod py_chunk_identifier;
+pub mod py_clutter_filter_options;
+pub mod py_colormap;
+pub mod py_contour_options;
+pub mod py_contours;
+pub mod py_convert_options;
+pub mod py_geotiff_options;
+pub mod py_hail;
+pub mod py_hail_options;
+pub mod py_image;
+pub mod py_kdp_options;
 pub mod py_level2_file;
+pub mod py_melting_layer;
+pub mod py_melting_layer_options;
+pub mod py_mesh_accumulator;
+pub mod py_qpe_options;
+pub mod py_qvp;
+pub mod py_qvp_options;
+pub mod py_rain_accumulator;
+pub mod py_rain_rate_grid;
+pub mod py_raster;
+pub mod py_render_options;
+pub mod py_rotation;
+pub mod py_rotation_options;
+pub mod py_shear_layers;
+pub mod py_storm_cell;
+pub mod py_storm_cell_options;
+pub mod py_storm_relative_options;
+pub mod py_storm_tracker;
 pub mod py_sweep;
+pub mod py_sweep_buffer;
+pub mod py_time_height;
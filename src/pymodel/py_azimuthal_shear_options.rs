@@ -0,0 +1,69 @@
+use pyo3::prelude::*;
+
+use crate::model::grid::Grid;
+use crate::products::azimuthal_shear::AzimuthalShearParams;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyAzimuthalShearOptions {
+    // Width of the LLSD kernel across and along the beam, in km
+    #[pyo3(get, set)]
+    pub azimuthal_width: f32,
+    #[pyo3(get, set)]
+    pub radial_width: f32,
+    // Bottom and top of the low and mid level layers in km above the radar
+    #[pyo3(get, set)]
+    pub low_layer: (f32, f32),
+    #[pyo3(get, set)]
+    pub mid_layer: (f32, f32),
+    // Number of cells along each side of the layer grids
+    #[pyo3(get, set)]
+    pub grid_size: usize,
+    // Width of each grid cell in km
+    #[pyo3(get, set)]
+    pub grid_spacing: f32,
+}
+
+#[pymethods]
+impl PyAzimuthalShearOptions {
+    #[new]
+    #[pyo3(signature = (
+        azimuthal_width = AzimuthalShearParams::default().azimuthal_width,
+        radial_width = AzimuthalShearParams::default().radial_width,
+        low_layer = (0.0, 2.0),
+        mid_layer = (3.0, 6.0),
+        grid_size = Grid::default().size,
+        grid_spacing = Grid::default().spacing,
+    ))]
+    fn py_new(
+        azimuthal_width: f32,
+        radial_width: f32,
+        low_layer: (f32, f32),
+        mid_layer: (f32, f32),
+        grid_size: usize,
+        grid_spacing: f32,
+    ) -> Self {
+        Self {
+            azimuthal_width,
+            radial_width,
+            low_layer,
+            mid_layer,
+            grid_size,
+            grid_spacing,
+        }
+    }
+}
+
+impl PyAzimuthalShearOptions {
+    pub(crate) fn params(&self) -> AzimuthalShearParams {
+        AzimuthalShearParams {
+            azimuthal_width: self.azimuthal_width,
+            radial_width: self.radial_width,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn grid(&self) -> Grid {
+        Grid::new(self.grid_size, self.grid_spacing)
+    }
+}
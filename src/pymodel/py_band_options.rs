@@ -0,0 +1,95 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::model::sweep_type::product_from_name;
+use crate::render::raster::Band;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyBandOptions {
+    // Product of the band, named like the PyLevel2File fields
+    #[pyo3(get)]
+    pub product: String,
+    // How the band is resampled from the volume: "tilt" for the sweep
+    // closest to elevation, "composite" for the largest value of every
+    // sweep or "cappi" for the value at altitude
+    #[pyo3(get)]
+    pub method: String,
+    // Elevation in degrees of a tilt band
+    #[pyo3(get, set)]
+    pub elevation: f32,
+    // Altitude in km above mean sea level of a CAPPI band
+    #[pyo3(get, set)]
+    pub altitude: f32,
+}
+
+#[pymethods]
+impl PyBandOptions {
+    #[new]
+    #[pyo3(signature = (
+        product = "reflectivity",
+        method = "tilt",
+        elevation = 0.5,
+        altitude = 3.0,
+    ))]
+    fn py_new(product: &str, method: &str, elevation: f32, altitude: f32) -> PyResult<Self> {
+        if product_from_name(product).is_none() {
+            return Err(PyValueError::new_err(format!(
+                "Unexpected product: {}",
+                product
+            )));
+        }
+
+        if !matches!(method, "tilt" | "composite" | "cappi") {
+            return Err(PyValueError::new_err(format!(
+                "Unexpected method: {}",
+                method
+            )));
+        }
+
+        Ok(Self {
+            product: product.to_string(),
+            method: method.to_string(),
+            elevation,
+            altitude,
+        })
+    }
+}
+
+impl Default for PyBandOptions {
+    fn default() -> Self {
+        Self {
+            product: "reflectivity".to_string(),
+            method: "tilt".to_string(),
+            elevation: 0.5,
+            altitude: 3.0,
+        }
+    }
+}
+
+impl PyBandOptions {
+    pub(crate) fn band(&self) -> Band {
+        let product = product_from_name(&self.product).expect("Product is validated on creation");
+
+        match self.method.as_str() {
+            "composite" => Band::Composite { product },
+            "cappi" => Band::Cappi {
+                product,
+                altitude: self.altitude,
+            },
+            _ => Band::Tilt {
+                product,
+                elevation: self.elevation.to_radians(),
+            },
+        }
+    }
+
+    // Description of the band in the GeoTIFF
+    pub(crate) fn name(&self) -> String {
+        match self.method.as_str() {
+            "composite" => format!("{} composite", self.product),
+            "cappi" => format!("{} CAPPI {} km", self.product, self.altitude),
+            _ => format!("{} {}°", self.product, self.elevation),
+        }
+    }
+}
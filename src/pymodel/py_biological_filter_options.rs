@@ -0,0 +1,101 @@
+use pyo3::prelude::*;
+
+use crate::filter::biological::BiologicalFilterParams;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyBiologicalFilterOptions {
+    // Number of gates either side of a gate used for the textures
+    #[pyo3(get, set)]
+    pub window_half_width: usize,
+    // Correlation coefficient ramp, gates at or above the upper value are
+    // never masked
+    #[pyo3(get, set)]
+    pub correlation_low: f32,
+    #[pyo3(get, set)]
+    pub correlation_high: f32,
+    // Absolute differential reflectivity ramp in dB
+    #[pyo3(get, set)]
+    pub zdr_low: f32,
+    #[pyo3(get, set)]
+    pub zdr_high: f32,
+    // Differential reflectivity texture ramp in dB
+    #[pyo3(get, set)]
+    pub zdr_texture_low: f32,
+    #[pyo3(get, set)]
+    pub zdr_texture_high: f32,
+    // Differential phase texture ramp in degrees
+    #[pyo3(get, set)]
+    pub use_differential_phase: bool,
+    #[pyo3(get, set)]
+    pub phidp_texture_low: f32,
+    #[pyo3(get, set)]
+    pub phidp_texture_high: f32,
+    // Weighted score at or above which a gate is masked
+    #[pyo3(get, set)]
+    pub score_threshold: f32,
+}
+
+#[pymethods]
+impl PyBiologicalFilterOptions {
+    #[new]
+    #[pyo3(signature = (
+        window_half_width = BiologicalFilterParams::default().window_half_width,
+        correlation_low = BiologicalFilterParams::default().correlation_low,
+        correlation_high = BiologicalFilterParams::default().correlation_high,
+        zdr_low = BiologicalFilterParams::default().zdr_low,
+        zdr_high = BiologicalFilterParams::default().zdr_high,
+        zdr_texture_low = BiologicalFilterParams::default().zdr_texture_low,
+        zdr_texture_high = BiologicalFilterParams::default().zdr_texture_high,
+        use_differential_phase = BiologicalFilterParams::default().use_differential_phase,
+        phidp_texture_low = BiologicalFilterParams::default().phidp_texture_low,
+        phidp_texture_high = BiologicalFilterParams::default().phidp_texture_high,
+        score_threshold = BiologicalFilterParams::default().score_threshold,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        window_half_width: usize,
+        correlation_low: f32,
+        correlation_high: f32,
+        zdr_low: f32,
+        zdr_high: f32,
+        zdr_texture_low: f32,
+        zdr_texture_high: f32,
+        use_differential_phase: bool,
+        phidp_texture_low: f32,
+        phidp_texture_high: f32,
+        score_threshold: f32,
+    ) -> Self {
+        Self {
+            window_half_width,
+            correlation_low,
+            correlation_high,
+            zdr_low,
+            zdr_high,
+            zdr_texture_low,
+            zdr_texture_high,
+            use_differential_phase,
+            phidp_texture_low,
+            phidp_texture_high,
+            score_threshold,
+        }
+    }
+}
+
+impl PyBiologicalFilterOptions {
+    pub(crate) fn params(&self) -> BiologicalFilterParams {
+        BiologicalFilterParams {
+            window_half_width: self.window_half_width,
+            correlation_low: self.correlation_low,
+            correlation_high: self.correlation_high,
+            zdr_low: self.zdr_low,
+            zdr_high: self.zdr_high,
+            zdr_texture_low: self.zdr_texture_low,
+            zdr_texture_high: self.zdr_texture_high,
+            use_differential_phase: self.use_differential_phase,
+            phidp_texture_low: self.phidp_texture_low,
+            phidp_texture_high: self.phidp_texture_high,
+            score_threshold: self.score_threshold,
+        }
+    }
+}
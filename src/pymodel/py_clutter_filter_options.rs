@@ -0,0 +1,76 @@
+use pyo3::prelude::*;
+
+use crate::filter::clutter::ClutterFilterParams;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyClutterFilterOptions {
+    // Number of gates either side of a gate used for texture and spin
+    #[pyo3(get, set)]
+    pub window_half_width: usize,
+    // Reflectivity texture in dBZ^2 above which a gate looks like clutter
+    #[pyo3(get, set)]
+    pub texture_threshold: f32,
+    // Smallest reflectivity difference in dBZ counted as a spin change
+    #[pyo3(get, set)]
+    pub spin_change: f32,
+    // Fraction of spin changes above which a gate looks like clutter
+    #[pyo3(get, set)]
+    pub spin_threshold: f32,
+    // Absolute velocity in m/s below which a gate looks like clutter
+    #[pyo3(get, set)]
+    pub velocity_threshold: f32,
+    // Correlation coefficient below which a gate looks like clutter
+    #[pyo3(get, set)]
+    pub correlation_threshold: f32,
+    // Fraction of the available tests which must flag a gate to mask it
+    #[pyo3(get, set)]
+    pub score_threshold: f32,
+}
+
+#[pymethods]
+impl PyClutterFilterOptions {
+    #[new]
+    #[pyo3(signature = (
+        window_half_width = ClutterFilterParams::default().window_half_width,
+        texture_threshold = ClutterFilterParams::default().texture_threshold,
+        spin_change = ClutterFilterParams::default().spin_change,
+        spin_threshold = ClutterFilterParams::default().spin_threshold,
+        velocity_threshold = ClutterFilterParams::default().velocity_threshold,
+        correlation_threshold = ClutterFilterParams::default().correlation_threshold,
+        score_threshold = ClutterFilterParams::default().score_threshold,
+    ))]
+    fn py_new(
+        window_half_width: usize,
+        texture_threshold: f32,
+        spin_change: f32,
+        spin_threshold: f32,
+        velocity_threshold: f32,
+        correlation_threshold: f32,
+        score_threshold: f32,
+    ) -> Self {
+        Self {
+            window_half_width,
+            texture_threshold,
+            spin_change,
+            spin_threshold,
+            velocity_threshold,
+            correlation_threshold,
+            score_threshold,
+        }
+    }
+}
+
+impl PyClutterFilterOptions {
+    pub(crate) fn params(&self) -> ClutterFilterParams {
+        ClutterFilterParams {
+            window_half_width: self.window_half_width,
+            texture_threshold: self.texture_threshold,
+            spin_change: self.spin_change,
+            spin_threshold: self.spin_threshold,
+            velocity_threshold: self.velocity_threshold,
+            correlation_threshold: self.correlation_threshold,
+            score_threshold: self.score_threshold,
+        }
+    }
+}
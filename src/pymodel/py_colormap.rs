@@ -0,0 +1,72 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::model::sweep_type::product_from_name;
+use crate::render::colormap::Colormap;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyColormap {
+    pub(crate) colormap: Colormap,
+}
+
+#[pymethods]
+impl PyColormap {
+    // Stops of (value, (red, green, blue, alpha)) in increasing order of
+    // value. Values below the first stop are transparent.
+    #[new]
+    #[pyo3(signature = (stops, interpolate = false))]
+    fn py_new(stops: Vec<(f32, (u8, u8, u8, u8))>, interpolate: bool) -> PyResult<Self> {
+        let stops = stops
+            .into_iter()
+            .map(|(value, (r, g, b, a))| (value, [r, g, b, a]))
+            .collect();
+
+        Ok(Self {
+            colormap: Colormap::new(stops, interpolate).map_err(PyValueError::new_err)?,
+        })
+    }
+
+    // Loads a colormap with one stop per line as a value followed by red,
+    // green, blue and optionally alpha
+    #[staticmethod]
+    #[pyo3(signature = (path, interpolate = false))]
+    fn load(path: &str, interpolate: bool) -> PyResult<Self> {
+        let text = std::fs::read_to_string(path)?;
+
+        Ok(Self {
+            colormap: Colormap::parse(&text, interpolate).map_err(PyValueError::new_err)?,
+        })
+    }
+
+    // The NWS style colormap of a product
+    #[staticmethod]
+    fn builtin(product: &str) -> PyResult<Self> {
+        let product = product_from_name(product)
+            .ok_or_else(|| PyValueError::new_err(format!("Unexpected product: {}", product)))?;
+
+        Ok(Self {
+            colormap: Colormap::builtin(product),
+        })
+    }
+
+    #[getter]
+    fn stops(&self) -> Vec<(f32, (u8, u8, u8, u8))> {
+        self.colormap
+            .stops
+            .iter()
+            .map(|(value, [r, g, b, a])| (*value, (*r, *g, *b, *a)))
+            .collect()
+    }
+
+    #[getter]
+    fn interpolate(&self) -> bool {
+        self.colormap.interpolate
+    }
+
+    // Color of a value as (red, green, blue, alpha)
+    fn color(&self, value: f32) -> (u8, u8, u8, u8) {
+        let [r, g, b, a] = self.colormap.color(value);
+        (r, g, b, a)
+    }
+}
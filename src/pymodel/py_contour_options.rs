@@ -0,0 +1,110 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::model::site::Site;
+use crate::pymodel::py_band_options::PyBandOptions;
+use crate::pymodel::py_render_options::check_size;
+use crate::render::image::Bounds;
+use crate::render::raster::LatLonGrid;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyContourOptions {
+    // Values to contour, such as dBZ or m/s
+    #[pyo3(get)]
+    pub thresholds: Vec<f32>,
+    // What to contour. Tilts are contoured on their gates, and composites
+    // and CAPPIs on a latitude/longitude grid.
+    #[pyo3(get, set)]
+    pub band: PyBandOptions,
+    // Enclose values at or below the thresholds, such as inbound
+    // velocities, rather than at or above them
+    #[pyo3(get, set)]
+    pub below: bool,
+    // Tolerance in km of the simplification of the polygons, or 0 to keep
+    // every corner
+    #[pyo3(get, set)]
+    pub simplify: f32,
+    // Distance in km around the radar and pixel size in km of the grid of
+    // composites and CAPPIs
+    #[pyo3(get)]
+    pub distance: f32,
+    #[pyo3(get)]
+    pub resolution: f32,
+}
+
+#[pymethods]
+impl PyContourOptions {
+    #[new]
+    #[pyo3(signature = (
+        thresholds,
+        band = None,
+        below = false,
+        simplify = 0.0,
+        distance = 230.0,
+        resolution = 1.0,
+    ))]
+    fn py_new(
+        thresholds: Vec<f32>,
+        band: Option<PyBandOptions>,
+        below: bool,
+        simplify: f32,
+        distance: f32,
+        resolution: f32,
+    ) -> PyResult<Self> {
+        if thresholds.is_empty() || thresholds.iter().any(|threshold| !threshold.is_finite()) {
+            return Err(PyValueError::new_err(
+                "thresholds must be one or more finite values",
+            ));
+        }
+
+        check_grid(distance, resolution)?;
+
+        Ok(Self {
+            thresholds,
+            band: band.unwrap_or_default(),
+            below,
+            simplify,
+            distance,
+            resolution,
+        })
+    }
+
+    #[setter]
+    fn set_distance(&mut self, distance: f32) -> PyResult<()> {
+        check_grid(distance, self.resolution)?;
+        self.distance = distance;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_resolution(&mut self, resolution: f32) -> PyResult<()> {
+        check_grid(self.distance, resolution)?;
+        self.resolution = resolution;
+        Ok(())
+    }
+}
+
+// Checks that the grid of composites and CAPPIs has pixels and is no
+// larger than an image
+fn check_grid(distance: f32, resolution: f32) -> PyResult<()> {
+    if !(distance > 0.0 && resolution > 0.0) {
+        return Err(PyValueError::new_err(
+            "distance and resolution must be positive",
+        ));
+    }
+
+    let size = grid_size(distance, resolution);
+    check_size("Contour grids", size, size)
+}
+
+fn grid_size(distance: f32, resolution: f32) -> usize {
+    (2.0 * distance / resolution).ceil() as usize
+}
+
+impl PyContourOptions {
+    pub(crate) fn grid(&self, site: &Site) -> LatLonGrid {
+        let size = grid_size(self.distance, self.resolution);
+        LatLonGrid::new(Bounds::around(site, self.distance), size, size)
+    }
+}
@@ -0,0 +1,129 @@
+use std::path::Path;
+
+use pyo3::prelude::*;
+
+use crate::model::volume::Volume;
+use crate::pymodel::py_contour_options::PyContourOptions;
+use crate::render::contour::{contour, to_geojson, Cells, Polygon};
+use crate::render::raster::Band;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyContours {
+    // Description of what was contoured, as in PyRaster band names, or the
+    // contoured field of a grid such as "mesh"
+    #[pyo3(get)]
+    pub product: String,
+    #[pyo3(get)]
+    pub thresholds: Vec<f32>,
+    #[pyo3(get)]
+    pub below: bool,
+    #[pyo3(get)]
+    pub start_time: i64,
+    #[pyo3(get)]
+    pub end_time: i64,
+    // GeoJSON feature collection with a MultiPolygon feature per threshold
+    #[pyo3(get)]
+    pub geojson: String,
+}
+
+impl PyContours {
+    // Contours the product selected by the options, if the volume has it
+    // and its site is known
+    pub(crate) fn new(volume: &Volume, options: &PyContourOptions) -> Option<Self> {
+        let site = volume.site.as_ref()?;
+        let product = options.band.name();
+
+        let (start_time, end_time, contours) = match options.band.band() {
+            Band::Tilt { product, elevation } => {
+                let sweep = volume.closest_sweep(product, elevation)?;
+                let data = sweep.product(product)?;
+                let values: Vec<f32> = (0..data.radials)
+                    .flat_map(|radial| {
+                        (0..data.gates)
+                            .map(move |gate| data.value(radial, gate).unwrap_or(f32::NAN))
+                    })
+                    .collect();
+
+                let cells = Cells::Sweep {
+                    sweep,
+                    site,
+                    gates: data.gates,
+                };
+                let contours = contour_thresholds(&cells, &values, options);
+
+                (sweep.start_time, sweep.end_time, contours)
+            }
+            band => {
+                let grid = options.grid(site);
+                let values = band.resample(volume, site, &grid);
+
+                let contours = contour_thresholds(&Cells::LatLon(&grid), &values, options);
+
+                let start_time = volume.sweeps.iter().map(|sweep| sweep.start_time).min()?;
+                let end_time = volume.sweeps.iter().map(|sweep| sweep.end_time).max()?;
+                (start_time, end_time, contours)
+            }
+        };
+
+        Some(Self {
+            geojson: to_geojson(&product, &contours),
+            product,
+            thresholds: options.thresholds.clone(),
+            below: options.below,
+            start_time: start_time.timestamp(),
+            end_time: end_time.timestamp(),
+        })
+    }
+
+    // Contours the values of the cells of a grid or raster with the
+    // thresholds of the options. Their band, distance and resolution are
+    // not used.
+    pub(crate) fn from_cells(
+        product: &str,
+        cells: &Cells,
+        values: &[f32],
+        options: &PyContourOptions,
+        start_time: i64,
+        end_time: i64,
+    ) -> Self {
+        let contours = contour_thresholds(cells, values, options);
+
+        Self {
+            geojson: to_geojson(product, &contours),
+            product: product.to_string(),
+            thresholds: options.thresholds.clone(),
+            below: options.below,
+            start_time,
+            end_time,
+        }
+    }
+}
+
+// Polygons of the cells passing each threshold of the options
+fn contour_thresholds(
+    cells: &Cells,
+    values: &[f32],
+    options: &PyContourOptions,
+) -> Vec<(f32, Vec<Polygon>)> {
+    options
+        .thresholds
+        .iter()
+        .map(|threshold| {
+            let polygons = contour(cells, values, *threshold, options.below, options.simplify);
+            (*threshold, polygons)
+        })
+        .collect()
+}
+
+#[pymethods]
+impl PyContours {
+    // Writes the GeoJSON to the path
+    fn save(&self, py: Python, path: String) -> PyResult<()> {
+        py.allow_threads(|| {
+            std::fs::write(Path::new(&path), &self.geojson)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,209 @@
+use pyo3::prelude::*;
+
+use crate::model::melting_layer::MeltingLayer;
+use crate::pymodel::py_azimuthal_shear_options::PyAzimuthalShearOptions;
+use crate::pymodel::py_biological_filter_options::PyBiologicalFilterOptions;
+use crate::pymodel::py_clutter_filter_options::PyClutterFilterOptions;
+use crate::pymodel::py_contour_options::PyContourOptions;
+use crate::pymodel::py_geotiff_options::PyGeoTiffOptions;
+use crate::pymodel::py_hail_options::PyHailOptions;
+use crate::pymodel::py_kdp_options::PyKdpOptions;
+use crate::pymodel::py_melting_layer_options::PyMeltingLayerOptions;
+use crate::pymodel::py_qpe_options::PyQpeOptions;
+use crate::pymodel::py_qvp_options::PyQvpOptions;
+use crate::pymodel::py_render_options::PyRenderOptions;
+use crate::pymodel::py_rotation_options::PyRotationOptions;
+use crate::pymodel::py_storm_cell_options::PyStormCellOptions;
+use crate::pymodel::py_storm_relative_options::PyStormRelativeOptions;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyConvertOptions {
+    // Moves incomplete sweeps onto the nominal azimuth grid with the missing
+    // radials masked. Otherwise incomplete sweeps only contain the radials
+    // which were received, at the azimuths listed on the sweep, and do not
+    // wrap around from the last radial to the first.
+    #[pyo3(get, set)]
+    pub regrid_partial_sweeps: bool,
+
+    // Resamples every sweep onto an exact uniform azimuth grid starting at
+    // north, for consumers which need the radials at fixed angles
+    #[pyo3(get, set)]
+    pub resample_uniform: bool,
+
+    // Masks ground clutter and anomalous propagation with the given
+    // thresholds, or None to skip the clutter filter
+    #[pyo3(get, set)]
+    pub clutter_filter: Option<PyClutterFilterOptions>,
+
+    // Masks birds, insects and chaff on every moment with the given
+    // thresholds, or None to skip the biological filter
+    #[pyo3(get, set)]
+    pub biological_filter: Option<PyBiologicalFilterOptions>,
+
+    // Estimates specific differential phase from the differential phase
+    // of the sweeps with dual pol data, which also improves classification,
+    // or None to skip KDP estimation
+    #[pyo3(get, set)]
+    pub estimate_kdp: Option<PyKdpOptions>,
+
+    // Detects the melting layer from quasi-vertical profiles of the higher
+    // tilts. The detected layer is used for classification when
+    // melting_layer is not given. None skips detection.
+    #[pyo3(get, set)]
+    pub detect_melting_layer: Option<PyMeltingLayerOptions>,
+
+    // Classifies the hydrometeors of the sweeps with dual pol data
+    #[pyo3(get, set)]
+    pub classify_hydrometeors: bool,
+
+    // Bottom and top of the melting layer in km above mean sea level, used
+    // to limit the hydrometeor classes by height
+    #[pyo3(get, set)]
+    pub melting_layer: Option<(f32, f32)>,
+
+    // Estimates the rain rate of the lowest tilt and grids it, or None to
+    // skip precipitation estimation
+    #[pyo3(get, set)]
+    pub qpe: Option<PyQpeOptions>,
+
+    // Quasi-vertical profiles to produce from the processed volume
+    #[pyo3(get, set)]
+    pub qvps: Vec<PyQvpOptions>,
+
+    // Identifies storm cells from the reflectivity of every tilt, or None
+    // to skip storm cell identification
+    #[pyo3(get, set)]
+    pub storm_cells: Option<PyStormCellOptions>,
+
+    // Detects mesocyclones and tornado vortex signatures in the dealiased
+    // velocity, or None to skip rotation detection
+    #[pyo3(get, set)]
+    pub rotation: Option<PyRotationOptions>,
+
+    // Estimates azimuthal shear and divergence from the dealiased velocity
+    // and grids their layer maxima, or None to skip it
+    #[pyo3(get, set)]
+    pub azimuthal_shear: Option<PyAzimuthalShearOptions>,
+
+    // Removes the storm motion from the dealiased velocity, using the mean
+    // wind when no motion is given, or None to skip storm-relative velocity
+    #[pyo3(get, set)]
+    pub storm_relative: Option<PyStormRelativeOptions>,
+
+    // Grids the hail detection products and adds them to the storm cells,
+    // or None to skip hail detection
+    #[pyo3(get, set)]
+    pub hail: Option<PyHailOptions>,
+
+    // Images to render from the processed volume
+    #[pyo3(get, set)]
+    pub images: Vec<PyRenderOptions>,
+
+    // GeoTIFF rasters to resample from the processed volume
+    #[pyo3(get, set)]
+    pub rasters: Vec<PyGeoTiffOptions>,
+
+    // GeoJSON contours to trace from the processed volume
+    #[pyo3(get, set)]
+    pub contours: Vec<PyContourOptions>,
+}
+
+impl PyConvertOptions {
+    pub(crate) fn melting_layer(&self) -> Option<MeltingLayer> {
+        self.melting_layer
+            .map(|(bottom, top)| MeltingLayer { bottom, top })
+    }
+}
+
+#[pymethods]
+impl PyConvertOptions {
+    #[new]
+    #[pyo3(signature = (
+        regrid_partial_sweeps = true,
+        resample_uniform = false,
+        clutter_filter = None,
+        biological_filter = None,
+        estimate_kdp = None,
+        detect_melting_layer = None,
+        classify_hydrometeors = false,
+        melting_layer = None,
+        qpe = None,
+        qvps = Vec::new(),
+        storm_cells = None,
+        rotation = None,
+        azimuthal_shear = None,
+        storm_relative = None,
+        hail = None,
+        images = Vec::new(),
+        rasters = Vec::new(),
+        contours = Vec::new(),
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        regrid_partial_sweeps: bool,
+        resample_uniform: bool,
+        clutter_filter: Option<PyClutterFilterOptions>,
+        biological_filter: Option<PyBiologicalFilterOptions>,
+        estimate_kdp: Option<PyKdpOptions>,
+        detect_melting_layer: Option<PyMeltingLayerOptions>,
+        classify_hydrometeors: bool,
+        melting_layer: Option<(f32, f32)>,
+        qpe: Option<PyQpeOptions>,
+        qvps: Vec<PyQvpOptions>,
+        storm_cells: Option<PyStormCellOptions>,
+        rotation: Option<PyRotationOptions>,
+        azimuthal_shear: Option<PyAzimuthalShearOptions>,
+        storm_relative: Option<PyStormRelativeOptions>,
+        hail: Option<PyHailOptions>,
+        images: Vec<PyRenderOptions>,
+        rasters: Vec<PyGeoTiffOptions>,
+        contours: Vec<PyContourOptions>,
+    ) -> Self {
+        Self {
+            regrid_partial_sweeps,
+            resample_uniform,
+            clutter_filter,
+            biological_filter,
+            estimate_kdp,
+            detect_melting_layer,
+            classify_hydrometeors,
+            melting_layer,
+            qpe,
+            qvps,
+            storm_cells,
+            rotation,
+            azimuthal_shear,
+            storm_relative,
+            hail,
+            images,
+            rasters,
+            contours,
+        }
+    }
+}
+
+impl Default for PyConvertOptions {
+    fn default() -> Self {
+        Self {
+            regrid_partial_sweeps: true,
+            resample_uniform: false,
+            clutter_filter: None,
+            biological_filter: None,
+            estimate_kdp: None,
+            detect_melting_layer: None,
+            classify_hydrometeors: false,
+            melting_layer: None,
+            qpe: None,
+            qvps: Vec::new(),
+            storm_cells: None,
+            rotation: None,
+            azimuthal_shear: None,
+            storm_relative: None,
+            hail: None,
+            images: Vec::new(),
+            rasters: Vec::new(),
+            contours: Vec::new(),
+        }
+    }
+}
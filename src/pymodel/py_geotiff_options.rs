@@ -0,0 +1,112 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::model::site::Site;
+use crate::pymodel::py_band_options::PyBandOptions;
+use crate::pymodel::py_render_options::{check_bounds, check_size};
+use crate::render::image::Bounds;
+use crate::render::raster::LatLonGrid;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyGeoTiffOptions {
+    // Bands of the raster, in order
+    #[pyo3(get)]
+    pub bands: Vec<PyBandOptions>,
+    // Size of the raster in pixels
+    #[pyo3(get)]
+    pub width: usize,
+    #[pyo3(get)]
+    pub height: usize,
+    // Degrees of (west, south, east, north) covered by the raster, or None
+    // for the square around the radar out to distance km
+    #[pyo3(get)]
+    pub bounds: Option<(f32, f32, f32, f32)>,
+    #[pyo3(get, set)]
+    pub distance: f32,
+    // Value written for pixels without data
+    #[pyo3(get, set)]
+    pub nodata: f32,
+}
+
+#[pymethods]
+impl PyGeoTiffOptions {
+    #[new]
+    #[pyo3(signature = (
+        bands,
+        width = 1024,
+        height = 1024,
+        bounds = None,
+        distance = 230.0,
+        nodata = -9999.0,
+    ))]
+    fn py_new(
+        bands: Vec<PyBandOptions>,
+        width: usize,
+        height: usize,
+        bounds: Option<(f32, f32, f32, f32)>,
+        distance: f32,
+        nodata: f32,
+    ) -> PyResult<Self> {
+        check_bands(&bands)?;
+        check_size("Rasters", width, height)?;
+
+        check_bounds(bounds)?;
+
+        Ok(Self {
+            bands,
+            width,
+            height,
+            bounds,
+            distance,
+            nodata,
+        })
+    }
+
+    #[setter]
+    fn set_bands(&mut self, bands: Vec<PyBandOptions>) -> PyResult<()> {
+        check_bands(&bands)?;
+        self.bands = bands;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_width(&mut self, width: usize) -> PyResult<()> {
+        check_size("Rasters", width, self.height)?;
+        self.width = width;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_height(&mut self, height: usize) -> PyResult<()> {
+        check_size("Rasters", self.width, height)?;
+        self.height = height;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_bounds(&mut self, bounds: Option<(f32, f32, f32, f32)>) -> PyResult<()> {
+        check_bounds(bounds)?;
+        self.bounds = bounds;
+        Ok(())
+    }
+}
+
+fn check_bands(bands: &[PyBandOptions]) -> PyResult<()> {
+    if bands.is_empty() {
+        return Err(PyValueError::new_err("A GeoTIFF needs at least one band"));
+    }
+
+    Ok(())
+}
+
+impl PyGeoTiffOptions {
+    pub(crate) fn grid(&self, site: &Site) -> LatLonGrid {
+        let bounds = match self.bounds {
+            Some(edges) => Bounds::from_edges(edges).expect("Bounds are validated when set"),
+            None => Bounds::around(site, self.distance),
+        };
+
+        LatLonGrid::new(bounds, self.width, self.height)
+    }
+}
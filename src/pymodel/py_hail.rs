@@ -0,0 +1,160 @@
+use std::path::Path;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::model::grid::Grid;
+use crate::model::hail::HailEstimate;
+use crate::model::site::Site;
+use crate::model::volume::Volume;
+use crate::products::hail::hail_grid;
+use crate::pymodel::py_contour_options::PyContourOptions;
+use crate::pymodel::py_contours::PyContours;
+use crate::pymodel::py_hail_options::PyHailOptions;
+use crate::pymodel::py_raster::grid_geotiff;
+use crate::render::contour::Cells;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyHail {
+    // Severe hail index in J/m/s
+    #[pyo3(get)]
+    pub shi: f32,
+    // Probability of hail and of severe hail, in percent
+    #[pyo3(get)]
+    pub poh: f32,
+    #[pyo3(get)]
+    pub posh: f32,
+    // Maximum expected hail size in mm
+    #[pyo3(get)]
+    pub mesh: f32,
+}
+
+impl PyHail {
+    pub(crate) fn new(estimate: &HailEstimate) -> Self {
+        Self {
+            shi: estimate.shi,
+            poh: estimate.poh,
+            posh: estimate.posh,
+            mesh: estimate.mesh,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyHailGrid {
+    // Number of cells along each side of the grid, centered on the radar
+    #[pyo3(get)]
+    pub size: usize,
+    // Width of each cell in km
+    #[pyo3(get)]
+    pub spacing: f32,
+    // Values of each cell, row-major from the north west corner, with NaN
+    // where there is no data
+    #[pyo3(get)]
+    pub shi: Vec<f32>,
+    #[pyo3(get)]
+    pub poh: Vec<f32>,
+    #[pyo3(get)]
+    pub posh: Vec<f32>,
+    #[pyo3(get)]
+    pub mesh: Vec<f32>,
+
+    #[pyo3(get)]
+    pub start_time: i64,
+    #[pyo3(get)]
+    pub end_time: i64,
+
+    // Site of the radar the grid is centered on, if known
+    pub(crate) site: Option<Site>,
+}
+
+impl PyHailGrid {
+    // Grids the hail products of the volume, if it has reflectivity
+    pub(crate) fn new(volume: &Volume, options: &PyHailOptions) -> Option<Self> {
+        let hail = hail_grid(volume, &options.grid(), &options.params())?;
+
+        Some(Self {
+            size: hail.grid.size,
+            spacing: hail.grid.spacing,
+            shi: hail.shi,
+            poh: hail.poh,
+            posh: hail.posh,
+            mesh: hail.mesh,
+            start_time: hail.start_time.timestamp(),
+            end_time: hail.end_time.timestamp(),
+            site: volume.site,
+        })
+    }
+
+    fn encode(&self, nodata: f32) -> PyResult<Vec<u8>> {
+        grid_geotiff(
+            &[
+                ("shi", &self.shi),
+                ("poh", &self.poh),
+                ("posh", &self.posh),
+                ("mesh", &self.mesh),
+            ],
+            &Grid::new(self.size, self.spacing),
+            self.site.as_ref(),
+            nodata,
+        )
+    }
+}
+
+#[pymethods]
+impl PyHailGrid {
+    // Contours "shi", "poh", "posh" or "mesh" with the thresholds of the
+    // options
+    #[pyo3(signature = (options, product = "mesh"))]
+    fn contours(&self, options: &PyContourOptions, product: &str) -> PyResult<PyContours> {
+        let values = match product {
+            "shi" => &self.shi,
+            "poh" => &self.poh,
+            "posh" => &self.posh,
+            "mesh" => &self.mesh,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unexpected hail product: {}",
+                    product
+                )))
+            }
+        };
+
+        let site = self
+            .site
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("The radar site is unknown"))?;
+
+        Ok(PyContours::from_cells(
+            product,
+            &Cells::Grid {
+                grid: &Grid::new(self.size, self.spacing),
+                site,
+            },
+            values,
+            options,
+            self.start_time,
+            self.end_time,
+        ))
+    }
+
+    // The hail products encoded as the bands of a GeoTIFF, as in
+    // PyRainRateGrid.geotiff
+    #[pyo3(signature = (nodata = -9999.0))]
+    fn geotiff<'py>(&self, py: Python<'py>, nodata: f32) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &self.encode(nodata)?))
+    }
+
+    // Writes the hail products as a GeoTIFF to the path
+    #[pyo3(signature = (path, nodata = -9999.0))]
+    fn save(&self, py: Python, path: String, nodata: f32) -> PyResult<()> {
+        py.allow_threads(|| {
+            std::fs::write(Path::new(&path), self.encode(nodata)?)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,124 @@
+use std::cmp::Ordering;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::model::grid::Grid;
+use crate::products::hail::HailParams;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyHailOptions {
+    // Heights of the 0°C and -20°C levels in km above mean sea level, such
+    // as from a nearby sounding or model analysis
+    #[pyo3(get)]
+    pub freezing_level: f32,
+    #[pyo3(get)]
+    pub minus_20_level: f32,
+    // Number of cells along each side of the hail grid
+    #[pyo3(get)]
+    pub grid_size: usize,
+    // Width of each grid cell in km
+    #[pyo3(get)]
+    pub grid_spacing: f32,
+}
+
+#[pymethods]
+impl PyHailOptions {
+    #[new]
+    #[pyo3(signature = (
+        freezing_level,
+        minus_20_level,
+        grid_size = Grid::default().size,
+        grid_spacing = Grid::default().spacing,
+    ))]
+    fn py_new(
+        freezing_level: f32,
+        minus_20_level: f32,
+        grid_size: usize,
+        grid_spacing: f32,
+    ) -> PyResult<Self> {
+        check_levels(freezing_level, minus_20_level)?;
+        check_grid(grid_size, grid_spacing)?;
+
+        Ok(Self {
+            freezing_level,
+            minus_20_level,
+            grid_size,
+            grid_spacing,
+        })
+    }
+
+    #[setter]
+    fn set_freezing_level(&mut self, freezing_level: f32) -> PyResult<()> {
+        check_levels(freezing_level, self.minus_20_level)?;
+        self.freezing_level = freezing_level;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_minus_20_level(&mut self, minus_20_level: f32) -> PyResult<()> {
+        check_levels(self.freezing_level, minus_20_level)?;
+        self.minus_20_level = minus_20_level;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_grid_size(&mut self, grid_size: usize) -> PyResult<()> {
+        check_grid(grid_size, self.grid_spacing)?;
+        self.grid_size = grid_size;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_grid_spacing(&mut self, grid_spacing: f32) -> PyResult<()> {
+        check_grid(self.grid_size, grid_spacing)?;
+        self.grid_spacing = grid_spacing;
+        Ok(())
+    }
+}
+
+// The -20°C level is always above the freezing level, and the hail
+// temperature weights are undefined otherwise
+fn check_levels(freezing_level: f32, minus_20_level: f32) -> PyResult<()> {
+    if minus_20_level.partial_cmp(&freezing_level) != Some(Ordering::Greater) {
+        return Err(PyValueError::new_err(
+            "minus_20_level must be above freezing_level",
+        ));
+    }
+
+    Ok(())
+}
+
+// Most cells along each side of a grid, which keeps each grid of values
+// to 64 MB
+pub(crate) const MAX_GRID_SIZE: usize = 4000;
+
+// Checks that a grid has cells of a positive width and can be allocated
+pub(crate) fn check_grid(grid_size: usize, grid_spacing: f32) -> PyResult<()> {
+    if grid_size == 0 || grid_size > MAX_GRID_SIZE {
+        return Err(PyValueError::new_err(format!(
+            "grid_size must be between 1 and {}",
+            MAX_GRID_SIZE
+        )));
+    }
+
+    if !(grid_spacing > 0.0 && grid_spacing.is_finite()) {
+        return Err(PyValueError::new_err("grid_spacing must be positive"));
+    }
+
+    Ok(())
+}
+
+impl PyHailOptions {
+    pub(crate) fn params(&self) -> HailParams {
+        HailParams {
+            freezing_level: self.freezing_level,
+            minus_20_level: self.minus_20_level,
+        }
+    }
+
+    pub(crate) fn grid(&self) -> Grid {
+        Grid::new(self.grid_size, self.grid_spacing)
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::model::site::Site;
+use crate::model::sweep::Sweep;
+use crate::model::sweep_type::{product_name, SweepType};
+use crate::model::volume::Volume;
+use crate::pymodel::py_render_options::PyRenderOptions;
+use crate::render::image::{encode_png, render_sweep, Bounds};
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyImage {
+    #[pyo3(get)]
+    pub product: String,
+    // Elevation of the rendered sweep in radians
+    #[pyo3(get)]
+    pub elevation: f32,
+    #[pyo3(get)]
+    pub width: usize,
+    #[pyo3(get)]
+    pub height: usize,
+    // Degrees of (west, south, east, north) covered by the image
+    #[pyo3(get)]
+    pub bounds: (f32, f32, f32, f32),
+    #[pyo3(get)]
+    pub start_time: i64,
+    #[pyo3(get)]
+    pub end_time: i64,
+    pixels: Vec<u8>,
+}
+
+impl PyImage {
+    // Renders the sweep of the volume selected by the options, if the
+    // volume has the product and its site is known
+    pub(crate) fn new(volume: &Volume, options: &PyRenderOptions) -> Option<Self> {
+        let site = volume.site.as_ref()?;
+        let product = options.product();
+        let sweep = volume.closest_sweep(product, options.elevation.to_radians())?;
+
+        Self::from_sweep(sweep, product, site, options)
+    }
+
+    // Renders the product of the given sweep with the size, bounds and
+    // colormap of the options, if the sweep has the product
+    pub(crate) fn from_sweep(
+        sweep: &Sweep,
+        product: SweepType,
+        site: &Site,
+        options: &PyRenderOptions,
+    ) -> Option<Self> {
+        let grid = options.grid(site);
+        let pixels = render_sweep(
+            sweep,
+            sweep.product(product)?,
+            site,
+            &grid,
+            &options.colormap(product),
+        );
+        let bounds = grid.bounds;
+
+        Some(Self {
+            product: product_name(product).to_string(),
+            elevation: sweep.elevation,
+            width: options.width,
+            height: options.height,
+            bounds: (bounds.west, bounds.south, bounds.east, bounds.north),
+            start_time: sweep.start_time.timestamp(),
+            end_time: sweep.end_time.timestamp(),
+            pixels,
+        })
+    }
+
+    fn bounds(&self) -> Bounds {
+        let (west, south, east, north) = self.bounds;
+        Bounds {
+            west,
+            south,
+            east,
+            north,
+        }
+    }
+}
+
+#[pymethods]
+impl PyImage {
+    // RGBA pixels, row-major from the top left corner
+    fn rgba<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.pixels)
+    }
+
+    // The image encoded as a PNG
+    fn png<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        let data =
+            encode_png(&self.pixels, self.width, self.height).map_err(PyValueError::new_err)?;
+
+        Ok(PyBytes::new(py, &data))
+    }
+
+    // Contents of a world file georeferencing the image
+    #[getter]
+    fn world_file(&self) -> String {
+        self.bounds().world_file(self.width, self.height)
+    }
+
+    // Writes the image as a PNG to the path, with its world file next to it
+    // using the .pgw extension
+    fn save(&self, py: Python, path: String) -> PyResult<()> {
+        py.allow_threads(|| {
+            let data =
+                encode_png(&self.pixels, self.width, self.height).map_err(PyValueError::new_err)?;
+            std::fs::write(&path, data)?;
+            std::fs::write(Path::new(&path).with_extension("pgw"), self.world_file())?;
+
+            Ok(())
+        })
+    }
+}
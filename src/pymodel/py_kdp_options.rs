@@ -0,0 +1,57 @@
+use pyo3::prelude::*;
+
+use crate::products::kdp::KdpParams;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyKdpOptions {
+    // Correlation coefficient below which the differential phase is too
+    // noisy to use and is interpolated over
+    #[pyo3(get, set)]
+    pub correlation_threshold: f32,
+    // Reflectivity in dBZ at or above which the short window is used
+    #[pyo3(get, set)]
+    pub reflectivity_threshold: f32,
+    // Lengths in km of the windows KDP is fitted over in heavy
+    // precipitation and elsewhere
+    #[pyo3(get, set)]
+    pub short_window: f32,
+    #[pyo3(get, set)]
+    pub long_window: f32,
+}
+
+#[pymethods]
+impl PyKdpOptions {
+    #[new]
+    #[pyo3(signature = (
+        correlation_threshold = KdpParams::default().correlation_threshold,
+        reflectivity_threshold = KdpParams::default().reflectivity_threshold,
+        short_window = KdpParams::default().short_window,
+        long_window = KdpParams::default().long_window,
+    ))]
+    fn py_new(
+        correlation_threshold: f32,
+        reflectivity_threshold: f32,
+        short_window: f32,
+        long_window: f32,
+    ) -> Self {
+        Self {
+            correlation_threshold,
+            reflectivity_threshold,
+            short_window,
+            long_window,
+        }
+    }
+}
+
+impl PyKdpOptions {
+    pub(crate) fn params(&self) -> KdpParams {
+        KdpParams {
+            correlation_threshold: self.correlation_threshold,
+            reflectivity_threshold: self.reflectivity_threshold,
+            short_window: self.short_window,
+            long_window: self.long_window,
+            ..Default::default()
+        }
+    }
+}
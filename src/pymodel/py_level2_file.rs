@@ -1,21 +1,103 @@
+use std::sync::Arc;
+
 use crate::model::sweep_type::*;
 use crate::model::volume::Volume;
+use crate::pymodel::py_contours::PyContours;
+use crate::pymodel::py_hail::PyHailGrid;
+use crate::pymodel::py_image::PyImage;
+use crate::pymodel::py_melting_layer::PyMeltingLayer;
+use crate::pymodel::py_qvp::PyQvp;
+use crate::pymodel::py_rain_rate_grid::PyRainRateGrid;
+use crate::pymodel::py_raster::PyRaster;
+use crate::pymodel::py_rotation::PyRotation;
+use crate::pymodel::py_shear_layers::PyShearLayers;
+use crate::pymodel::py_storm_cell::PyStormCells;
 use crate::pymodel::py_sweep::PySweep;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
 #[pyclass]
 #[derive(Clone)]
 pub struct PyLevel2File {
+    // Volume coverage pattern number, e.g. 212
+    #[pyo3(get)]
+    pub vcp: u16,
     #[pyo3(get)]
     pub reflectivity: Vec<PySweep>,
     #[pyo3(get)]
     pub velocity: Vec<PySweep>,
+    #[pyo3(get)]
+    pub correlation_coefficient: Vec<PySweep>,
+    #[pyo3(get)]
+    pub differential_reflectivity: Vec<PySweep>,
+    #[pyo3(get)]
+    pub differential_phase: Vec<PySweep>,
+    // Estimated KDP and the filtered differential phase, when enabled
+    #[pyo3(get)]
+    pub specific_differential_phase: Vec<PySweep>,
+    #[pyo3(get)]
+    pub filtered_differential_phase: Vec<PySweep>,
+    // Clutter scores of the reflectivity sweeps, when the clutter filter is enabled
+    #[pyo3(get)]
+    pub clutter: Vec<PySweep>,
+    // Hydrometeor class codes, when classification is enabled
+    #[pyo3(get)]
+    pub hydrometeor_class: Vec<PySweep>,
+    // Rain rate of the lowest tilt and its grid, when QPE is enabled
+    #[pyo3(get)]
+    pub rain_rate: Option<PySweep>,
+    // Azimuthal shear and divergence of the velocity sweeps and their
+    // gridded layer maxima, when azimuthal shear estimation is enabled
+    #[pyo3(get)]
+    pub azimuthal_shear: Vec<PySweep>,
+    #[pyo3(get)]
+    pub divergence: Vec<PySweep>,
+    #[pyo3(get)]
+    pub shear_layers: Option<PyShearLayers>,
+    // Storm-relative velocity of the velocity sweeps and the storm motion
+    // (u, v) in m/s it removes, when storm-relative velocity is enabled
+    #[pyo3(get)]
+    pub storm_relative_velocity: Vec<PySweep>,
+    #[pyo3(get)]
+    pub storm_motion: Option<(f32, f32)>,
+    #[pyo3(get)]
+    pub rain_rate_grid: Option<PyRainRateGrid>,
+    // Detected melting layer, when detection is enabled and found a layer
+    #[pyo3(get)]
+    pub melting_layer: Option<PyMeltingLayer>,
+    // Quasi-vertical profiles requested in the options, skipping products
+    // which the volume does not have
+    #[pyo3(get)]
+    pub qvps: Vec<PyQvp>,
+    // Storm cells of the volume, when identification is enabled
+    #[pyo3(get)]
+    pub storm_cells: Option<PyStormCells>,
+    // Mesocyclones and tornado vortex signatures, when detection is enabled
+    #[pyo3(get)]
+    pub rotations: Vec<PyRotation>,
+    // Gridded hail products, when hail detection is enabled
+    #[pyo3(get)]
+    pub hail_grid: Option<PyHailGrid>,
+    // Images requested in the options, skipping products which the volume
+    // does not have
+    #[pyo3(get)]
+    pub images: Vec<PyImage>,
+    // GeoTIFF rasters requested in the options
+    #[pyo3(get)]
+    pub rasters: Vec<PyRaster>,
+    // GeoJSON contours requested in the options, skipping products which
+    // the volume does not have
+    #[pyo3(get)]
+    pub contours: Vec<PyContours>,
+
+    // The converted volume, which sweeps are looked up in
+    pub(crate) volume: Arc<Volume>,
 }
 
-fn collect_sweeps(volume: &Volume, data_type: SweepType) -> Vec<PySweep> {
+fn collect_sweeps(volume: &Arc<Volume>, data_type: SweepType) -> Vec<PySweep> {
     let mut sweeps: Vec<PySweep> = Vec::new();
 
-    for sweep in volume.sweeps.iter() {
+    for (index, sweep) in volume.sweeps.iter().enumerate() {
         if !sweep.has_product(data_type) {
             continue;
         }
@@ -24,7 +106,7 @@ fn collect_sweeps(volume: &Volume, data_type: SweepType) -> Vec<PySweep> {
             continue;
         }
 
-        sweeps.push(PySweep::new(sweep, data_type))
+        sweeps.push(PySweep::new(volume, index, data_type))
     }
 
     sweeps.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap());
@@ -34,9 +116,60 @@ fn collect_sweeps(volume: &Volume, data_type: SweepType) -> Vec<PySweep> {
 
 impl PyLevel2File {
     pub(crate) fn new(volume: Volume) -> Self {
+        let volume = Arc::new(volume);
+
         Self {
+            vcp: volume.vcp,
             reflectivity: collect_sweeps(&volume, REFLECTIVITY),
             velocity: collect_sweeps(&volume, VELOCITY),
+            correlation_coefficient: collect_sweeps(&volume, CORRELATION_COEFFICIENT),
+            differential_reflectivity: collect_sweeps(&volume, DIFFERENTIAL_REFLECTIVITY),
+            differential_phase: collect_sweeps(&volume, DIFFERENTIAL_PHASE),
+            specific_differential_phase: collect_sweeps(&volume, SPECIFIC_DIFFERENTIAL_PHASE),
+            filtered_differential_phase: collect_sweeps(&volume, FILTERED_DIFFERENTIAL_PHASE),
+            clutter: collect_sweeps(&volume, CLUTTER),
+            hydrometeor_class: collect_sweeps(&volume, HYDROMETEOR_CLASS),
+            rain_rate: collect_sweeps(&volume, RAIN_RATE).pop(),
+            rain_rate_grid: None,
+            azimuthal_shear: collect_sweeps(&volume, AZIMUTHAL_SHEAR),
+            divergence: collect_sweeps(&volume, DIVERGENCE),
+            shear_layers: None,
+            storm_relative_velocity: collect_sweeps(&volume, STORM_RELATIVE_VELOCITY),
+            storm_motion: volume.storm_motion,
+            melting_layer: volume.melting_layer.as_ref().map(PyMeltingLayer::new),
+            qvps: Vec::new(),
+            storm_cells: volume.storm_cells.as_ref().map(|storm_cells| PyStormCells {
+                storm_cells: storm_cells.clone(),
+            }),
+            rotations: volume.rotations.iter().map(PyRotation::new).collect(),
+            hail_grid: None,
+            images: Vec::new(),
+            rasters: Vec::new(),
+            contours: Vec::new(),
+            volume,
         }
     }
 }
+
+#[pymethods]
+impl PyLevel2File {
+    // Returns the most recent sweep of the lowest elevation for the product
+    // ("reflectivity" or "velocity"), which is the last SAILS scan if present
+    fn latest_low_level(&self, product: &str) -> PyResult<Option<PySweep>> {
+        let product = match product {
+            "reflectivity" => REFLECTIVITY,
+            "velocity" => VELOCITY,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unexpected product: {}",
+                    product
+                )))
+            }
+        };
+
+        Ok(self
+            .volume
+            .latest_low_level_index(product)
+            .map(|index| PySweep::new(&self.volume, index, product)))
+    }
+}
@@ -0,0 +1,26 @@
+use pyo3::prelude::*;
+
+use crate::model::melting_layer::MeltingLayerEstimate;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyMeltingLayer {
+    // Heights of the bottom and top of the layer above mean sea level, in km
+    #[pyo3(get)]
+    pub bottom: f32,
+    #[pyo3(get)]
+    pub top: f32,
+    // How clear the signature of the layer is, from 0 to 1
+    #[pyo3(get)]
+    pub confidence: f32,
+}
+
+impl PyMeltingLayer {
+    pub(crate) fn new(estimate: &MeltingLayerEstimate) -> Self {
+        Self {
+            bottom: estimate.layer.bottom,
+            top: estimate.layer.top,
+            confidence: estimate.confidence,
+        }
+    }
+}
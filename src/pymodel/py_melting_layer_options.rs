@@ -0,0 +1,77 @@
+use pyo3::prelude::*;
+
+use crate::products::melting_layer::MeltingLayerParams;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyMeltingLayerOptions {
+    // Range of elevations in degrees whose profiles are used
+    #[pyo3(get, set)]
+    pub min_elevation: f32,
+    #[pyo3(get, set)]
+    pub max_elevation: f32,
+    // Correlation coefficient below which a bin is inside the layer
+    #[pyo3(get, set)]
+    pub correlation_threshold: f32,
+    // Correlation coefficient below which echoes are non-meteorological
+    #[pyo3(get, set)]
+    pub min_correlation: f32,
+    // Smallest differential reflectivity in dB and reflectivity in dBZ at
+    // the correlation dip
+    #[pyo3(get, set)]
+    pub min_zdr: f32,
+    #[pyo3(get, set)]
+    pub min_reflectivity: f32,
+    // Number of consecutive profile bins without data the layer may
+    // extend across
+    #[pyo3(get, set)]
+    pub max_missing_bins: usize,
+}
+
+#[pymethods]
+impl PyMeltingLayerOptions {
+    #[new]
+    #[pyo3(signature = (
+        min_elevation = MeltingLayerParams::default().min_elevation.to_degrees(),
+        max_elevation = MeltingLayerParams::default().max_elevation.to_degrees(),
+        correlation_threshold = MeltingLayerParams::default().correlation_threshold,
+        min_correlation = MeltingLayerParams::default().min_correlation,
+        min_zdr = MeltingLayerParams::default().min_zdr,
+        min_reflectivity = MeltingLayerParams::default().min_reflectivity,
+        max_missing_bins = MeltingLayerParams::default().max_missing_bins,
+    ))]
+    fn py_new(
+        min_elevation: f32,
+        max_elevation: f32,
+        correlation_threshold: f32,
+        min_correlation: f32,
+        min_zdr: f32,
+        min_reflectivity: f32,
+        max_missing_bins: usize,
+    ) -> Self {
+        Self {
+            min_elevation,
+            max_elevation,
+            correlation_threshold,
+            min_correlation,
+            min_zdr,
+            min_reflectivity,
+            max_missing_bins,
+        }
+    }
+}
+
+impl PyMeltingLayerOptions {
+    pub(crate) fn params(&self) -> MeltingLayerParams {
+        MeltingLayerParams {
+            min_elevation: self.min_elevation.to_radians(),
+            max_elevation: self.max_elevation.to_radians(),
+            correlation_threshold: self.correlation_threshold,
+            min_correlation: self.min_correlation,
+            min_zdr: self.min_zdr,
+            min_reflectivity: self.min_reflectivity,
+            max_missing_bins: self.max_missing_bins,
+            ..Default::default()
+        }
+    }
+}
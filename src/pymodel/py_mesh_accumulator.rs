@@ -0,0 +1,116 @@
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::model::grid::Grid;
+use crate::model::site::Site;
+use crate::products::hail::MeshAccumulator;
+use crate::pymodel::py_hail::PyHailGrid;
+use crate::pymodel::py_hail_options::check_grid;
+use crate::pymodel::py_raster::grid_geotiff;
+
+#[pyclass]
+pub struct PyMeshAccumulator {
+    accumulator: MeshAccumulator,
+    // Site of the radar of the first grid added, if known
+    site: Option<Site>,
+}
+
+impl PyMeshAccumulator {
+    fn encode(&self, nodata: f32) -> PyResult<Vec<u8>> {
+        grid_geotiff(
+            &[("mesh", &self.accumulator.maximum)],
+            &self.accumulator.grid,
+            self.site.as_ref(),
+            nodata,
+        )
+    }
+}
+
+#[pymethods]
+impl PyMeshAccumulator {
+    #[new]
+    #[pyo3(signature = (
+        grid_size = Grid::default().size,
+        grid_spacing = Grid::default().spacing,
+    ))]
+    fn py_new(grid_size: usize, grid_spacing: f32) -> PyResult<Self> {
+        check_grid(grid_size, grid_spacing)?;
+
+        Ok(Self {
+            accumulator: MeshAccumulator::new(Grid::new(grid_size, grid_spacing)),
+            site: None,
+        })
+    }
+
+    // Adds the MESH of a hail grid, which must not start before the
+    // previous grid ended and must use the same grid
+    fn add(&mut self, hail: &PyHailGrid) -> PyResult<()> {
+        let grid = Grid::new(hail.size, hail.spacing);
+        if grid != self.accumulator.grid {
+            return Err(PyValueError::new_err(
+                "Hail grid does not match the accumulator grid",
+            ));
+        }
+
+        let start_time = DateTime::<Utc>::from_timestamp(hail.start_time, 0)
+            .ok_or_else(|| PyValueError::new_err("Invalid start time"))?;
+        let end_time = DateTime::<Utc>::from_timestamp(hail.end_time, 0)
+            .ok_or_else(|| PyValueError::new_err("Invalid end time"))?;
+
+        self.accumulator
+            .add(&hail.mesh, start_time, end_time)
+            .map_err(PyValueError::new_err)?;
+
+        // Grids of the same size are assumed to be around the same radar
+        self.site = self.site.or(hail.site);
+
+        Ok(())
+    }
+
+    // Largest MESH in mm of each cell, row-major from the north west
+    // corner, with NaN where no grid had data
+    #[getter]
+    fn maximum(&self) -> Vec<f32> {
+        self.accumulator.maximum.clone()
+    }
+
+    #[getter]
+    fn start_time(&self) -> Option<i64> {
+        self.accumulator.start_time.map(|time| time.timestamp())
+    }
+
+    #[getter]
+    fn end_time(&self) -> Option<i64> {
+        self.accumulator.end_time.map(|time| time.timestamp())
+    }
+
+    #[getter]
+    fn grid_size(&self) -> usize {
+        self.accumulator.grid.size
+    }
+
+    #[getter]
+    fn grid_spacing(&self) -> f32 {
+        self.accumulator.grid.spacing
+    }
+
+    // The MESH swath encoded as a GeoTIFF, as in PyRainRateGrid.geotiff
+    #[pyo3(signature = (nodata = -9999.0))]
+    fn geotiff<'py>(&self, py: Python<'py>, nodata: f32) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &self.encode(nodata)?))
+    }
+
+    // Writes the MESH swath as a GeoTIFF to the path
+    #[pyo3(signature = (path, nodata = -9999.0))]
+    fn save(&self, py: Python, path: String, nodata: f32) -> PyResult<()> {
+        py.allow_threads(|| {
+            std::fs::write(Path::new(&path), self.encode(nodata)?)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,75 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::model::grid::Grid;
+use crate::products::qpe::{QpeParams, ZrRelationship};
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyQpeOptions {
+    // Z-R relationship: "marshall_palmer", "tropical" or "convective"
+    #[pyo3(get)]
+    pub relationship: String,
+    // Use R(KDP) in heavy rain, which needs KDP estimation to be enabled
+    #[pyo3(get, set)]
+    pub use_kdp: bool,
+    // Use R(Z, ZDR) where ZDR is available
+    #[pyo3(get, set)]
+    pub use_zdr: bool,
+    // Number of cells along each side of the rain rate grid
+    #[pyo3(get, set)]
+    pub grid_size: usize,
+    // Width of each grid cell in km
+    #[pyo3(get, set)]
+    pub grid_spacing: f32,
+}
+
+#[pymethods]
+impl PyQpeOptions {
+    #[new]
+    #[pyo3(signature = (
+        relationship = QpeParams::default().relationship.name(),
+        use_kdp = QpeParams::default().use_kdp,
+        use_zdr = QpeParams::default().use_zdr,
+        grid_size = Grid::default().size,
+        grid_spacing = Grid::default().spacing,
+    ))]
+    fn py_new(
+        relationship: &str,
+        use_kdp: bool,
+        use_zdr: bool,
+        grid_size: usize,
+        grid_spacing: f32,
+    ) -> PyResult<Self> {
+        if ZrRelationship::from_name(relationship).is_none() {
+            return Err(PyValueError::new_err(format!(
+                "Unexpected Z-R relationship: {}",
+                relationship
+            )));
+        }
+
+        Ok(Self {
+            relationship: relationship.to_string(),
+            use_kdp,
+            use_zdr,
+            grid_size,
+            grid_spacing,
+        })
+    }
+}
+
+impl PyQpeOptions {
+    pub(crate) fn params(&self) -> QpeParams {
+        QpeParams {
+            relationship: ZrRelationship::from_name(&self.relationship)
+                .expect("Relationship is validated on creation"),
+            use_kdp: self.use_kdp,
+            use_zdr: self.use_zdr,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn grid(&self) -> Grid {
+        Grid::new(self.grid_size, self.grid_spacing)
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use pyo3::prelude::*;
+
+use crate::model::volume::Volume;
+use crate::products::qvp::{volume_profile, Qvp};
+use crate::pymodel::py_qvp_options::PyQvpOptions;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyQvp {
+    #[pyo3(get)]
+    pub product: String,
+    // Elevation of the profiled sweep in radians
+    #[pyo3(get)]
+    pub elevation: f32,
+    // Height of the center of each bin above mean sea level, in km
+    #[pyo3(get)]
+    pub heights: Vec<f32>,
+    // Value of each bin, with NaN where there is no data
+    #[pyo3(get)]
+    pub values: Vec<f32>,
+    // Start time of the profiled sweep
+    #[pyo3(get)]
+    pub time: i64,
+}
+
+impl PyQvp {
+    // Profiles the volume, if it has a sweep with the product
+    pub(crate) fn new(volume: &Volume, options: &PyQvpOptions) -> Option<Self> {
+        let (profile, time, elevation) = volume_profile(
+            volume,
+            options.product(),
+            options.elevation.to_radians(),
+            &options.params(),
+        )?;
+
+        Some(Self {
+            product: options.product.clone(),
+            elevation,
+            heights: profile.heights,
+            values: profile.values,
+            time: time.timestamp(),
+        })
+    }
+
+    pub(crate) fn profile(&self) -> (Qvp, DateTime<Utc>) {
+        (
+            Qvp {
+                heights: self.heights.clone(),
+                values: self.values.clone(),
+            },
+            DateTime::<Utc>::from_timestamp(self.time, 0).unwrap_or_default(),
+        )
+    }
+}
@@ -0,0 +1,126 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::model::sweep_type::{product_from_name, SweepType};
+use crate::products::qvp::{QvpParams, QvpStatistic};
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyQvpOptions {
+    // Product to profile, named like the PyLevel2File fields such as
+    // "reflectivity" or "differential_reflectivity"
+    #[pyo3(get)]
+    pub product: String,
+    // Elevation in degrees. The closest scanned elevation is used.
+    #[pyo3(get, set)]
+    pub elevation: f32,
+    // Azimuthal statistic of each range: "mean" or "median"
+    #[pyo3(get)]
+    pub statistic: String,
+    // Height of each bin and of the top of the profile in km
+    #[pyo3(get)]
+    pub bin_size: f32,
+    #[pyo3(get)]
+    pub max_height: f32,
+    // Fraction of the radials which must have data for a range to be used
+    #[pyo3(get, set)]
+    pub min_fraction: f32,
+}
+
+#[pymethods]
+impl PyQvpOptions {
+    #[new]
+    #[pyo3(signature = (
+        product = "reflectivity",
+        elevation = 10.0,
+        statistic = QvpParams::default().statistic.name(),
+        bin_size = QvpParams::default().bin_size,
+        max_height = QvpParams::default().max_height,
+        min_fraction = QvpParams::default().min_fraction,
+    ))]
+    fn py_new(
+        product: &str,
+        elevation: f32,
+        statistic: &str,
+        bin_size: f32,
+        max_height: f32,
+        min_fraction: f32,
+    ) -> PyResult<Self> {
+        if product_from_name(product).is_none() {
+            return Err(PyValueError::new_err(format!(
+                "Unexpected product: {}",
+                product
+            )));
+        }
+
+        if QvpStatistic::from_name(statistic).is_none() {
+            return Err(PyValueError::new_err(format!(
+                "Unexpected statistic: {}",
+                statistic
+            )));
+        }
+
+        check_bins(bin_size, max_height)?;
+
+        Ok(Self {
+            product: product.to_string(),
+            elevation,
+            statistic: statistic.to_string(),
+            bin_size,
+            max_height,
+            min_fraction,
+        })
+    }
+
+    #[setter]
+    fn set_bin_size(&mut self, bin_size: f32) -> PyResult<()> {
+        check_bins(bin_size, self.max_height)?;
+        self.bin_size = bin_size;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_max_height(&mut self, max_height: f32) -> PyResult<()> {
+        check_bins(self.bin_size, max_height)?;
+        self.max_height = max_height;
+        Ok(())
+    }
+}
+
+// Most bins in a profile
+const MAX_BINS: f32 = 10000.0;
+
+// Checks that the profile has a positive number of bins which can be
+// allocated
+fn check_bins(bin_size: f32, max_height: f32) -> PyResult<()> {
+    if !(bin_size > 0.0 && max_height > 0.0) {
+        return Err(PyValueError::new_err(
+            "bin_size and max_height must be positive",
+        ));
+    }
+
+    if max_height / bin_size > MAX_BINS {
+        return Err(PyValueError::new_err(format!(
+            "Profiles can have at most {} bins",
+            MAX_BINS
+        )));
+    }
+
+    Ok(())
+}
+
+impl PyQvpOptions {
+    pub(crate) fn product(&self) -> SweepType {
+        product_from_name(&self.product).expect("Product is validated on creation")
+    }
+
+    pub(crate) fn params(&self) -> QvpParams {
+        QvpParams {
+            bin_size: self.bin_size,
+            max_height: self.max_height,
+            min_fraction: self.min_fraction,
+            statistic: QvpStatistic::from_name(&self.statistic)
+                .expect("Statistic is validated on creation"),
+        }
+    }
+}
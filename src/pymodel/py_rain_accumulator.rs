@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use chrono::{DateTime, Duration, Utc};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::model::grid::Grid;
+use crate::model::site::Site;
+use crate::products::qpe::RainAccumulator;
+use crate::pymodel::py_rain_rate_grid::PyRainRateGrid;
+use crate::pymodel::py_raster::grid_geotiff;
+
+#[pyclass]
+pub struct PyRainAccumulator {
+    accumulator: RainAccumulator,
+    // Site of the radar of the first grid added, if known
+    site: Option<Site>,
+}
+
+impl PyRainAccumulator {
+    fn encode(&self, nodata: f32) -> PyResult<Vec<u8>> {
+        grid_geotiff(
+            &[("rainfall", &self.accumulator.totals)],
+            &self.accumulator.grid,
+            self.site.as_ref(),
+            nodata,
+        )
+    }
+}
+
+#[pymethods]
+impl PyRainAccumulator {
+    #[new]
+    #[pyo3(signature = (
+        grid_size = Grid::default().size,
+        grid_spacing = Grid::default().spacing,
+        max_gap = 1800,
+    ))]
+    fn py_new(grid_size: usize, grid_spacing: f32, max_gap: i64) -> PyResult<Self> {
+        let max_gap = Duration::try_seconds(max_gap)
+            .ok_or_else(|| PyValueError::new_err("max_gap is out of range"))?;
+
+        Ok(Self {
+            accumulator: RainAccumulator::new(Grid::new(grid_size, grid_spacing), max_gap),
+            site: None,
+        })
+    }
+
+    // Adds the rain rate grid of a scan, which must be later than the
+    // previous scan and use the same grid
+    fn add(&mut self, rain_rate: &PyRainRateGrid) -> PyResult<()> {
+        let grid = Grid::new(rain_rate.size, rain_rate.spacing);
+        if grid != self.accumulator.grid {
+            return Err(PyValueError::new_err(
+                "Rain rate grid does not match the accumulator grid",
+            ));
+        }
+
+        let start_time = DateTime::<Utc>::from_timestamp(rain_rate.start_time, 0)
+            .ok_or_else(|| PyValueError::new_err("Invalid start time"))?;
+        let end_time = DateTime::<Utc>::from_timestamp(rain_rate.end_time, 0)
+            .ok_or_else(|| PyValueError::new_err("Invalid end time"))?;
+
+        self.accumulator
+            .add(rain_rate.values.clone(), start_time, end_time)
+            .map_err(PyValueError::new_err)?;
+
+        // Grids of the same size are assumed to be around the same radar
+        self.site = self.site.or(rain_rate.site);
+
+        Ok(())
+    }
+
+    // Rainfall in mm of each cell, row-major from the north west corner
+    #[getter]
+    fn totals(&self) -> Vec<f32> {
+        self.accumulator.totals.clone()
+    }
+
+    // Seconds of rainfall covered by the totals
+    #[getter]
+    fn duration(&self) -> i64 {
+        self.accumulator.duration.num_seconds()
+    }
+
+    #[getter]
+    fn grid_size(&self) -> usize {
+        self.accumulator.grid.size
+    }
+
+    #[getter]
+    fn grid_spacing(&self) -> f32 {
+        self.accumulator.grid.spacing
+    }
+
+    // The rainfall totals encoded as a GeoTIFF, as in PyRainRateGrid.geotiff
+    #[pyo3(signature = (nodata = -9999.0))]
+    fn geotiff<'py>(&self, py: Python<'py>, nodata: f32) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &self.encode(nodata)?))
+    }
+
+    // Writes the rainfall totals as a GeoTIFF to the path
+    #[pyo3(signature = (path, nodata = -9999.0))]
+    fn save(&self, py: Python, path: String, nodata: f32) -> PyResult<()> {
+        py.allow_threads(|| {
+            std::fs::write(Path::new(&path), self.encode(nodata)?)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::Path;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::model::grid::{grid_sweep, Grid};
+use crate::model::site::Site;
+use crate::model::volume::Volume;
+use crate::pymodel::py_contour_options::PyContourOptions;
+use crate::pymodel::py_contours::PyContours;
+use crate::pymodel::py_raster::grid_geotiff;
+use crate::render::contour::Cells;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyRainRateGrid {
+    // Number of cells along each side of the grid, centered on the radar
+    #[pyo3(get)]
+    pub size: usize,
+    // Width of each cell in km
+    #[pyo3(get)]
+    pub spacing: f32,
+    // Rain rate in mm/h of each cell, row-major from the north west corner,
+    // with NaN where there is no data
+    #[pyo3(get)]
+    pub values: Vec<f32>,
+
+    #[pyo3(get)]
+    pub start_time: i64,
+    #[pyo3(get)]
+    pub end_time: i64,
+
+    // Site of the radar the grid is centered on, if known
+    pub(crate) site: Option<Site>,
+}
+
+impl PyRainRateGrid {
+    // Grids the rain rate of the volume, if it has been estimated
+    pub(crate) fn new(volume: &Volume, grid: &Grid) -> Option<Self> {
+        let sweep = volume
+            .sweeps
+            .iter()
+            .find(|sweep| sweep.rain_rate.is_some())?;
+
+        Some(Self {
+            size: grid.size,
+            spacing: grid.spacing,
+            values: grid_sweep(grid, sweep, sweep.rain_rate.as_ref()?),
+            start_time: sweep.start_time.timestamp(),
+            end_time: sweep.end_time.timestamp(),
+            site: volume.site,
+        })
+    }
+
+    fn encode(&self, nodata: f32) -> PyResult<Vec<u8>> {
+        grid_geotiff(
+            &[("rain_rate", &self.values)],
+            &Grid::new(self.size, self.spacing),
+            self.site.as_ref(),
+            nodata,
+        )
+    }
+}
+
+#[pymethods]
+impl PyRainRateGrid {
+    // Contours the rain rate with the thresholds of the options
+    fn contours(&self, options: &PyContourOptions) -> PyResult<PyContours> {
+        let site = self
+            .site
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("The radar site is unknown"))?;
+
+        Ok(PyContours::from_cells(
+            "rain_rate",
+            &Cells::Grid {
+                grid: &Grid::new(self.size, self.spacing),
+                site,
+            },
+            &self.values,
+            options,
+            self.start_time,
+            self.end_time,
+        ))
+    }
+
+    // The rain rate encoded as a GeoTIFF in latitude and longitude, with a
+    // pixel for each cell resampled from the nearest cell and nodata where
+    // there is no data
+    #[pyo3(signature = (nodata = -9999.0))]
+    fn geotiff<'py>(&self, py: Python<'py>, nodata: f32) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &self.encode(nodata)?))
+    }
+
+    // Writes the rain rate as a GeoTIFF to the path
+    #[pyo3(signature = (path, nodata = -9999.0))]
+    fn save(&self, py: Python, path: String, nodata: f32) -> PyResult<()> {
+        py.allow_threads(|| {
+            std::fs::write(Path::new(&path), self.encode(nodata)?)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::Path;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::model::grid::Grid;
+use crate::model::site::Site;
+use crate::model::volume::Volume;
+use crate::pymodel::py_contour_options::PyContourOptions;
+use crate::pymodel::py_contours::PyContours;
+use crate::pymodel::py_geotiff_options::PyGeoTiffOptions;
+use crate::render::contour::Cells;
+use crate::render::geotiff::encode_geotiff;
+use crate::render::image::Bounds;
+use crate::render::raster::{covering_grid, resample_grid, LatLonGrid};
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyRaster {
+    #[pyo3(get)]
+    pub width: usize,
+    #[pyo3(get)]
+    pub height: usize,
+    // Degrees of (west, south, east, north) covered by the raster
+    #[pyo3(get)]
+    pub bounds: (f32, f32, f32, f32),
+    // Value written to the GeoTIFF for pixels without data
+    #[pyo3(get)]
+    pub nodata: f32,
+    #[pyo3(get)]
+    pub start_time: i64,
+    #[pyo3(get)]
+    pub end_time: i64,
+    bands: Vec<(String, Vec<f32>)>,
+}
+
+impl PyRaster {
+    // Resamples the bands selected by the options, if the site of the
+    // volume is known. Bands of products which the volume does not have
+    // have no data.
+    pub(crate) fn new(volume: &Volume, options: &PyGeoTiffOptions) -> Option<Self> {
+        let site = volume.site.as_ref()?;
+        let grid = options.grid(site);
+
+        let bands = options
+            .bands
+            .iter()
+            .map(|band| (band.name(), band.band().resample(volume, site, &grid)))
+            .collect();
+
+        let start_time = volume.sweeps.iter().map(|sweep| sweep.start_time).min()?;
+        let end_time = volume.sweeps.iter().map(|sweep| sweep.end_time).max()?;
+        let bounds = grid.bounds;
+
+        Some(Self {
+            width: grid.width,
+            height: grid.height,
+            bounds: (bounds.west, bounds.south, bounds.east, bounds.north),
+            nodata: options.nodata,
+            start_time: start_time.timestamp(),
+            end_time: end_time.timestamp(),
+            bands,
+        })
+    }
+
+    fn bounds(&self) -> Bounds {
+        let (west, south, east, north) = self.bounds;
+        Bounds {
+            west,
+            south,
+            east,
+            north,
+        }
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, String> {
+        encode_geotiff(
+            &self.bands,
+            self.width,
+            self.height,
+            &self.bounds(),
+            self.nodata,
+        )
+    }
+}
+
+// Encodes named grids around the radar as the bands of a GeoTIFF, with a
+// pixel for each cell
+pub(crate) fn grid_geotiff(
+    bands: &[(&str, &[f32])],
+    grid: &Grid,
+    site: Option<&Site>,
+    nodata: f32,
+) -> PyResult<Vec<u8>> {
+    let site = site.ok_or_else(|| PyValueError::new_err("The radar site is unknown"))?;
+    let raster = covering_grid(grid, site);
+
+    let bands: Vec<_> = bands
+        .iter()
+        .map(|(name, values)| (name.to_string(), resample_grid(grid, values, site, &raster)))
+        .collect();
+
+    encode_geotiff(&bands, raster.width, raster.height, &raster.bounds, nodata)
+        .map_err(PyValueError::new_err)
+}
+
+#[pymethods]
+impl PyRaster {
+    // Description of each band
+    #[getter]
+    fn band_names(&self) -> Vec<String> {
+        self.bands.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    // Values of each band, row-major from the north west corner, with NaN
+    // where there is no data
+    #[getter]
+    fn values(&self) -> Vec<Vec<f32>> {
+        self.bands
+            .iter()
+            .map(|(_, values)| values.clone())
+            .collect()
+    }
+
+    // The raster encoded as a GeoTIFF
+    fn geotiff<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        let data = self.encode().map_err(PyValueError::new_err)?;
+
+        Ok(PyBytes::new(py, &data))
+    }
+
+    // Contours a band with the thresholds of the options, following the
+    // pixels of the raster
+    #[pyo3(signature = (options, band = 0))]
+    fn contours(&self, options: &PyContourOptions, band: usize) -> PyResult<PyContours> {
+        let Some((name, values)) = self.bands.get(band) else {
+            return Err(PyValueError::new_err(format!("No band {}", band)));
+        };
+
+        let grid = LatLonGrid::new(self.bounds(), self.width, self.height);
+        Ok(PyContours::from_cells(
+            name,
+            &Cells::LatLon(&grid),
+            values,
+            options,
+            self.start_time,
+            self.end_time,
+        ))
+    }
+
+    // Writes the raster as a GeoTIFF to the path
+    fn save(&self, py: Python, path: String) -> PyResult<()> {
+        py.allow_threads(|| {
+            let data = self.encode().map_err(PyValueError::new_err)?;
+            std::fs::write(Path::new(&path), data)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,152 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::model::site::Site;
+use crate::model::sweep_type::{product_from_name, SweepType};
+use crate::pymodel::py_colormap::PyColormap;
+use crate::render::colormap::Colormap;
+use crate::render::image::Bounds;
+use crate::render::raster::LatLonGrid;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyRenderOptions {
+    // Product to render, named like the PyLevel2File fields
+    #[pyo3(get)]
+    pub product: String,
+    // Elevation in degrees. The closest scanned elevation is used.
+    #[pyo3(get, set)]
+    pub elevation: f32,
+    // Size of the image in pixels
+    #[pyo3(get)]
+    pub width: usize,
+    #[pyo3(get)]
+    pub height: usize,
+    // Degrees of (west, south, east, north) covered by the image, or None
+    // for the square around the radar out to distance km
+    #[pyo3(get)]
+    pub bounds: Option<(f32, f32, f32, f32)>,
+    #[pyo3(get, set)]
+    pub distance: f32,
+    // Colormap of the product values, or None for the built in colormap
+    #[pyo3(get, set)]
+    pub colormap: Option<PyColormap>,
+}
+
+#[pymethods]
+impl PyRenderOptions {
+    #[new]
+    #[pyo3(signature = (
+        product = "reflectivity",
+        elevation = 0.5,
+        width = 1024,
+        height = 1024,
+        bounds = None,
+        distance = 230.0,
+        colormap = None,
+    ))]
+    fn py_new(
+        product: &str,
+        elevation: f32,
+        width: usize,
+        height: usize,
+        bounds: Option<(f32, f32, f32, f32)>,
+        distance: f32,
+        colormap: Option<PyColormap>,
+    ) -> PyResult<Self> {
+        if product_from_name(product).is_none() {
+            return Err(PyValueError::new_err(format!(
+                "Unexpected product: {}",
+                product
+            )));
+        }
+
+        check_size("Images", width, height)?;
+
+        check_bounds(bounds)?;
+
+        Ok(Self {
+            product: product.to_string(),
+            elevation,
+            width,
+            height,
+            bounds,
+            distance,
+            colormap,
+        })
+    }
+
+    #[setter]
+    fn set_width(&mut self, width: usize) -> PyResult<()> {
+        check_size("Images", width, self.height)?;
+        self.width = width;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_height(&mut self, height: usize) -> PyResult<()> {
+        check_size("Images", self.width, height)?;
+        self.height = height;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_bounds(&mut self, bounds: Option<(f32, f32, f32, f32)>) -> PyResult<()> {
+        check_bounds(bounds)?;
+        self.bounds = bounds;
+        Ok(())
+    }
+}
+
+// Most pixels along each side of an image or raster
+const MAX_SIZE: usize = 16384;
+
+// Checks that images or rasters have pixels and can be allocated
+pub(crate) fn check_size(kind: &str, width: usize, height: usize) -> PyResult<()> {
+    if width == 0 || height == 0 {
+        return Err(PyValueError::new_err(format!("{} must have a size", kind)));
+    }
+
+    if width > MAX_SIZE || height > MAX_SIZE {
+        return Err(PyValueError::new_err(format!(
+            "{} can be at most {} pixels across",
+            kind, MAX_SIZE
+        )));
+    }
+
+    Ok(())
+}
+
+// Checks that bounds given as degrees of (west, south, east, north) have
+// west < east and south < north
+pub(crate) fn check_bounds(bounds: Option<(f32, f32, f32, f32)>) -> PyResult<()> {
+    match bounds.map(Bounds::from_edges) {
+        Some(None) => Err(PyValueError::new_err(
+            "bounds must have west < east and south < north",
+        )),
+        _ => Ok(()),
+    }
+}
+
+impl PyRenderOptions {
+    pub(crate) fn product(&self) -> SweepType {
+        product_from_name(&self.product).expect("Product is validated on creation")
+    }
+
+    pub(crate) fn grid(&self, site: &Site) -> LatLonGrid {
+        let bounds = match self.bounds {
+            Some(edges) => Bounds::from_edges(edges).expect("Bounds are validated when set"),
+            None => Bounds::around(site, self.distance),
+        };
+
+        LatLonGrid::new(bounds, self.width, self.height)
+    }
+
+    // The colormap of the options, or the built in colormap of the product
+    pub(crate) fn colormap(&self, product: SweepType) -> Colormap {
+        match &self.colormap {
+            Some(colormap) => colormap.colormap.clone(),
+            None => Colormap::builtin(product),
+        }
+    }
+}
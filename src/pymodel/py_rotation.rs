@@ -0,0 +1,62 @@
+use pyo3::prelude::*;
+
+use crate::model::rotation::Rotation;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyRotation {
+    // Center on the lowest tilt in km east and north of the radar
+    #[pyo3(get)]
+    pub x: f32,
+    #[pyo3(get)]
+    pub y: f32,
+    // Heights of the lowest and highest tilts in km above mean sea level
+    #[pyo3(get)]
+    pub base: f32,
+    #[pyo3(get)]
+    pub top: f32,
+    // Half the largest velocity difference across the rotation in m/s,
+    // over all tilts and on the lowest tilt
+    #[pyo3(get)]
+    pub rotational_velocity: f32,
+    #[pyo3(get)]
+    pub low_level_rotational_velocity: f32,
+    // Distance between the velocity peaks in km
+    #[pyo3(get)]
+    pub diameter: f32,
+    #[pyo3(get)]
+    pub gate_to_gate_delta_v: f32,
+    #[pyo3(get)]
+    pub strength_rank: u8,
+    #[pyo3(get)]
+    pub is_tvs: bool,
+    #[pyo3(get)]
+    pub features: usize,
+}
+
+impl PyRotation {
+    pub(crate) fn new(rotation: &Rotation) -> Self {
+        Self {
+            x: rotation.x,
+            y: rotation.y,
+            base: rotation.base,
+            top: rotation.top,
+            rotational_velocity: rotation.rotational_velocity,
+            low_level_rotational_velocity: rotation.low_level_rotational_velocity,
+            diameter: rotation.diameter,
+            gate_to_gate_delta_v: rotation.gate_to_gate_delta_v,
+            strength_rank: rotation.strength_rank,
+            is_tvs: rotation.is_tvs,
+            features: rotation.features,
+        }
+    }
+}
+
+#[pymethods]
+impl PyRotation {
+    // Depth of the rotation in km
+    #[getter]
+    fn depth(&self) -> f32 {
+        self.top - self.base
+    }
+}
@@ -0,0 +1,86 @@
+use pyo3::prelude::*;
+
+use crate::products::rotation::RotationParams;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyRotationOptions {
+    // Smallest velocity difference in m/s and azimuthal shear in s⁻¹
+    // across a run of increasing velocity
+    #[pyo3(get, set)]
+    pub min_delta_v: f32,
+    #[pyo3(get, set)]
+    pub min_shear: f32,
+    // Largest distance in km across a run of increasing velocity
+    #[pyo3(get, set)]
+    pub max_diameter: f32,
+    // Range in km beyond which rotation is not searched
+    #[pyo3(get, set)]
+    pub max_range: f32,
+    // Largest horizontal distance in km between the features of a rotation
+    // on consecutive tilts
+    #[pyo3(get, set)]
+    pub max_association_distance: f32,
+    // Number of tilts a rotation must span
+    #[pyo3(get, set)]
+    pub min_features: usize,
+    // Gate to gate velocity difference in m/s and depth in km needed for a
+    // tornado vortex signature
+    #[pyo3(get, set)]
+    pub tvs_delta_v: f32,
+    #[pyo3(get, set)]
+    pub tvs_min_depth: f32,
+}
+
+#[pymethods]
+impl PyRotationOptions {
+    #[new]
+    #[pyo3(signature = (
+        min_delta_v = RotationParams::default().min_delta_v,
+        min_shear = RotationParams::default().min_shear,
+        max_diameter = RotationParams::default().max_diameter,
+        max_range = RotationParams::default().max_range,
+        max_association_distance = RotationParams::default().max_association_distance,
+        min_features = RotationParams::default().min_features,
+        tvs_delta_v = RotationParams::default().tvs_delta_v,
+        tvs_min_depth = RotationParams::default().tvs_min_depth,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        min_delta_v: f32,
+        min_shear: f32,
+        max_diameter: f32,
+        max_range: f32,
+        max_association_distance: f32,
+        min_features: usize,
+        tvs_delta_v: f32,
+        tvs_min_depth: f32,
+    ) -> Self {
+        Self {
+            min_delta_v,
+            min_shear,
+            max_diameter,
+            max_range,
+            max_association_distance,
+            min_features,
+            tvs_delta_v,
+            tvs_min_depth,
+        }
+    }
+}
+
+impl PyRotationOptions {
+    pub(crate) fn params(&self) -> RotationParams {
+        RotationParams {
+            min_delta_v: self.min_delta_v,
+            min_shear: self.min_shear,
+            max_diameter: self.max_diameter,
+            max_range: self.max_range,
+            max_association_distance: self.max_association_distance,
+            min_features: self.min_features,
+            tvs_delta_v: self.tvs_delta_v,
+            tvs_min_depth: self.tvs_min_depth,
+            ..Default::default()
+        }
+    }
+}
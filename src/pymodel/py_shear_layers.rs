@@ -0,0 +1,150 @@
+use std::path::Path;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::model::grid::Grid;
+use crate::model::site::Site;
+use crate::model::sweep_type::{AZIMUTHAL_SHEAR, DIVERGENCE};
+use crate::model::volume::Volume;
+use crate::products::azimuthal_shear::layer_maximum;
+use crate::pymodel::py_azimuthal_shear_options::PyAzimuthalShearOptions;
+use crate::pymodel::py_contour_options::PyContourOptions;
+use crate::pymodel::py_contours::PyContours;
+use crate::pymodel::py_raster::grid_geotiff;
+use crate::render::contour::Cells;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyShearLayers {
+    // Number of cells along each side of the grids, centered on the radar
+    #[pyo3(get)]
+    pub size: usize,
+    // Width of each cell in km
+    #[pyo3(get)]
+    pub spacing: f32,
+    // Bottom and top of each layer in km above the radar
+    #[pyo3(get)]
+    pub low_layer: (f32, f32),
+    #[pyo3(get)]
+    pub mid_layer: (f32, f32),
+    // Largest azimuthal shear and divergence in s⁻¹ within each layer,
+    // row-major from the north west corner, with NaN where there is no data
+    #[pyo3(get)]
+    pub low_level_shear: Vec<f32>,
+    #[pyo3(get)]
+    pub mid_level_shear: Vec<f32>,
+    #[pyo3(get)]
+    pub low_level_divergence: Vec<f32>,
+    #[pyo3(get)]
+    pub mid_level_divergence: Vec<f32>,
+
+    #[pyo3(get)]
+    pub start_time: i64,
+    #[pyo3(get)]
+    pub end_time: i64,
+
+    // Site of the radar the grid is centered on, if known
+    site: Option<Site>,
+}
+
+impl PyShearLayers {
+    // Grids the layer maxima of the volume, if azimuthal shear has been
+    // estimated
+    pub(crate) fn new(volume: &Volume, options: &PyAzimuthalShearOptions) -> Option<Self> {
+        let sweeps = volume
+            .sweeps
+            .iter()
+            .filter(|sweep| sweep.azimuthal_shear.is_some());
+        let start_time = sweeps.clone().map(|sweep| sweep.start_time).min()?;
+        let end_time = sweeps.map(|sweep| sweep.end_time).max()?;
+
+        let grid = options.grid();
+        let (low_bottom, low_top) = options.low_layer;
+        let (mid_bottom, mid_top) = options.mid_layer;
+
+        Some(Self {
+            size: grid.size,
+            spacing: grid.spacing,
+            low_layer: options.low_layer,
+            mid_layer: options.mid_layer,
+            low_level_shear: layer_maximum(volume, &grid, AZIMUTHAL_SHEAR, low_bottom, low_top),
+            mid_level_shear: layer_maximum(volume, &grid, AZIMUTHAL_SHEAR, mid_bottom, mid_top),
+            low_level_divergence: layer_maximum(volume, &grid, DIVERGENCE, low_bottom, low_top),
+            mid_level_divergence: layer_maximum(volume, &grid, DIVERGENCE, mid_bottom, mid_top),
+            start_time: start_time.timestamp(),
+            end_time: end_time.timestamp(),
+            site: volume.site,
+        })
+    }
+
+    fn encode(&self, nodata: f32) -> PyResult<Vec<u8>> {
+        grid_geotiff(
+            &[
+                ("low_level_shear", &self.low_level_shear),
+                ("mid_level_shear", &self.mid_level_shear),
+                ("low_level_divergence", &self.low_level_divergence),
+                ("mid_level_divergence", &self.mid_level_divergence),
+            ],
+            &Grid::new(self.size, self.spacing),
+            self.site.as_ref(),
+            nodata,
+        )
+    }
+}
+
+#[pymethods]
+impl PyShearLayers {
+    // Contours one of the layer grids, named like its field such as
+    // "low_level_shear", with the thresholds of the options
+    #[pyo3(signature = (options, layer = "low_level_shear"))]
+    fn contours(&self, options: &PyContourOptions, layer: &str) -> PyResult<PyContours> {
+        let values = match layer {
+            "low_level_shear" => &self.low_level_shear,
+            "mid_level_shear" => &self.mid_level_shear,
+            "low_level_divergence" => &self.low_level_divergence,
+            "mid_level_divergence" => &self.mid_level_divergence,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unexpected layer: {}",
+                    layer
+                )))
+            }
+        };
+
+        let site = self
+            .site
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("The radar site is unknown"))?;
+
+        Ok(PyContours::from_cells(
+            layer,
+            &Cells::Grid {
+                grid: &Grid::new(self.size, self.spacing),
+                site,
+            },
+            values,
+            options,
+            self.start_time,
+            self.end_time,
+        ))
+    }
+
+    // The layers encoded as the bands of a GeoTIFF, named like their
+    // fields, as in PyRainRateGrid.geotiff
+    #[pyo3(signature = (nodata = -9999.0))]
+    fn geotiff<'py>(&self, py: Python<'py>, nodata: f32) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &self.encode(nodata)?))
+    }
+
+    // Writes the layers as a GeoTIFF to the path
+    #[pyo3(signature = (path, nodata = -9999.0))]
+    fn save(&self, py: Python, path: String, nodata: f32) -> PyResult<()> {
+        py.allow_threads(|| {
+            std::fs::write(Path::new(&path), self.encode(nodata)?)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,76 @@
+use pyo3::prelude::*;
+
+use crate::model::storm_cell::{StormCell, StormCells};
+use crate::pymodel::py_hail::PyHail;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyStormCell {
+    // Mass weighted centroid in km east and north of the radar, and in km
+    // above mean sea level
+    #[pyo3(get)]
+    pub x: f32,
+    #[pyo3(get)]
+    pub y: f32,
+    #[pyo3(get)]
+    pub height: f32,
+    // Heights of the lowest and highest components in km
+    #[pyo3(get)]
+    pub base: f32,
+    #[pyo3(get)]
+    pub top: f32,
+    #[pyo3(get)]
+    pub max_reflectivity: f32,
+    #[pyo3(get)]
+    pub max_reflectivity_height: f32,
+    // Cell based vertically integrated liquid in kg/m²
+    #[pyo3(get)]
+    pub vil: f32,
+    // Number of tilts with a component of the cell
+    #[pyo3(get)]
+    pub components: usize,
+    // Hail products of the cell, when hail detection is enabled
+    #[pyo3(get)]
+    pub hail: Option<PyHail>,
+}
+
+impl PyStormCell {
+    pub(crate) fn new(cell: &StormCell) -> Self {
+        Self {
+            x: cell.x,
+            y: cell.y,
+            height: cell.height,
+            base: cell.base,
+            top: cell.top,
+            max_reflectivity: cell.max_reflectivity,
+            max_reflectivity_height: cell.max_reflectivity_height,
+            vil: cell.vil,
+            components: cell.components,
+            hail: cell.hail.as_ref().map(PyHail::new),
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyStormCells {
+    pub(crate) storm_cells: StormCells,
+}
+
+#[pymethods]
+impl PyStormCells {
+    // Start time of the volume the cells were identified in
+    #[getter]
+    fn time(&self) -> i64 {
+        self.storm_cells.time.timestamp()
+    }
+
+    #[getter]
+    fn cells(&self) -> Vec<PyStormCell> {
+        self.storm_cells
+            .cells
+            .iter()
+            .map(PyStormCell::new)
+            .collect()
+    }
+}
@@ -0,0 +1,56 @@
+use pyo3::prelude::*;
+
+use crate::products::storm_cells::StormCellParams;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyStormCellOptions {
+    // Reflectivity thresholds in dBZ used to segment each tilt
+    #[pyo3(get, set)]
+    pub thresholds: Vec<f32>,
+    // Smallest area of a component on a tilt in km²
+    #[pyo3(get, set)]
+    pub min_area: f32,
+    // Largest horizontal distance in km between the components of a cell
+    // on consecutive tilts
+    #[pyo3(get, set)]
+    pub max_association_distance: f32,
+    // Number of tilts a cell must span
+    #[pyo3(get, set)]
+    pub min_components: usize,
+}
+
+#[pymethods]
+impl PyStormCellOptions {
+    #[new]
+    #[pyo3(signature = (
+        thresholds = StormCellParams::default().thresholds,
+        min_area = StormCellParams::default().min_area,
+        max_association_distance = StormCellParams::default().max_association_distance,
+        min_components = StormCellParams::default().min_components,
+    ))]
+    fn py_new(
+        thresholds: Vec<f32>,
+        min_area: f32,
+        max_association_distance: f32,
+        min_components: usize,
+    ) -> Self {
+        Self {
+            thresholds,
+            min_area,
+            max_association_distance,
+            min_components,
+        }
+    }
+}
+
+impl PyStormCellOptions {
+    pub(crate) fn params(&self) -> StormCellParams {
+        StormCellParams {
+            thresholds: self.thresholds.clone(),
+            min_area: self.min_area,
+            max_association_distance: self.max_association_distance,
+            min_components: self.min_components,
+        }
+    }
+}
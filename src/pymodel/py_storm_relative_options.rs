@@ -0,0 +1,57 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::products::storm_relative::motion_from_direction;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyStormRelativeOptions {
+    // Direction in degrees the storm moves from and its speed in m/s
+    #[pyo3(get)]
+    pub direction: Option<f32>,
+    #[pyo3(get)]
+    pub speed: Option<f32>,
+    // Storm motion (u, v) in m/s towards the east and north, such as the
+    // mean motion of a storm tracker
+    #[pyo3(get)]
+    pub motion: Option<(f32, f32)>,
+}
+
+#[pymethods]
+impl PyStormRelativeOptions {
+    #[new]
+    #[pyo3(signature = (direction = None, speed = None, motion = None))]
+    fn py_new(
+        direction: Option<f32>,
+        speed: Option<f32>,
+        motion: Option<(f32, f32)>,
+    ) -> PyResult<Self> {
+        if direction.is_some() != speed.is_some() {
+            return Err(PyValueError::new_err(
+                "direction and speed must be given together",
+            ));
+        }
+
+        if direction.is_some() && motion.is_some() {
+            return Err(PyValueError::new_err(
+                "Give either direction and speed or motion",
+            ));
+        }
+
+        Ok(Self {
+            direction,
+            speed,
+            motion,
+        })
+    }
+}
+
+impl PyStormRelativeOptions {
+    // The given storm motion, or None to estimate it from the mean wind
+    pub(crate) fn motion(&self) -> Option<(f32, f32)> {
+        match (self.direction, self.speed) {
+            (Some(direction), Some(speed)) => Some(motion_from_direction(direction, speed)),
+            _ => self.motion,
+        }
+    }
+}
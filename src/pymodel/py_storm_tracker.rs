@@ -0,0 +1,83 @@
+use chrono::Duration;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::products::storm_tracking::{StormTracker, TrackedCell};
+use crate::pymodel::py_storm_cell::{PyStormCell, PyStormCells};
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyTrackedCell {
+    // Identifies the cell across volumes
+    #[pyo3(get)]
+    pub id: u32,
+    #[pyo3(get)]
+    pub cell: PyStormCell,
+    // Motion in m/s towards the east and north, once the cell has been
+    // matched across two volumes
+    #[pyo3(get)]
+    pub motion: Option<(f32, f32)>,
+    // Forecast positions in km east and north of the radar for each of the
+    // tracker's forecast intervals, empty when the motion is unknown
+    #[pyo3(get)]
+    pub forecast: Vec<(f32, f32)>,
+    // Positions of the cell in the previous volumes, oldest first
+    #[pyo3(get)]
+    pub track: Vec<(f32, f32)>,
+}
+
+impl PyTrackedCell {
+    fn new(tracked_cell: &TrackedCell) -> Self {
+        Self {
+            id: tracked_cell.id,
+            cell: PyStormCell::new(&tracked_cell.cell),
+            motion: tracked_cell.motion,
+            forecast: tracked_cell.forecast.clone(),
+            track: tracked_cell.track.clone(),
+        }
+    }
+}
+
+#[pyclass]
+pub struct PyStormTracker {
+    tracker: StormTracker,
+}
+
+#[pymethods]
+impl PyStormTracker {
+    #[new]
+    #[pyo3(signature = (max_speed = 30.0, max_gap = 1200, forecast_minutes = vec![15, 30, 45, 60]))]
+    fn py_new(max_speed: f32, max_gap: i64, forecast_minutes: Vec<i64>) -> PyResult<Self> {
+        let max_gap = Duration::try_seconds(max_gap)
+            .ok_or_else(|| PyValueError::new_err("max_gap is out of range"))?;
+        let forecast_intervals = forecast_minutes
+            .into_iter()
+            .map(|minutes| {
+                Duration::try_minutes(minutes)
+                    .ok_or_else(|| PyValueError::new_err("forecast_minutes is out of range"))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        Ok(Self {
+            tracker: StormTracker::new(max_speed, max_gap, forecast_intervals),
+        })
+    }
+
+    // Matches the storm cells of a volume to the cells of the previous
+    // volume, which must be earlier
+    fn update(&mut self, storm_cells: &PyStormCells) -> PyResult<Vec<PyTrackedCell>> {
+        let tracked = self
+            .tracker
+            .update(&storm_cells.storm_cells)
+            .map_err(PyValueError::new_err)?;
+
+        Ok(tracked.iter().map(PyTrackedCell::new).collect())
+    }
+
+    // Mean motion (u, v) in m/s of the latest cells with a known motion,
+    // for use as the storm motion of storm-relative velocity
+    #[getter]
+    fn mean_motion(&self) -> Option<(f32, f32)> {
+        self.tracker.mean_motion()
+    }
+}
@@ -1,10 +1,26 @@
+use std::sync::Arc;
+
+use nexrad_decode::messages::volume_coverage_pattern::WaveformType;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-use crate::model::{sweep::Sweep, sweep_type::*};
+use crate::model::{gate_status::*, sweep::Sweep, sweep_type::*, volume::Volume};
+use crate::pymodel::py_image::PyImage;
+use crate::pymodel::py_render_options::PyRenderOptions;
+use crate::pymodel::py_sweep_buffer::{PySweepBuffer, SweepBufferKind};
 
 #[pyclass]
 #[derive(Clone)]
 pub struct PySweep {
+    #[pyo3(get)]
+    pub cut_index: usize,
+    #[pyo3(get)]
+    pub waveform: String,
+    #[pyo3(get)]
+    pub paired_cut: Option<usize>,
+    #[pyo3(get)]
+    pub is_sails: bool,
+
     #[pyo3(get)]
     pub elevation: f32,
 
@@ -14,6 +30,16 @@ pub struct PySweep {
     pub az_step: f32,
     #[pyo3(get)]
     pub az_count: i32,
+    #[pyo3(get)]
+    pub nominal_az_count: i32,
+    #[pyo3(get)]
+    pub completeness: f32,
+    #[pyo3(get)]
+    pub azimuths: Vec<f32>,
+    #[pyo3(get)]
+    pub elevations: Vec<f32>,
+    #[pyo3(get)]
+    pub times: Vec<i64>,
 
     #[pyo3(get)]
     pub range_first: f32,
@@ -29,92 +55,98 @@ pub struct PySweep {
 
     #[pyo3(get)]
     pub data: Vec<u8>,
+    #[pyo3(get)]
+    pub mask: Vec<u8>,
+
+    // The converted volume and the index of this sweep in it, which the
+    // values and masked buffers are views into
+    volume: Arc<Volume>,
+    index: usize,
+    product: SweepType,
+}
+
+fn waveform_name(waveform_type: WaveformType) -> &'static str {
+    match waveform_type {
+        WaveformType::CS => "CS",
+        WaveformType::CDW => "CDW",
+        WaveformType::CDWO => "CDWO",
+        WaveformType::B => "B",
+        WaveformType::SPP => "SPP",
+        _ => "Unknown",
+    }
 }
 
 impl PySweep {
-    pub(crate) fn empty(start_time: i64, end_time: i64, elevation: f32) -> Self {
+    pub(crate) fn empty(volume: &Arc<Volume>, index: usize, data_type: SweepType) -> Self {
+        let sweep = &volume.sweeps[index];
+
         Self {
-            elevation,
+            cut_index: sweep.cut_index,
+            waveform: waveform_name(sweep.waveform_type).to_string(),
+            paired_cut: sweep.paired_cut,
+            is_sails: sweep.is_sails,
+            elevation: sweep.elevation,
             az_first: 0.0,
             az_step: 0.0,
             az_count: 0,
+            nominal_az_count: sweep.nominal_az_count,
+            completeness: sweep.completeness(),
+            azimuths: Vec::new(),
+            elevations: Vec::new(),
+            times: Vec::new(),
             range_first: 0.0,
             range_step: 0.0,
             range_count: 0,
-            start_time: start_time,
-            end_time: end_time,
+            start_time: sweep.start_time.timestamp(),
+            end_time: sweep.end_time.timestamp(),
             data: Vec::new(),
+            mask: Vec::new(),
+            volume: volume.clone(),
+            index,
+            product: data_type,
         }
     }
 
-    pub(crate) fn new(sweep: &Sweep, data_type: SweepType) -> Self {
+    pub(crate) fn new(volume: &Arc<Volume>, index: usize, data_type: SweepType) -> Self {
+        let sweep: &Sweep = &volume.sweeps[index];
         let mut data: Vec<u8> = Vec::new();
+        let mut mask_status: Vec<u8> = Vec::new();
 
-        let (min, max) = match data_type {
-            REFLECTIVITY => (-20.0, 80.0),
-            VELOCITY => (-100.0, 100.0),
-            _ => panic!("Unexpected product: {}", data_type),
-        };
-
-        let product = match data_type {
-            REFLECTIVITY => sweep.reflectivity.as_ref().unwrap(),
-            VELOCITY => sweep.velocity.as_ref().unwrap(),
-            _ => panic!("Unexpected product {}", data_type),
-        };
+        let (min, max) = product_range(data_type);
 
-        // Find the first gate with data somewhere in one of the radials
-        let mut first_gate = 0;
-        let mut found_data = false;
-        for gate in 0..product.gates {
-            for radial in 0..product.radials {
-                if !product.get_mask(radial, gate) {
-                    // We have data!
-                    found_data = true;
-                    break;
-                }
-            }
-            if found_data {
-                break;
-            }
-            // We didn't find data in any radial for this gate
-            // The first gate with data must be at least the next one
-            first_gate = gate as i32 + 1;
-        }
+        let product = sweep
+            .product(data_type)
+            .unwrap_or_else(|| panic!("Unexpected product {}", data_type));
 
-        // Find the last gate with data somewhere in one of the radials
-        let mut last_gate = product.gates as i32 - 1;
-        found_data = false;
-        for gate in (0..product.gates).rev() {
-            for radial in 0..product.radials {
-                if !product.get_mask(radial, gate) {
-                    // We have data!
-                    found_data = true;
-                    break;
-                }
-            }
-            if found_data {
-                break;
+        // Find the first and last gates with data somewhere in one of the radials,
+        // keeping range folded gates so they can be displayed
+        let mut has_data = vec![false; product.gates];
+        for radial in 0..product.radials {
+            let mask = product.radial_mask(radial);
+            let range_folded = product.radial_range_folded(radial);
+            for gate in 0..product.gates {
+                has_data[gate] |= !mask[gate] || range_folded[gate];
             }
-            // We didn't find data in any radial for this gate
-            // The last gate with data must be at least the one before
-            last_gate = gate as i32 - 1;
         }
 
-        if last_gate < first_gate {
-            return PySweep::empty(
-                sweep.start_time.timestamp(),
-                sweep.end_time.timestamp(),
-                sweep.elevation,
-            );
-        }
+        let (first_gate, last_gate) = match (
+            has_data.iter().position(|has_data| *has_data),
+            has_data.iter().rposition(|has_data| *has_data),
+        ) {
+            (Some(first_gate), Some(last_gate)) => (first_gate, last_gate),
+            _ => return PySweep::empty(volume, index, data_type),
+        };
 
-        for radial in 0..product.radials {
+        for (radial, (values, mask)) in product.radials_iter().enumerate() {
             data.push(0);
-            for gate in (first_gate as usize)..((last_gate + 1) as usize) {
-                if product.get_mask(radial, gate) {
+            mask_status.push(GATE_NO_DATA);
+            for gate in first_gate..(last_gate + 1) {
+                mask_status.push(product.status(radial, gate));
+
+                if mask[gate] {
                     data.push(0);
                 } else {
-                    let mut value = product.get_value(radial, gate);
+                    let mut value = values[gate];
 
                     value -= min;
                     value /= max - min;
@@ -126,22 +158,79 @@ impl PySweep {
                 }
             }
             data.push(0);
+            mask_status.push(GATE_NO_DATA);
         }
 
         let range_first = sweep.range_first + (first_gate as f32 * sweep.range_step);
-        let range_count = last_gate - first_gate + 1;
+        let range_count = (last_gate - first_gate + 1) as i32;
 
         Self {
+            cut_index: sweep.cut_index,
+            waveform: waveform_name(sweep.waveform_type).to_string(),
+            paired_cut: sweep.paired_cut,
+            is_sails: sweep.is_sails,
             elevation: sweep.elevation,
             az_first: sweep.az_first,
             az_step: sweep.az_step,
             az_count: sweep.az_count,
+            nominal_az_count: sweep.nominal_az_count,
+            completeness: sweep.completeness(),
+            azimuths: sweep.azimuths.clone(),
+            elevations: sweep.elevations.clone(),
+            times: sweep.times.iter().map(|t| t.timestamp_millis()).collect(),
             range_first: range_first - sweep.range_step,
             range_step: sweep.range_step,
             range_count: range_count + 2,
             start_time: sweep.start_time.timestamp(),
             end_time: sweep.end_time.timestamp(),
             data,
+            mask: mask_status,
+            volume: volume.clone(),
+            index,
+            product: data_type,
         }
     }
 }
+
+#[pymethods]
+impl PySweep {
+    // Unquantized values of every gate as a radials x gates float32 buffer,
+    // shared with the converted volume rather than copied. Masked gates
+    // hold stale values.
+    #[getter]
+    fn values(&self) -> PySweepBuffer {
+        PySweepBuffer::new(
+            self.volume.clone(),
+            self.index,
+            self.product,
+            SweepBufferKind::Values,
+        )
+    }
+
+    // Mask of every gate as a radials x gates bool buffer, true where the
+    // gate has no value, shared with the converted volume
+    #[getter]
+    fn masked(&self) -> PySweepBuffer {
+        PySweepBuffer::new(
+            self.volume.clone(),
+            self.index,
+            self.product,
+            SweepBufferKind::Mask,
+        )
+    }
+
+    // Renders this sweep, such as a SAILS repeat which rendering by
+    // elevation would not pick, with the size, bounds and colormap of the
+    // options. Their product and elevation are not used.
+    fn render(&self, options: &PyRenderOptions) -> PyResult<PyImage> {
+        let site = self
+            .volume
+            .site
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("The radar site is unknown"))?;
+        let sweep = &self.volume.sweeps[self.index];
+
+        Ok(PyImage::from_sweep(sweep, self.product, site, options)
+            .expect("The sweep has its product"))
+    }
+}
@@ -0,0 +1,168 @@
+use std::ffi::{c_void, CString};
+use std::os::raw::c_int;
+use std::ptr;
+use std::sync::Arc;
+
+use pyo3::exceptions::PyBufferError;
+use pyo3::prelude::*;
+use pyo3::{ffi, AsPyPointer};
+
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::SweepType;
+use crate::model::volume::Volume;
+
+// Which flat buffer of a product's SweepData is exposed
+#[derive(Clone, Copy)]
+pub(crate) enum SweepBufferKind {
+    // Gate values as float32, masked gates hold stale values
+    Values,
+    // Gate mask as bool, true where the gate has no value
+    Mask,
+}
+
+impl SweepBufferKind {
+    fn format(self) -> &'static str {
+        match self {
+            SweepBufferKind::Values => "f",
+            SweepBufferKind::Mask => "?",
+        }
+    }
+
+    fn itemsize(self) -> isize {
+        match self {
+            SweepBufferKind::Values => std::mem::size_of::<f32>() as isize,
+            SweepBufferKind::Mask => std::mem::size_of::<bool>() as isize,
+        }
+    }
+}
+
+// Read-only radials x gates view of one flat buffer of a converted sweep,
+// exposed through the buffer protocol so memoryview and numpy.asarray use
+// the volume's storage without copying. The view keeps the volume alive.
+#[pyclass]
+pub struct PySweepBuffer {
+    volume: Arc<Volume>,
+    sweep: usize,
+    product: SweepType,
+    kind: SweepBufferKind,
+    shape: [isize; 2],
+    strides: [isize; 2],
+}
+
+impl PySweepBuffer {
+    pub(crate) fn new(
+        volume: Arc<Volume>,
+        sweep: usize,
+        product: SweepType,
+        kind: SweepBufferKind,
+    ) -> Self {
+        let data = volume.sweeps[sweep]
+            .product(product)
+            .unwrap_or_else(|| panic!("Unexpected product {}", product));
+
+        let itemsize = kind.itemsize();
+        let shape = [data.radials as isize, data.gates as isize];
+        let strides = [data.gates as isize * itemsize, itemsize];
+
+        Self {
+            volume,
+            sweep,
+            product,
+            kind,
+            shape,
+            strides,
+        }
+    }
+
+    fn data(&self) -> &SweepData {
+        self.volume.sweeps[self.sweep]
+            .product(self.product)
+            .unwrap()
+    }
+
+    fn buffer(&self) -> *const c_void {
+        match self.kind {
+            SweepBufferKind::Values => self.data().as_slice().as_ptr() as *const c_void,
+            SweepBufferKind::Mask => self.data().mask_slice().as_ptr() as *const c_void,
+        }
+    }
+}
+
+#[pymethods]
+impl PySweepBuffer {
+    // (radials, gates) of the buffer
+    #[getter]
+    fn shape(&self) -> (usize, usize) {
+        (self.shape[0] as usize, self.shape[1] as usize)
+    }
+
+    // Range in km of the first gate and between gates, which locate the
+    // columns of the buffer. Unlike PySweep.range_first these are not
+    // trimmed to the gates with data.
+    #[getter]
+    fn range_first(&self) -> f32 {
+        self.volume.sweeps[self.sweep].range_first
+    }
+
+    #[getter]
+    fn range_step(&self) -> f32 {
+        self.volume.sweeps[self.sweep].range_step
+    }
+
+    unsafe fn __getbuffer__(
+        slf: &PyCell<Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+
+        if (flags & ffi::PyBUF_WRITABLE) == ffi::PyBUF_WRITABLE {
+            return Err(PyBufferError::new_err("Sweep buffers are read-only"));
+        }
+
+        // The shape and strides live in the object, which the view keeps
+        // alive until it is released
+        let buffer = slf.borrow();
+        (*view).obj = ffi::_Py_NewRef(slf.as_ptr());
+
+        (*view).buf = buffer.buffer() as *mut c_void;
+        (*view).len = buffer.shape[0] * buffer.strides[0];
+        (*view).readonly = 1;
+        (*view).itemsize = buffer.kind.itemsize();
+
+        (*view).format = if (flags & ffi::PyBUF_FORMAT) == ffi::PyBUF_FORMAT {
+            CString::new(buffer.kind.format()).unwrap().into_raw()
+        } else {
+            ptr::null_mut()
+        };
+
+        // Without PyBUF_ND the consumer sees the buffer as flat bytes
+        if (flags & ffi::PyBUF_ND) == ffi::PyBUF_ND {
+            (*view).ndim = 2;
+            (*view).shape = buffer.shape.as_ptr() as *mut isize;
+        } else {
+            (*view).ndim = 1;
+            (*view).shape = ptr::null_mut();
+        }
+
+        (*view).strides = if (flags & ffi::PyBUF_STRIDES) == ffi::PyBUF_STRIDES {
+            buffer.strides.as_ptr() as *mut isize
+        } else {
+            ptr::null_mut()
+        };
+
+        (*view).suboffsets = ptr::null_mut();
+        (*view).internal = ptr::null_mut();
+
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(&self, view: *mut ffi::Py_buffer) {
+        // Release the format string, when one was requested
+        if !(*view).format.is_null() {
+            drop(CString::from_raw((*view).format));
+        }
+    }
+}
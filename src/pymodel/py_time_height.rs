@@ -0,0 +1,31 @@
+use pyo3::prelude::*;
+
+use crate::products::qvp::TimeHeight;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyTimeHeight {
+    // Time of each profile, in increasing order
+    #[pyo3(get)]
+    pub times: Vec<i64>,
+    // Height of the center of each bin above mean sea level, in km
+    #[pyo3(get)]
+    pub heights: Vec<f32>,
+    // Row-major with one row of heights per time, NaN where missing
+    #[pyo3(get)]
+    pub values: Vec<f32>,
+}
+
+impl PyTimeHeight {
+    pub(crate) fn new(time_height: TimeHeight) -> Self {
+        Self {
+            times: time_height
+                .times
+                .iter()
+                .map(|time| time.timestamp())
+                .collect(),
+            heights: time_height.heights,
+            values: time_height.values,
+        }
+    }
+}
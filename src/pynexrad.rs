@@ -1,28 +1,119 @@
 use pyo3::prelude::*;
 
-use crate::bindings::convert_chunks::convert_chunks;
-use crate::bindings::download_chunk::download_chunk;
-use crate::bindings::download_nexrad_file::download_nexrad_file;
-use crate::bindings::get_latest_volume::get_latest_volume;
-use crate::bindings::list_chunks_in_volume::list_chunks_in_volume;
-use crate::bindings::list_records::list_records;
+use crate::bindings::convert_chunks::{convert_chunks, convert_chunks_async};
+use crate::bindings::download_chunk::{download_chunk, download_chunk_async};
+use crate::bindings::download_nexrad_file::{download_nexrad_file, download_nexrad_file_async};
+use crate::bindings::find_volume_at::{find_volume_at, find_volume_at_async};
+use crate::bindings::get_latest_volume::{get_latest_volume, get_latest_volume_async};
+use crate::bindings::list_chunks_in_volume::{list_chunks_in_volume, list_chunks_in_volume_async};
+use crate::bindings::list_records::{list_records, list_records_async};
+use crate::bindings::read_nexrad_file::{read_nexrad_file, read_nexrad_file_async};
+use crate::bindings::stack_qvps::stack_qvps;
+use crate::bindings::thread_pool::{get_thread_count, set_thread_count};
+use crate::bindings::write_archive::{chunks_to_archive, write_archive, write_archive_async};
+use crate::pymodel::py_azimuthal_shear_options::PyAzimuthalShearOptions;
+use crate::pymodel::py_band_options::PyBandOptions;
+use crate::pymodel::py_biological_filter_options::PyBiologicalFilterOptions;
 use crate::pymodel::py_chunk::PyChunk;
 use crate::pymodel::py_chunk_identifier::PyChunkIdentifier;
+use crate::pymodel::py_clutter_filter_options::PyClutterFilterOptions;
+use crate::pymodel::py_colormap::PyColormap;
+use crate::pymodel::py_contour_options::PyContourOptions;
+use crate::pymodel::py_contours::PyContours;
+use crate::pymodel::py_convert_options::PyConvertOptions;
+use crate::pymodel::py_geotiff_options::PyGeoTiffOptions;
+use crate::pymodel::py_hail::{PyHail, PyHailGrid};
+use crate::pymodel::py_hail_options::PyHailOptions;
+use crate::pymodel::py_image::PyImage;
+use crate::pymodel::py_kdp_options::PyKdpOptions;
 use crate::pymodel::py_level2_file::PyLevel2File;
+use crate::pymodel::py_melting_layer::PyMeltingLayer;
+use crate::pymodel::py_melting_layer_options::PyMeltingLayerOptions;
+use crate::pymodel::py_mesh_accumulator::PyMeshAccumulator;
+use crate::pymodel::py_qpe_options::PyQpeOptions;
+use crate::pymodel::py_qvp::PyQvp;
+use crate::pymodel::py_qvp_options::PyQvpOptions;
+use crate::pymodel::py_rain_accumulator::PyRainAccumulator;
+use crate::pymodel::py_rain_rate_grid::PyRainRateGrid;
+use crate::pymodel::py_raster::PyRaster;
+use crate::pymodel::py_render_options::PyRenderOptions;
+use crate::pymodel::py_rotation::PyRotation;
+use crate::pymodel::py_rotation_options::PyRotationOptions;
+use crate::pymodel::py_shear_layers::PyShearLayers;
+use crate::pymodel::py_storm_cell::{PyStormCell, PyStormCells};
+use crate::pymodel::py_storm_cell_options::PyStormCellOptions;
+use crate::pymodel::py_storm_relative_options::PyStormRelativeOptions;
+use crate::pymodel::py_storm_tracker::{PyStormTracker, PyTrackedCell};
 use crate::pymodel::py_sweep::PySweep;
+use crate::pymodel::py_sweep_buffer::PySweepBuffer;
+use crate::pymodel::py_time_height::PyTimeHeight;
 
 #[pymodule]
 fn pynexrad(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(list_records, m)?)?;
     m.add_function(wrap_pyfunction!(download_nexrad_file, m)?)?;
+    m.add_function(wrap_pyfunction!(find_volume_at, m)?)?;
+    m.add_function(wrap_pyfunction!(read_nexrad_file, m)?)?;
+
+    m.add_function(wrap_pyfunction!(list_records_async, m)?)?;
+    m.add_function(wrap_pyfunction!(download_nexrad_file_async, m)?)?;
+    m.add_function(wrap_pyfunction!(find_volume_at_async, m)?)?;
+    m.add_function(wrap_pyfunction!(read_nexrad_file_async, m)?)?;
 
     m.add_class::<PyLevel2File>()?;
     m.add_class::<PySweep>()?;
+    m.add_class::<PySweepBuffer>()?;
+    m.add_class::<PyConvertOptions>()?;
+    m.add_class::<PyClutterFilterOptions>()?;
+    m.add_class::<PyBiologicalFilterOptions>()?;
+    m.add_class::<PyKdpOptions>()?;
+    m.add_class::<PyMeltingLayerOptions>()?;
+    m.add_class::<PyQpeOptions>()?;
+    m.add_class::<PyRainRateGrid>()?;
+    m.add_class::<PyRainAccumulator>()?;
+    m.add_class::<PyMeltingLayer>()?;
+    m.add_class::<PyQvpOptions>()?;
+    m.add_class::<PyQvp>()?;
+    m.add_class::<PyTimeHeight>()?;
+    m.add_function(wrap_pyfunction!(stack_qvps, m)?)?;
+    m.add_class::<PyStormCellOptions>()?;
+    m.add_class::<PyStormCell>()?;
+    m.add_class::<PyStormCells>()?;
+    m.add_class::<PyStormTracker>()?;
+    m.add_class::<PyTrackedCell>()?;
+    m.add_class::<PyRotationOptions>()?;
+    m.add_class::<PyRotation>()?;
+    m.add_class::<PyAzimuthalShearOptions>()?;
+    m.add_class::<PyShearLayers>()?;
+    m.add_class::<PyStormRelativeOptions>()?;
+    m.add_class::<PyHailOptions>()?;
+    m.add_class::<PyHail>()?;
+    m.add_class::<PyHailGrid>()?;
+    m.add_class::<PyMeshAccumulator>()?;
+    m.add_class::<PyColormap>()?;
+    m.add_class::<PyRenderOptions>()?;
+    m.add_class::<PyImage>()?;
+    m.add_class::<PyBandOptions>()?;
+    m.add_class::<PyGeoTiffOptions>()?;
+    m.add_class::<PyRaster>()?;
+    m.add_class::<PyContourOptions>()?;
+    m.add_class::<PyContours>()?;
+
+    m.add_function(wrap_pyfunction!(set_thread_count, m)?)?;
+    m.add_function(wrap_pyfunction!(get_thread_count, m)?)?;
 
     m.add_function(wrap_pyfunction!(get_latest_volume, m)?)?;
     m.add_function(wrap_pyfunction!(list_chunks_in_volume, m)?)?;
     m.add_function(wrap_pyfunction!(download_chunk, m)?)?;
     m.add_function(wrap_pyfunction!(convert_chunks, m)?)?;
+    m.add_function(wrap_pyfunction!(chunks_to_archive, m)?)?;
+    m.add_function(wrap_pyfunction!(write_archive, m)?)?;
+
+    m.add_function(wrap_pyfunction!(get_latest_volume_async, m)?)?;
+    m.add_function(wrap_pyfunction!(list_chunks_in_volume_async, m)?)?;
+    m.add_function(wrap_pyfunction!(download_chunk_async, m)?)?;
+    m.add_function(wrap_pyfunction!(convert_chunks_async, m)?)?;
+    m.add_function(wrap_pyfunction!(write_archive_async, m)?)?;
 
     m.add_class::<PyChunkIdentifier>()?;
     m.add_class::<PyChunk>()?;
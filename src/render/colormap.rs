@@ -0,0 +1,217 @@
+// Colormaps from product values to RGBA colors. A colormap is a list of
+// stops in increasing order of value. Values below the first stop are
+// transparent. Stepped colormaps give each value the color of the last
+// stop at or below it, while interpolated colormaps blend between stops.
+
+use crate::model::sweep_type::*;
+
+pub type Rgba = [u8; 4];
+
+pub const TRANSPARENT: Rgba = [0, 0, 0, 0];
+
+// Shown for range folded gates, as on NWS displays
+pub const RANGE_FOLDED: Rgba = [119, 0, 125, 255];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Colormap {
+    pub stops: Vec<(f32, Rgba)>,
+    pub interpolate: bool,
+}
+
+impl Colormap {
+    pub fn new(stops: Vec<(f32, Rgba)>, interpolate: bool) -> Result<Self, String> {
+        if stops.is_empty() {
+            return Err("A colormap needs at least one stop".to_string());
+        }
+
+        if stops.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err("Colormap stops must be in increasing order of value".to_string());
+        }
+
+        Ok(Self { stops, interpolate })
+    }
+
+    // Parses a colormap with one stop per line as a value followed by red,
+    // green, blue and optionally alpha from 0 to 255, separated by spaces
+    // or commas. Blank lines and lines starting with # are skipped.
+    pub fn parse(text: &str, interpolate: bool) -> Result<Self, String> {
+        let mut stops = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|field| !field.is_empty())
+                .collect();
+            if fields.len() != 4 && fields.len() != 5 {
+                return Err(format!(
+                    "Line {}: expected a value and 3 or 4 color components",
+                    number + 1
+                ));
+            }
+
+            let value = fields[0]
+                .parse::<f32>()
+                .map_err(|_| format!("Line {}: invalid value {}", number + 1, fields[0]))?;
+
+            let mut color = [0, 0, 0, 255];
+            for (component, field) in color.iter_mut().zip(fields[1..].iter()) {
+                *component = field.parse::<u8>().map_err(|_| {
+                    format!("Line {}: invalid color component {}", number + 1, field)
+                })?;
+            }
+
+            stops.push((value, color));
+        }
+
+        Self::new(stops, interpolate)
+    }
+
+    pub fn color(&self, value: f32) -> Rgba {
+        let index = self.stops.partition_point(|(stop, _)| *stop <= value);
+        if index == 0 || value.is_nan() {
+            return TRANSPARENT;
+        }
+
+        let (lower_value, lower) = self.stops[index - 1];
+        if !self.interpolate || index == self.stops.len() {
+            return lower;
+        }
+
+        let (upper_value, upper) = self.stops[index];
+        let fraction = (value - lower_value) / (upper_value - lower_value);
+        let mut color = [0; 4];
+        for (component, (lower, upper)) in color.iter_mut().zip(lower.iter().zip(upper.iter())) {
+            *component = (*lower as f32 + fraction * (*upper as f32 - *lower as f32)).round() as u8;
+        }
+        color
+    }
+
+    // NWS style colormap of a product
+    pub fn builtin(product: SweepType) -> Self {
+        let (stops, interpolate): (&[(f32, Rgba)], bool) = match product {
+            REFLECTIVITY => (REFLECTIVITY_STOPS, false),
+            VELOCITY | STORM_RELATIVE_VELOCITY => (VELOCITY_STOPS, true),
+            CORRELATION_COEFFICIENT => (CORRELATION_STOPS, true),
+            DIFFERENTIAL_REFLECTIVITY => (DIFFERENTIAL_REFLECTIVITY_STOPS, true),
+            HYDROMETEOR_CLASS => (HYDROMETEOR_CLASS_STOPS, false),
+            RAIN_RATE => (RAIN_RATE_STOPS, false),
+            _ => return Self::ramp(product_range(product)),
+        };
+
+        Self {
+            stops: stops.to_vec(),
+            interpolate,
+        }
+    }
+
+    // Blue to yellow ramp over a range of values
+    fn ramp((min, max): (f32, f32)) -> Self {
+        let stops = RAMP_COLORS
+            .iter()
+            .enumerate()
+            .map(|(index, color)| {
+                let fraction = index as f32 / (RAMP_COLORS.len() - 1) as f32;
+                (min + fraction * (max - min), *color)
+            })
+            .collect();
+
+        Self {
+            stops,
+            interpolate: true,
+        }
+    }
+}
+
+const REFLECTIVITY_STOPS: &[(f32, Rgba)] = &[
+    (5.0, [4, 233, 231, 255]),
+    (10.0, [1, 159, 244, 255]),
+    (15.0, [3, 0, 244, 255]),
+    (20.0, [2, 253, 2, 255]),
+    (25.0, [1, 197, 1, 255]),
+    (30.0, [0, 142, 0, 255]),
+    (35.0, [253, 248, 2, 255]),
+    (40.0, [229, 188, 0, 255]),
+    (45.0, [253, 149, 0, 255]),
+    (50.0, [253, 0, 0, 255]),
+    (55.0, [212, 0, 0, 255]),
+    (60.0, [188, 0, 0, 255]),
+    (65.0, [248, 0, 253, 255]),
+    (70.0, [152, 84, 198, 255]),
+    (75.0, [253, 253, 253, 255]),
+];
+
+// Inbound velocities are green and outbound velocities are red
+const VELOCITY_STOPS: &[(f32, Rgba)] = &[
+    (-100.0, [2, 252, 2, 255]),
+    (-30.0, [1, 228, 1, 255]),
+    (-15.0, [0, 160, 0, 255]),
+    (-1.0, [78, 121, 76, 255]),
+    (0.0, [137, 137, 137, 255]),
+    (1.0, [121, 76, 76, 255]),
+    (15.0, [160, 0, 0, 255]),
+    (30.0, [228, 1, 1, 255]),
+    (100.0, [252, 2, 2, 255]),
+];
+
+const CORRELATION_STOPS: &[(f32, Rgba)] = &[
+    (0.2, [0, 0, 80, 255]),
+    (0.45, [0, 0, 200, 255]),
+    (0.65, [0, 120, 255, 255]),
+    (0.8, [0, 200, 0, 255]),
+    (0.9, [240, 240, 0, 255]),
+    (0.95, [255, 140, 0, 255]),
+    (0.98, [220, 0, 0, 255]),
+    (1.0, [140, 0, 0, 255]),
+    (1.05, [255, 180, 220, 255]),
+];
+
+const DIFFERENTIAL_REFLECTIVITY_STOPS: &[(f32, Rgba)] = &[
+    (-8.0, [40, 40, 40, 255]),
+    (-2.0, [150, 150, 150, 255]),
+    (0.0, [220, 220, 220, 255]),
+    (0.5, [0, 0, 200, 255]),
+    (1.0, [0, 180, 255, 255]),
+    (2.0, [0, 200, 0, 255]),
+    (3.0, [240, 240, 0, 255]),
+    (4.0, [255, 140, 0, 255]),
+    (5.0, [220, 0, 0, 255]),
+    (8.0, [255, 180, 220, 255]),
+];
+
+// One color per class code, in the order of model::hydro_class
+const HYDROMETEOR_CLASS_STOPS: &[(f32, Rgba)] = &[
+    (0.0, TRANSPARENT),
+    (1.0, [0, 200, 0, 255]),
+    (2.0, [0, 120, 0, 255]),
+    (3.0, [220, 0, 0, 255]),
+    (4.0, [255, 150, 150, 255]),
+    (5.0, [120, 170, 255, 255]),
+    (6.0, [0, 80, 220, 255]),
+    (7.0, [200, 200, 255, 255]),
+    (8.0, [200, 120, 40, 255]),
+    (9.0, [120, 120, 120, 255]),
+];
+
+const RAIN_RATE_STOPS: &[(f32, Rgba)] = &[
+    (0.1, [180, 240, 250, 255]),
+    (1.0, [75, 180, 240, 255]),
+    (2.5, [20, 100, 210, 255]),
+    (5.0, [0, 200, 0, 255]),
+    (10.0, [250, 240, 0, 255]),
+    (25.0, [250, 150, 0, 255]),
+    (50.0, [230, 0, 0, 255]),
+    (100.0, [200, 0, 200, 255]),
+];
+
+const RAMP_COLORS: &[Rgba] = &[
+    [68, 1, 84, 255],
+    [59, 82, 139, 255],
+    [33, 145, 140, 255],
+    [94, 201, 98, 255],
+    [253, 231, 37, 255],
+];
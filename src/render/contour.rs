@@ -0,0 +1,535 @@
+// Contours values into polygons with holes by tracing the boundaries of the
+// cells which pass a threshold, and writes them as GeoJSON. Cells are the
+// gates of a sweep, the cells of a grid around the radar or the pixels of a
+// latitude/longitude grid, so polygons follow the cell geometry rather than
+// an interpolated surface.
+
+use std::collections::{BTreeMap, HashSet};
+use std::f32::consts::PI;
+use std::fmt::Write;
+
+use crate::model::geometry::{ground_range, EARTH_RADIUS};
+use crate::model::grid::Grid;
+use crate::model::site::Site;
+use crate::model::sweep::{angular_distance, Sweep};
+use crate::render::raster::LatLonGrid;
+
+const KM_PER_DEGREE: f32 = EARTH_RADIUS * PI / 180.0;
+
+// Degrees of longitude and latitude
+pub type Point = (f32, f32);
+
+// Rows and columns moved along an edge between cell corners
+type Direction = (i8, i8);
+type Corner = (usize, usize);
+type Edge = (Corner, Direction);
+
+// A polygon with closed rings, the exterior counterclockwise and the holes
+// clockwise as in RFC 7946
+#[derive(Clone, Debug)]
+pub struct Polygon {
+    pub exterior: Vec<Point>,
+    pub holes: Vec<Vec<Point>>,
+}
+
+// Cells which values are contoured on, stored row-major
+pub(crate) enum Cells<'a> {
+    // Gates of a sweep, with a row per radial and a column per gate
+    Sweep {
+        sweep: &'a Sweep,
+        site: &'a Site,
+        gates: usize,
+    },
+    // Cells of a grid centered on the radar
+    Grid {
+        grid: &'a Grid,
+        site: &'a Site,
+    },
+    // Pixels of a latitude/longitude grid
+    LatLon(&'a LatLonGrid),
+}
+
+impl Cells<'_> {
+    fn rows(&self) -> usize {
+        match self {
+            Cells::Sweep { sweep, .. } => sweep.azimuths.len(),
+            Cells::Grid { grid, .. } => grid.size,
+            Cells::LatLon(grid) => grid.height,
+        }
+    }
+
+    fn cols(&self) -> usize {
+        match self {
+            Cells::Sweep { gates, .. } => *gates,
+            Cells::Grid { grid, .. } => grid.size,
+            Cells::LatLon(grid) => grid.width,
+        }
+    }
+
+    // Whether the last row neighbors the first, as in a complete sweep
+    fn wraps(&self) -> bool {
+        match self {
+            Cells::Sweep { sweep, .. } => {
+                let azimuths = &sweep.azimuths;
+                azimuths.len() > 2
+                    && angular_distance(azimuths[azimuths.len() - 1], azimuths[0])
+                        < 1.5 * sweep.az_step
+            }
+            Cells::Grid { .. } | Cells::LatLon(_) => false,
+        }
+    }
+
+    // Whether edges in a direction are straight lines. Edges between gates
+    // along a radial are, but edges across radials are arcs.
+    fn is_straight(&self, direction: Direction) -> bool {
+        match self {
+            Cells::Sweep { .. } => direction.0 == 0,
+            Cells::Grid { .. } | Cells::LatLon(_) => true,
+        }
+    }
+
+    // Longitude and latitude of the top left corner of a cell, where row
+    // and col may be one past the last cell
+    fn corner(&self, (row, col): Corner) -> Point {
+        match self {
+            Cells::Sweep { sweep, site, .. } => {
+                let half_step = sweep.az_step / 2.0;
+                let azimuth = match sweep.azimuths.get(row) {
+                    Some(azimuth) => azimuth - half_step,
+                    None => sweep.azimuths[row - 1] + half_step,
+                };
+                let range = f32::max(
+                    sweep.range_first + (col as f32 - 0.5) * sweep.range_step,
+                    0.0,
+                );
+                let distance = ground_range(range, sweep.elevation);
+
+                let (latitude, longitude) =
+                    site.offset(distance * azimuth.sin(), distance * azimuth.cos());
+                (longitude, latitude)
+            }
+            Cells::Grid { grid, site } => {
+                // The corner is half a cell west and north of the center
+                let (x, y) = grid.cell_center(row, col);
+                let half_spacing = grid.spacing / 2.0;
+
+                let (latitude, longitude) = site.offset(x - half_spacing, y + half_spacing);
+                (longitude, latitude)
+            }
+            Cells::LatLon(grid) => {
+                let bounds = &grid.bounds;
+                (
+                    bounds.west + col as f32 * (bounds.east - bounds.west) / grid.width as f32,
+                    bounds.north - row as f32 * (bounds.north - bounds.south) / grid.height as f32,
+                )
+            }
+        }
+    }
+
+    fn end(&self, ((row, col), (rows, cols)): Edge) -> Corner {
+        let row = row as isize + rows as isize;
+        let row = if self.wraps() {
+            row.rem_euclid(self.rows() as isize)
+        } else {
+            row
+        };
+
+        (row as usize, (col as isize + cols as isize) as usize)
+    }
+}
+
+// Polygons enclosing the cells with values at or above a threshold, or at
+// or below it, never enclosing NaN. Rings are simplified to within the
+// tolerance in km when it is positive.
+pub(crate) fn contour(
+    cells: &Cells,
+    values: &[f32],
+    threshold: f32,
+    below: bool,
+    tolerance: f32,
+) -> Vec<Polygon> {
+    let rows = cells.rows();
+    let cols = cells.cols();
+    let wraps = cells.wraps();
+    debug_assert_eq!(values.len(), rows * cols);
+
+    let inside = |row: usize, col: usize| {
+        let value = values[row * cols + col];
+        if below {
+            value <= threshold
+        } else {
+            value >= threshold
+        }
+    };
+
+    // Edges between enclosed and other cells, running clockwise around the
+    // enclosed cells. Corners past the last row are the first row's when
+    // the rows wrap.
+    let mut edges: BTreeMap<Corner, Vec<Direction>> = BTreeMap::new();
+    let mut add = |corner: Corner, direction: Direction| {
+        let corner = if wraps && corner.0 == rows {
+            (0, corner.1)
+        } else {
+            corner
+        };
+        edges.entry(corner).or_default().push(direction);
+    };
+
+    for row in 0..rows {
+        for col in 0..cols {
+            if !inside(row, col) {
+                continue;
+            }
+
+            let up = if row > 0 {
+                inside(row - 1, col)
+            } else {
+                wraps && inside(rows - 1, col)
+            };
+            let down = if row + 1 < rows {
+                inside(row + 1, col)
+            } else {
+                wraps && inside(0, col)
+            };
+            let left = col > 0 && inside(row, col - 1);
+            let right = col + 1 < cols && inside(row, col + 1);
+
+            if !up {
+                add((row, col), (0, 1));
+            }
+            if !right {
+                add((row, col + 1), (1, 0));
+            }
+            if !down {
+                add((row + 1, col + 1), (0, -1));
+            }
+            if !left {
+                add((row + 1, col), (-1, 0));
+            }
+        }
+    }
+
+    let mut exteriors: Vec<(Polygon, f32)> = Vec::new();
+    let mut holes: Vec<(Vec<Point>, Point)> = Vec::new();
+
+    let mut used: HashSet<Edge> = HashSet::new();
+    for (corner, directions) in &edges {
+        for direction in directions {
+            let start = (*corner, *direction);
+            if used.contains(&start) {
+                continue;
+            }
+
+            let ring = trace_ring(cells, &edges, start);
+            used.extend(ring.iter().copied());
+
+            // A point on the ring, which is on no other ring as rings do not
+            // share edges
+            let (a, b) = (cells.corner(start.0), cells.corner(cells.end(start)));
+            let probe = ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+
+            // Exteriors run clockwise like the edges around each cell, and
+            // holes run counterclockwise
+            let points = ring_points(cells, &ring);
+            let area = signed_area(&points);
+            if area < 0.0 {
+                let exterior = Polygon {
+                    exterior: points,
+                    holes: Vec::new(),
+                };
+                exteriors.push((exterior, -area));
+            } else {
+                holes.push((points, probe));
+            }
+        }
+    }
+
+    // Each hole belongs to the smallest exterior around it
+    for (hole, probe) in holes {
+        let parent = exteriors
+            .iter_mut()
+            .filter(|(polygon, _)| contains(&polygon.exterior, probe))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((polygon, _)) = parent {
+            polygon.holes.push(hole);
+        }
+    }
+
+    exteriors
+        .into_iter()
+        .map(|(polygon, _)| {
+            let finish = |ring: Vec<Point>| {
+                let mut ring = if tolerance > 0.0 {
+                    simplify(&ring, tolerance)
+                } else {
+                    ring
+                };
+                ring.reverse();
+                ring
+            };
+
+            Polygon {
+                exterior: finish(polygon.exterior),
+                holes: polygon.holes.into_iter().map(finish).collect(),
+            }
+        })
+        .collect()
+}
+
+// Follows edges from a starting edge until it is reached again. Where two
+// rings touch at a corner, turning the same way as around a cell keeps the
+// rings apart.
+fn trace_ring(cells: &Cells, edges: &BTreeMap<Corner, Vec<Direction>>, start: Edge) -> Vec<Edge> {
+    let mut ring = Vec::new();
+    let mut edge = start;
+
+    loop {
+        ring.push(edge);
+
+        let corner = cells.end(edge);
+        let outgoing = &edges[&corner];
+        let (rows, cols) = edge.1;
+        let direction = if outgoing.len() == 1 {
+            outgoing[0]
+        } else {
+            [(cols, -rows), (rows, cols), (-cols, rows)]
+                .into_iter()
+                .find(|direction| outgoing.contains(direction))
+                .expect("Boundary edges form closed rings")
+        };
+
+        edge = (corner, direction);
+        if edge == start {
+            return ring;
+        }
+    }
+}
+
+// Closed ring of the corners of edges, skipping corners along straight
+// lines
+fn ring_points(cells: &Cells, ring: &[Edge]) -> Vec<Point> {
+    let mut points = Vec::with_capacity(ring.len() + 1);
+
+    for (index, (corner, direction)) in ring.iter().enumerate() {
+        let (_, previous) = ring[(index + ring.len() - 1) % ring.len()];
+        if previous == *direction && cells.is_straight(*direction) {
+            continue;
+        }
+
+        points.push(cells.corner(*corner));
+    }
+    points.push(points[0]);
+
+    points
+}
+
+// Twice the area of a closed ring, positive when it is counterclockwise
+fn signed_area(ring: &[Point]) -> f32 {
+    ring.windows(2)
+        .map(|pair| pair[0].0 * pair[1].1 - pair[1].0 * pair[0].1)
+        .sum()
+}
+
+// Whether a point is inside a closed ring, by counting crossings of a ray
+// to the east
+fn contains(ring: &[Point], (x, y): Point) -> bool {
+    let mut inside = false;
+
+    for pair in ring.windows(2) {
+        let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+        if (y1 > y) != (y2 > y) && x < x1 + (y - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+// Douglas-Peucker simplification of a closed ring with a tolerance in km.
+// Rings which would collapse are kept as they are.
+fn simplify(ring: &[Point], tolerance: f32) -> Vec<Point> {
+    let scale = ring[0].1.to_radians().cos();
+    let projected: Vec<(f32, f32)> = ring
+        .iter()
+        .map(|(longitude, latitude)| (longitude * scale * KM_PER_DEGREE, latitude * KM_PER_DEGREE))
+        .collect();
+
+    // The ring is split at the corner furthest from the first, since the
+    // first and last are the same
+    let last = ring.len() - 1;
+    let Some(furthest) = (1..last).max_by(|a, b| {
+        distance(projected[0], projected[*a]).total_cmp(&distance(projected[0], projected[*b]))
+    }) else {
+        return ring.to_vec();
+    };
+
+    let mut keep = vec![false; ring.len()];
+    keep[0] = true;
+    keep[furthest] = true;
+    keep[last] = true;
+
+    let mut segments = vec![(0, furthest), (furthest, last)];
+    while let Some((first, last)) = segments.pop() {
+        let Some((index, offset)) = (first + 1..last)
+            .map(|index| {
+                let offset = segment_distance(projected[index], projected[first], projected[last]);
+                (index, offset)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+        else {
+            continue;
+        };
+
+        if offset > tolerance {
+            keep[index] = true;
+            segments.push((first, index));
+            segments.push((index, last));
+        }
+    }
+
+    let simplified: Vec<Point> = ring
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(point, _)| *point)
+        .collect();
+
+    if simplified.len() < 4 {
+        ring.to_vec()
+    } else {
+        simplified
+    }
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+// Distance from a point to the segment between a and b
+fn segment_distance(point: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    if length == 0.0 {
+        return distance(point, a);
+    }
+
+    let t = (((point.0 - a.0) * dx + (point.1 - a.1) * dy) / length).clamp(0.0, 1.0);
+    distance(point, (a.0 + t * dx, a.1 + t * dy))
+}
+
+// GeoJSON feature collection with a MultiPolygon feature of the polygons of
+// each threshold
+pub fn to_geojson(product: &str, contours: &[(f32, Vec<Polygon>)]) -> String {
+    let mut json = String::from(r#"{"type":"FeatureCollection","features":["#);
+
+    for (index, (threshold, polygons)) in contours.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+
+        write!(
+            json,
+            r#"{{"type":"Feature","properties":{{"product":"{}","threshold":{}}},"geometry":{{"type":"MultiPolygon","coordinates":["#,
+            product, threshold
+        )
+        .unwrap();
+
+        for (index, polygon) in polygons.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+
+            json.push('[');
+            for (index, ring) in std::iter::once(&polygon.exterior)
+                .chain(&polygon.holes)
+                .enumerate()
+            {
+                if index > 0 {
+                    json.push(',');
+                }
+
+                json.push('[');
+                for (index, (longitude, latitude)) in ring.iter().enumerate() {
+                    if index > 0 {
+                        json.push(',');
+                    }
+                    write!(json, "[{:.5},{:.5}]", longitude, latitude).unwrap();
+                }
+                json.push(']');
+            }
+            json.push(']');
+        }
+
+        json.push_str("]}}");
+    }
+
+    json.push_str("]}");
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Values of a grid given as strings, where '#' is 1 and any other
+    // character is 0
+    fn grid_values(rows: &[&str]) -> Vec<f32> {
+        rows.iter()
+            .flat_map(|row| row.bytes())
+            .map(|cell| if cell == b'#' { 1.0 } else { 0.0 })
+            .collect()
+    }
+
+    #[test]
+    fn rings_wind_as_in_rfc_7946() {
+        let grid = Grid::new(7, 1.0);
+        let site = Site {
+            latitude: 41.73,
+            longitude: -93.72,
+            height: 0.3,
+        };
+        let values = grid_values(&[
+            ".......", //
+            ".#####.", //
+            ".#...#.", //
+            ".#.#.#.", //
+            ".#...#.", //
+            ".#####.", //
+            ".......", //
+        ]);
+
+        let mut polygons = contour(
+            &Cells::Grid {
+                grid: &grid,
+                site: &site,
+            },
+            &values,
+            0.5,
+            false,
+            0.0,
+        );
+        polygons.sort_by_key(|polygon| polygon.holes.len());
+
+        // The island in the hole is a polygon of its own
+        assert_eq!(polygons.len(), 2);
+        let (island, ring) = (&polygons[0], &polygons[1]);
+        assert!(island.holes.is_empty());
+        assert_eq!(ring.holes.len(), 1);
+
+        // Squares keep only their corners
+        assert_eq!(island.exterior.len(), 5);
+        assert_eq!(ring.exterior.len(), 5);
+        assert_eq!(ring.holes[0].len(), 5);
+
+        // Exteriors are counterclockwise and holes clockwise
+        assert!(signed_area(&island.exterior) > 0.0);
+        assert!(signed_area(&ring.exterior) > 0.0);
+        assert!(signed_area(&ring.holes[0]) < 0.0);
+
+        // The hole is inside the exterior and around the island
+        assert!(contains(&ring.exterior, ring.holes[0][0]));
+        assert!(contains(&ring.holes[0], island.exterior[0]));
+        assert!(!contains(&island.exterior, ring.holes[0][0]));
+    }
+}
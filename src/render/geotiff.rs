@@ -0,0 +1,221 @@
+// Writes float rasters as GeoTIFF without GDAL. Files are little endian
+// baseline TIFFs with one uncompressed strip of interleaved 32 bit float
+// samples, georeferenced to WGS 84 latitude and longitude by GeoTIFF keys.
+
+use crate::render::image::Bounds;
+
+// TIFF field types
+const ASCII: u16 = 2;
+const SHORT: u16 = 3;
+const LONG: u16 = 4;
+const DOUBLE: u16 = 12;
+
+// GeoKeyDirectory version, key revision and minor revision
+const GEO_KEY_HEADER: [u16; 3] = [1, 1, 0];
+// GTModelTypeGeoKey with ModelTypeGeographic
+const MODEL_TYPE: [u16; 4] = [1024, 0, 1, 2];
+// GTRasterTypeGeoKey with RasterPixelIsArea
+const RASTER_TYPE: [u16; 4] = [1025, 0, 1, 1];
+// GeographicTypeGeoKey with EPSG:4326
+const GEOGRAPHIC_TYPE: [u16; 4] = [2048, 0, 1, 4326];
+
+struct Entry {
+    tag: u16,
+    field_type: u16,
+    count: u32,
+    data: Vec<u8>,
+}
+
+impl Entry {
+    fn shorts(tag: u16, values: &[u16]) -> Self {
+        Self {
+            tag,
+            field_type: SHORT,
+            count: values.len() as u32,
+            data: values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect(),
+        }
+    }
+
+    fn long(tag: u16, value: u32) -> Self {
+        Self {
+            tag,
+            field_type: LONG,
+            count: 1,
+            data: value.to_le_bytes().to_vec(),
+        }
+    }
+
+    fn doubles(tag: u16, values: &[f64]) -> Self {
+        Self {
+            tag,
+            field_type: DOUBLE,
+            count: values.len() as u32,
+            data: values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect(),
+        }
+    }
+
+    fn ascii(tag: u16, text: &str) -> Self {
+        let mut data = text.as_bytes().to_vec();
+        data.push(0);
+
+        Self {
+            tag,
+            field_type: ASCII,
+            count: data.len() as u32,
+            data,
+        }
+    }
+}
+
+// Encodes bands of values, row-major from the north west corner, as a
+// GeoTIFF covering the bounds. NaN values are written as nodata, and the
+// band names are stored as GDAL band descriptions.
+pub fn encode_geotiff(
+    bands: &[(String, Vec<f32>)],
+    width: usize,
+    height: usize,
+    bounds: &Bounds,
+    nodata: f32,
+) -> Result<Vec<u8>, String> {
+    if bands.is_empty() {
+        return Err("A GeoTIFF needs at least one band".to_string());
+    }
+
+    if let Some((name, _)) = bands
+        .iter()
+        .find(|(_, values)| values.len() != width * height)
+    {
+        return Err(format!(
+            "Band {} does not have {} by {} values",
+            name, width, height
+        ));
+    }
+
+    let samples = bands.len();
+    let strip_size = width * height * samples * 4;
+    if strip_size > u32::MAX as usize / 2 {
+        return Err("The GeoTIFF is too large".to_string());
+    }
+
+    let mut strip = Vec::with_capacity(strip_size);
+    for pixel in 0..width * height {
+        for (_, values) in bands {
+            let value = values[pixel];
+            let value = if value.is_nan() { nodata } else { value };
+            strip.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    let pixel_width = (bounds.east - bounds.west) as f64 / width as f64;
+    let pixel_height = (bounds.north - bounds.south) as f64 / height as f64;
+
+    let mut geo_keys = Vec::from(GEO_KEY_HEADER);
+    geo_keys.push(3);
+    geo_keys.extend_from_slice(&MODEL_TYPE);
+    geo_keys.extend_from_slice(&RASTER_TYPE);
+    geo_keys.extend_from_slice(&GEOGRAPHIC_TYPE);
+
+    let mut metadata = String::from("<GDALMetadata>");
+    for (sample, (name, _)) in bands.iter().enumerate() {
+        metadata.push_str(&format!(
+            "<Item name=\"DESCRIPTION\" sample=\"{}\" role=\"description\">{}</Item>",
+            sample,
+            escape_xml(name)
+        ));
+    }
+    metadata.push_str("</GDALMetadata>");
+
+    // The strip follows the header and the directory follows the strip
+    let strip_offset = 8;
+    let mut entries = vec![
+        // ImageWidth and ImageLength
+        Entry::long(256, width as u32),
+        Entry::long(257, height as u32),
+        // BitsPerSample
+        Entry::shorts(258, &vec![32; samples]),
+        // Compression none
+        Entry::shorts(259, &[1]),
+        // PhotometricInterpretation BlackIsZero
+        Entry::shorts(262, &[1]),
+        // StripOffsets
+        Entry::long(273, strip_offset),
+        // SamplesPerPixel
+        Entry::shorts(277, &[samples as u16]),
+        // RowsPerStrip and StripByteCounts
+        Entry::long(278, height as u32),
+        Entry::long(279, strip_size as u32),
+        // PlanarConfiguration chunky
+        Entry::shorts(284, &[1]),
+    ];
+    if samples > 1 {
+        // ExtraSamples unspecified
+        entries.push(Entry::shorts(338, &vec![0; samples - 1]));
+    }
+    entries.extend([
+        // SampleFormat IEEE floating point
+        Entry::shorts(339, &vec![3; samples]),
+        // ModelPixelScale
+        Entry::doubles(33550, &[pixel_width, pixel_height, 0.0]),
+        // ModelTiepoint from the north west corner of the raster
+        Entry::doubles(
+            33922,
+            &[0.0, 0.0, 0.0, bounds.west as f64, bounds.north as f64, 0.0],
+        ),
+        // GeoKeyDirectory
+        Entry::shorts(34735, &geo_keys),
+        // GDAL_METADATA and GDAL_NODATA
+        Entry::ascii(42112, &metadata),
+        Entry::ascii(42113, &nodata.to_string()),
+    ]);
+
+    let directory_offset = strip_offset as usize + strip.len();
+    let directory_size = 2 + entries.len() * 12 + 4;
+
+    let mut data = Vec::with_capacity(directory_offset + directory_size + 1024);
+    data.extend_from_slice(b"II");
+    data.extend_from_slice(&42u16.to_le_bytes());
+    data.extend_from_slice(&(directory_offset as u32).to_le_bytes());
+    data.extend_from_slice(&strip);
+
+    // Values longer than four bytes are stored after the directory, each
+    // starting on a word boundary
+    let mut overflow = Vec::new();
+    let overflow_offset = directory_offset + directory_size;
+
+    data.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    for entry in &entries {
+        data.extend_from_slice(&entry.tag.to_le_bytes());
+        data.extend_from_slice(&entry.field_type.to_le_bytes());
+        data.extend_from_slice(&entry.count.to_le_bytes());
+
+        if entry.data.len() <= 4 {
+            let mut value = [0; 4];
+            value[..entry.data.len()].copy_from_slice(&entry.data);
+            data.extend_from_slice(&value);
+        } else {
+            let offset = overflow_offset + overflow.len();
+            data.extend_from_slice(&(offset as u32).to_le_bytes());
+            overflow.extend_from_slice(&entry.data);
+            if overflow.len() & 1 == 1 {
+                overflow.push(0);
+            }
+        }
+    }
+    // No further directories
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(&overflow);
+
+    Ok(data)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,113 @@
+// Renders a sweep to an RGBA image covering a latitude and longitude
+// extent. Each pixel takes the color of the gate below its center, so
+// images can be overlaid on maps in a plate carrée projection using the
+// bounds or the world file.
+
+use crate::model::site::Site;
+use crate::model::sweep::{RadialIndex, Sweep};
+use crate::model::sweep_data::SweepData;
+use crate::render::colormap::{Colormap, RANGE_FOLDED};
+use crate::render::raster::{locate_gate, LatLonGrid};
+
+// Degrees of longitude and latitude of the edges of an image
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub west: f32,
+    pub south: f32,
+    pub east: f32,
+    pub north: f32,
+}
+
+impl Bounds {
+    // Bounds from degrees of (west, south, east, north), or None unless
+    // west is less than east and south is less than north
+    pub fn from_edges((west, south, east, north): (f32, f32, f32, f32)) -> Option<Self> {
+        (west < east && south < north).then_some(Self {
+            west,
+            south,
+            east,
+            north,
+        })
+    }
+
+    // The square extent around the radar out to a distance in km
+    pub fn around(site: &Site, distance: f32) -> Self {
+        let (north, _) = site.offset(0.0, distance);
+        let (south, _) = site.offset(0.0, -distance);
+        let (_, east) = site.offset(distance, 0.0);
+        let (_, west) = site.offset(-distance, 0.0);
+
+        Self {
+            west,
+            south,
+            east,
+            north,
+        }
+    }
+
+    // Contents of a world file for an image of this extent, which gives
+    // the pixel size and the center of the top left pixel
+    pub fn world_file(&self, width: usize, height: usize) -> String {
+        let pixel_width = (self.east - self.west) as f64 / width as f64;
+        let pixel_height = (self.north - self.south) as f64 / height as f64;
+
+        format!(
+            "{}\n0\n0\n{}\n{}\n{}\n",
+            pixel_width,
+            -pixel_height,
+            self.west as f64 + pixel_width / 2.0,
+            self.north as f64 - pixel_height / 2.0,
+        )
+    }
+}
+
+// RGBA pixels of a sweep, row-major from the top left corner
+pub(crate) fn render_sweep(
+    sweep: &Sweep,
+    data: &SweepData,
+    site: &Site,
+    grid: &LatLonGrid,
+    colormap: &Colormap,
+) -> Vec<u8> {
+    let index = RadialIndex::new(&sweep.azimuths, sweep.az_step);
+    let mut pixels = vec![0; grid.cells() * 4];
+
+    for row in 0..grid.height {
+        for col in 0..grid.width {
+            let (latitude, longitude) = grid.pixel_center(row, col);
+            let Some((radial, gate)) =
+                locate_gate(sweep, &index, data.gates, site, latitude, longitude)
+            else {
+                continue;
+            };
+
+            let color = match data.value(radial, gate) {
+                Some(value) => colormap.color(value),
+                None if data.is_range_folded(radial, gate) => RANGE_FOLDED,
+                None => continue,
+            };
+
+            let pixel = (row * grid.width + col) * 4;
+            pixels[pixel..pixel + 4].copy_from_slice(&color);
+        }
+    }
+
+    pixels
+}
+
+// Encodes RGBA pixels as a PNG image
+pub fn encode_png(pixels: &[u8], width: usize, height: usize) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+
+    let mut encoder = png::Encoder::new(&mut data, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().map_err(|error| error.to_string())?;
+    writer
+        .write_image_data(pixels)
+        .map_err(|error| error.to_string())?;
+    writer.finish().map_err(|error| error.to_string())?;
+
+    Ok(data)
+}
@@ -0,0 +1,8 @@
+// Headless rendering of sweeps to georeferenced RGBA images, GeoTIFF
+// rasters and GeoJSON contours
+
+pub mod colormap;
+pub mod contour;
+pub mod geotiff;
+pub mod image;
+pub mod raster;
@@ -0,0 +1,276 @@
+// Resampling of sweeps and volumes onto regular latitude/longitude grids,
+// the plate carrée rasters which images and GeoTIFFs are made of
+
+use crate::model::geometry::{beam_height, slant_range};
+use crate::model::grid::Grid;
+use crate::model::site::Site;
+use crate::model::sweep::{RadialIndex, Sweep};
+use crate::model::sweep_data::SweepData;
+use crate::model::sweep_type::SweepType;
+use crate::model::volume::{Volume, SAME_ELEVATION_TOLERANCE};
+use crate::render::image::Bounds;
+
+// Half of the 1° beamwidth of the WSR-88D, in radians
+const HALF_BEAMWIDTH: f32 = 0.008727;
+
+// Pixels covering a latitude and longitude extent. Pixels are stored
+// row-major with row 0 along the northern edge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatLonGrid {
+    pub bounds: Bounds,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl LatLonGrid {
+    pub fn new(bounds: Bounds, width: usize, height: usize) -> Self {
+        Self {
+            bounds,
+            width,
+            height,
+        }
+    }
+
+    pub fn cells(&self) -> usize {
+        self.width * self.height
+    }
+
+    // Latitude and longitude of the center of a pixel
+    pub fn pixel_center(&self, row: usize, col: usize) -> (f32, f32) {
+        let bounds = &self.bounds;
+        (
+            bounds.north - (row as f32 + 0.5) * (bounds.north - bounds.south) / self.height as f32,
+            bounds.west + (col as f32 + 0.5) * (bounds.east - bounds.west) / self.width as f32,
+        )
+    }
+}
+
+// Square raster with a pixel for each cell of a grid around the radar,
+// covering the extent of the grid
+pub fn covering_grid(grid: &Grid, site: &Site) -> LatLonGrid {
+    let distance = grid.size as f32 * grid.spacing / 2.0;
+    LatLonGrid::new(Bounds::around(site, distance), grid.size, grid.size)
+}
+
+// Samples the values of a grid around the radar at the center of each
+// pixel from the nearest cell, with NaN outside of the grid
+pub(crate) fn resample_grid(
+    grid: &Grid,
+    values: &[f32],
+    site: &Site,
+    raster: &LatLonGrid,
+) -> Vec<f32> {
+    let half = grid.size as f32 / 2.0;
+    let mut resampled = vec![f32::NAN; raster.cells()];
+
+    for row in 0..raster.height {
+        for col in 0..raster.width {
+            let (latitude, longitude) = raster.pixel_center(row, col);
+            let (x, y) = site.local(latitude, longitude);
+
+            let grid_row = (half - y / grid.spacing).floor();
+            let grid_col = (half + x / grid.spacing).floor();
+            if grid_row < 0.0 || grid_row >= grid.size as f32 {
+                continue;
+            }
+            if grid_col < 0.0 || grid_col >= grid.size as f32 {
+                continue;
+            }
+
+            resampled[row * raster.width + col] =
+                values[grid_row as usize * grid.size + grid_col as usize];
+        }
+    }
+
+    resampled
+}
+
+// What a band of a raster is resampled from
+#[derive(Clone, Copy, Debug)]
+pub enum Band {
+    // The sweep closest to an elevation in radians
+    Tilt { product: SweepType, elevation: f32 },
+    // Largest value of every sweep above each pixel
+    Composite { product: SweepType },
+    // Constant altitude value at a height in km above mean sea level
+    Cappi { product: SweepType, altitude: f32 },
+}
+
+impl Band {
+    // Values of each pixel, with NaN where there is no data
+    pub(crate) fn resample(&self, volume: &Volume, site: &Site, grid: &LatLonGrid) -> Vec<f32> {
+        match *self {
+            Band::Tilt { product, elevation } => match volume.closest_sweep(product, elevation) {
+                Some(sweep) => resample_sweep(sweep, sweep.product(product).unwrap(), site, grid),
+                None => vec![f32::NAN; grid.cells()],
+            },
+            Band::Composite { product } => composite(volume, product, site, grid),
+            Band::Cappi { product, altitude } => cappi(volume, product, site, grid, altitude),
+        }
+    }
+}
+
+// Radial and gate of a sweep above a latitude and longitude
+pub(crate) fn locate_gate(
+    sweep: &Sweep,
+    index: &RadialIndex,
+    gates: usize,
+    site: &Site,
+    latitude: f32,
+    longitude: f32,
+) -> Option<(usize, usize)> {
+    let (x, y) = site.local(latitude, longitude);
+    let radial = index.find(x.atan2(y))?;
+
+    let range = slant_range(x.hypot(y), sweep.elevations[radial]);
+    let gate = ((range - sweep.range_first) / sweep.range_step).round();
+    if gate < 0.0 || gate >= gates as f32 {
+        return None;
+    }
+
+    Some((radial, gate as usize))
+}
+
+// Samples a product of a sweep at the center of each pixel from the nearest
+// gate below the beam, with NaN where there is no data
+pub(crate) fn resample_sweep(
+    sweep: &Sweep,
+    data: &SweepData,
+    site: &Site,
+    grid: &LatLonGrid,
+) -> Vec<f32> {
+    let index = RadialIndex::new(&sweep.azimuths, sweep.az_step);
+    let mut values = vec![f32::NAN; grid.cells()];
+
+    for row in 0..grid.height {
+        for col in 0..grid.width {
+            let (latitude, longitude) = grid.pixel_center(row, col);
+            let Some((radial, gate)) =
+                locate_gate(sweep, &index, data.gates, site, latitude, longitude)
+            else {
+                continue;
+            };
+
+            if let Some(value) = data.value(radial, gate) {
+                values[row * grid.width + col] = value;
+            }
+        }
+    }
+
+    values
+}
+
+fn composite(volume: &Volume, product: SweepType, site: &Site, grid: &LatLonGrid) -> Vec<f32> {
+    let mut values = vec![f32::NAN; grid.cells()];
+
+    for sweep in volume
+        .sweeps
+        .iter()
+        .filter(|sweep| (sweep.sweep_type & product) != 0)
+    {
+        let Some(data) = sweep.product(product) else {
+            continue;
+        };
+
+        // The maximum ignores NaN
+        for (value, tilt) in values
+            .iter_mut()
+            .zip(resample_sweep(sweep, data, site, grid))
+        {
+            *value = value.max(tilt);
+        }
+    }
+
+    values
+}
+
+fn cappi(
+    volume: &Volume,
+    product: SweepType,
+    site: &Site,
+    grid: &LatLonGrid,
+    altitude: f32,
+) -> Vec<f32> {
+    // One sweep per elevation, skipping the supplemental low level scans
+    let mut sweeps: Vec<&Sweep> = volume
+        .sweeps
+        .iter()
+        .filter(|sweep| !sweep.is_sails && (sweep.sweep_type & product) != 0)
+        .filter(|sweep| sweep.product(product).is_some())
+        .collect();
+    sweeps.sort_by(|a, b| a.elevation.total_cmp(&b.elevation));
+    sweeps.dedup_by(|a, b| (a.elevation - b.elevation).abs() < SAME_ELEVATION_TOLERANCE);
+
+    let tilts: Vec<Vec<f32>> = sweeps
+        .iter()
+        .map(|sweep| resample_sweep(sweep, sweep.product(product).unwrap(), site, grid))
+        .collect();
+
+    let mut values = vec![f32::NAN; grid.cells()];
+    let mut levels = Vec::with_capacity(sweeps.len());
+    for row in 0..grid.height {
+        for col in 0..grid.width {
+            let index = row * grid.width + col;
+            if tilts.iter().all(|tilt| tilt[index].is_nan()) {
+                continue;
+            }
+
+            let (latitude, longitude) = grid.pixel_center(row, col);
+            let (x, y) = site.local(latitude, longitude);
+            let distance = x.hypot(y);
+
+            levels.clear();
+            levels.extend(sweeps.iter().zip(tilts.iter()).map(|(sweep, tilt)| {
+                let range = slant_range(distance, sweep.elevation);
+                Level {
+                    height: site.height + beam_height(range, sweep.elevation),
+                    depth: range * HALF_BEAMWIDTH,
+                    value: tilt[index],
+                }
+            }));
+
+            values[index] = constant_altitude(&levels, altitude);
+        }
+    }
+
+    values
+}
+
+struct Level {
+    // Height of the beam center above mean sea level and half of the beam
+    // depth, in km
+    height: f32,
+    depth: f32,
+    value: f32,
+}
+
+// Value at an altitude from levels in increasing order of elevation. Values
+// are interpolated in height between two tilts with data, and otherwise a
+// tilt covers the depth of its beam.
+fn constant_altitude(levels: &[Level], altitude: f32) -> f32 {
+    for pair in levels.windows(2) {
+        let (below, above) = (&pair[0], &pair[1]);
+        if below.value.is_nan() || above.value.is_nan() {
+            continue;
+        }
+
+        if below.height <= altitude && altitude <= above.height {
+            let weight = if above.height > below.height {
+                (altitude - below.height) / (above.height - below.height)
+            } else {
+                0.0
+            };
+            return below.value + weight * (above.value - below.value);
+        }
+    }
+
+    levels
+        .iter()
+        .filter(|level| !level.value.is_nan() && (level.height - altitude).abs() <= level.depth)
+        .min_by(|a, b| {
+            (a.height - altitude)
+                .abs()
+                .total_cmp(&(b.height - altitude).abs())
+        })
+        .map_or(f32::NAN, |level| level.value)
+}
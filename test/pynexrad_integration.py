@@ -1,20 +1,61 @@
 """
 Integration tests for the pynexrad module
 """
-from typing import List
+from typing import Dict, List, Tuple
+import asyncio
+import json
+import math
+import os
+import struct
+import tempfile
 import unittest
 from concurrent.futures import ThreadPoolExecutor
 
+import numpy as np
+
 from pynexrad import (
+    PyAzimuthalShearOptions,
+    PyBandOptions,
+    PyBiologicalFilterOptions,
     PyChunk,
+    PyClutterFilterOptions,
+    PyColormap,
+    PyContourOptions,
+    PyConvertOptions,
+    PyGeoTiffOptions,
+    PyHailOptions,
+    PyKdpOptions,
+    PyMeltingLayerOptions,
+    PyMeshAccumulator,
+    PyQpeOptions,
+    PyQvpOptions,
+    PyRainAccumulator,
+    PyRenderOptions,
+    PyRotationOptions,
+    PyStormCellOptions,
+    PyStormRelativeOptions,
+    PyStormTracker,
     PySweep,
     PyLevel2File,
+    chunks_to_archive,
     convert_chunks,
+    convert_chunks_async,
     download_chunk,
+    download_chunk_async,
     get_latest_volume,
+    get_latest_volume_async,
     list_chunks_in_volume,
+    list_chunks_in_volume_async,
     list_records,
     download_nexrad_file,
+    read_nexrad_file,
+    read_nexrad_file_async,
+    write_archive,
+    write_archive_async,
+    find_volume_at,
+    get_thread_count,
+    set_thread_count,
+    stack_qvps,
 )
 
 
@@ -386,6 +427,58 @@ class TestPynexrad(unittest.TestCase):
         self.assertIsInstance(level_2_file.reflectivity[0], PySweep)
         self.assertIsInstance(level_2_file.velocity[0], PySweep)
 
+    def test_sweep_cut_metadata(self) -> None:
+        """
+        Integration test to validate the split cut pairing and low level
+        scan selection of a volume.
+        """
+        level_2_file = download_nexrad_file("KDMX20220305_233003_V06")
+        self.assertGreater(level_2_file.vcp, 0)
+
+        cuts = {}
+        for sweep in level_2_file.reflectivity + level_2_file.velocity:
+            cuts[sweep.cut_index] = sweep
+
+        for sweep in cuts.values():
+            if sweep.paired_cut is None:
+                continue
+
+            paired = cuts[sweep.paired_cut]
+            self.assertEqual(paired.paired_cut, sweep.cut_index)
+            self.assertAlmostEqual(sweep.elevation, paired.elevation, places=3)
+            self.assertEqual(
+                {sweep.waveform, paired.waveform} - {"CDW", "CDWO"},
+                {"CS"},
+            )
+
+        lowest = min(sweep.elevation for sweep in level_2_file.reflectivity)
+        latest = level_2_file.latest_low_level("reflectivity")
+        self.assertIsNotNone(latest)
+        self.assertAlmostEqual(latest.elevation, lowest, places=3)
+
+        for sweep in level_2_file.reflectivity:
+            if abs(sweep.elevation - lowest) < 0.001:
+                self.assertLessEqual(sweep.start_time, latest.start_time)
+
+    def test_find_volume_at(self) -> None:
+        """
+        Integration test to validate finding the volume closest to a time
+        """
+        # 2022-03-05 23:31:00 UTC
+        result = find_volume_at("KDMX", 1646523060, 600)
+        self.assertEqual(result, ("KDMX20220305_233003_V06", 1646523003))
+
+        # 2022-03-06 00:00:00 UTC, the closest scan is on the previous day
+        result = find_volume_at("KDMX", 1646524800, 200)
+        self.assertEqual(result, ("KDMX20220305_235739_V06", 1646524659))
+
+        self.assertIsNone(find_volume_at("KDMX", 1646523060, 0))
+
+        with self.assertRaises(ValueError):
+            find_volume_at("KDMX", 1646523060, 10 * 24 * 60 * 60)
+        with self.assertRaises(ValueError):
+            find_volume_at("KDMX", 2**62, 600)
+
     def test_get_realtime_chunks(self) -> None:
         """
         Integration test to validate find the latest realtime data
@@ -431,6 +524,885 @@ class TestPynexrad(unittest.TestCase):
 
         assert_l2files_equal(self, volume, archive_volume)
 
+    def test_get_realtime_volume_async(self) -> None:
+        """
+        Integration test to validate that chunks downloaded concurrently
+        from an event loop convert to the same volume as the blocking API.
+        """
+        async def fetch_volume() -> tuple[List[PyChunk], PyLevel2File]:
+            latest_volume = await get_latest_volume_async("KDMX")
+            previous_volume = latest_volume - 1
+            if previous_volume <= 0:
+                previous_volume = 99
+
+            chunk_ids = await list_chunks_in_volume_async(
+                "KDMX",
+                previous_volume,
+            )
+            chunks = await asyncio.gather(
+                *[download_chunk_async(chunk_id) for chunk_id in chunk_ids]
+            )
+
+            return list(chunks), await convert_chunks_async(list(chunks))
+
+        chunks, volume = asyncio.run(fetch_volume())
+        self.assertGreater(len(chunks), 0)
+
+        assert_l2files_equal(self, volume, convert_chunks(chunks))
+
+    def test_write_archive(self) -> None:
+        """
+        Integration test to validate that the chunks of a realtime volume
+        can be saved as an archive file which decodes to the same volume.
+        """
+        latest_volume = get_latest_volume("KDMX")
+        previous_volume = latest_volume - 1
+        if previous_volume <= 0:
+            previous_volume = 99
+
+        chunks_in_volume = list_chunks_in_volume("KDMX", previous_volume)
+        with ThreadPoolExecutor() as executor:
+            chunk_data = list(executor.map(download_chunk, chunks_in_volume))
+
+        # The archive is assembled in sequence order regardless of input order
+        archive = chunks_to_archive(list(reversed(chunk_data)))
+        self.assertEqual(archive[:4], b"AR2V")
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "archive_V06")
+            write_archive(chunk_data, path)
+
+            with open(path, "rb") as f:
+                self.assertEqual(f.read(), archive)
+
+            assert_l2files_equal(
+                self,
+                read_nexrad_file(path),
+                convert_chunks(chunk_data),
+            )
+
+            # The awaitable versions must be called with a running event loop
+            async_path = os.path.join(directory, "async_archive_V06")
+
+            async def write_archive_later(chunks: List[PyChunk]) -> None:
+                await write_archive_async(chunks, async_path)
+
+            async def read_archive_later(archive_path: str) -> PyLevel2File:
+                return await read_nexrad_file_async(archive_path)
+
+            asyncio.run(write_archive_later(chunk_data))
+            with open(async_path, "rb") as f:
+                self.assertEqual(f.read(), archive)
+
+            assert_l2files_equal(
+                self,
+                asyncio.run(read_archive_later(async_path)),
+                read_nexrad_file(path),
+            )
+
+            with self.assertRaises(ValueError):
+                asyncio.run(write_archive_later(chunk_data[1:]))
+            with self.assertRaises(OSError):
+                asyncio.run(read_archive_later(os.path.join(directory, "missing_V06")))
+
+        with self.assertRaises(ValueError):
+            chunks_to_archive(chunk_data[1:])
+
+        # The volume may be missing its last chunks but not chunks in between
+        with self.assertRaises(ValueError):
+            chunks_to_archive(chunk_data[:2] + chunk_data[3:])
+
+    def test_partial_volume(self) -> None:
+        """
+        Integration test to validate that a volume missing its last chunks
+        keeps the incomplete sweep, either regridded or as received.
+        """
+        latest_volume = get_latest_volume("KDMX")
+        previous_volume = latest_volume - 1
+        if previous_volume <= 0:
+            previous_volume = 99
+
+        chunks_in_volume = list_chunks_in_volume("KDMX", previous_volume)
+        with ThreadPoolExecutor() as executor:
+            chunk_data = list(executor.map(download_chunk, chunks_in_volume))
+
+        # Drop the end of the volume to cut the last sweep short
+        partial_chunks = chunk_data[:len(chunk_data) // 2]
+
+        regridded = convert_chunks(partial_chunks)
+        for sweep in regridded.reflectivity:
+            self.assertEqual(sweep.az_count, sweep.nominal_az_count)
+            self.assertEqual(len(sweep.azimuths), sweep.az_count)
+
+        as_received = convert_chunks(
+            partial_chunks,
+            PyConvertOptions(regrid_partial_sweeps=False),
+        )
+        self.assertEqual(
+            len(as_received.reflectivity),
+            len(regridded.reflectivity),
+        )
+
+        incomplete = [
+            sweep for sweep in as_received.reflectivity + as_received.velocity
+            if sweep.completeness < 1.0
+        ]
+        for sweep in incomplete:
+            self.assertLess(sweep.az_count, sweep.nominal_az_count)
+            self.assertEqual(len(sweep.azimuths), sweep.az_count)
+            self.assertEqual(sweep.azimuths, sorted(sweep.azimuths))
+
+    def test_resample_uniform(self) -> None:
+        """
+        Integration test to validate the per radial angles and resampling
+        sweeps onto an exact uniform azimuth grid.
+        """
+        level_2_file = download_nexrad_file("KDMX20220305_233003_V06")
+        resampled = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(resample_uniform=True),
+        )
+
+        for sweep in level_2_file.reflectivity:
+            self.assertEqual(len(sweep.azimuths), sweep.az_count)
+            self.assertEqual(len(sweep.elevations), sweep.az_count)
+            self.assertEqual(len(sweep.times), sweep.az_count)
+            for time in sweep.times:
+                self.assertGreaterEqual(time // 1000, sweep.start_time)
+                self.assertLessEqual(time // 1000, sweep.end_time)
+
+        for sweep in resampled.reflectivity:
+            self.assertEqual(sweep.az_count, sweep.nominal_az_count)
+            self.assertAlmostEqual(sweep.az_first, sweep.az_step / 2, places=6)
+            for i, azimuth in enumerate(sweep.azimuths):
+                self.assertAlmostEqual(
+                    azimuth,
+                    (i + 0.5) * sweep.az_step,
+                    places=4,
+                )
+
+    def test_range_folded_mask(self) -> None:
+        """
+        Integration test to validate that the gate status mask lines up with
+        the data and that only valid gates have values.
+        """
+        level_2_file = download_nexrad_file("KDMX20220305_233003_V06")
+
+        for sweep in level_2_file.reflectivity + level_2_file.velocity:
+            self.assertIsInstance(sweep.data, list)
+            self.assertIsInstance(sweep.mask, list)
+            self.assertEqual(len(sweep.mask), len(sweep.data))
+            for value, status in zip(sweep.data, sweep.mask):
+                self.assertIn(status, (0, 1, 2))
+                self.assertEqual(value != 0, status == 0)
+
+    def test_sweep_buffers(self) -> None:
+        """
+        Integration test to validate that the values and mask of a sweep are
+        views of the converted volume rather than copies.
+        """
+        level_2_file = download_nexrad_file("KDMX20220305_233003_V06")
+        sweep = level_2_file.reflectivity[0]
+
+        view = memoryview(sweep.values)
+        self.assertTrue(view.readonly)
+        self.assertEqual(view.format, "f")
+        self.assertEqual(view.shape, sweep.values.shape)
+        self.assertEqual(view.shape[0], sweep.az_count)
+
+        values = np.asarray(sweep.values)
+        masked = np.asarray(sweep.masked)
+        self.assertEqual(values.dtype, np.float32)
+        self.assertEqual(masked.dtype, np.bool_)
+        self.assertEqual(values.shape, masked.shape)
+
+        # Separate accesses share the same storage, so neither was copied
+        self.assertFalse(values.flags.owndata)
+        self.assertTrue(np.shares_memory(values, np.asarray(sweep.values)))
+        self.assertTrue(np.shares_memory(masked, np.asarray(sweep.masked)))
+
+        self.assertEqual(
+            int((~masked).sum()),
+            sum(1 for status in sweep.mask if status == 0),
+        )
+
+        # The gate ranges of the buffer locate the trimmed data within it
+        self.assertEqual(sweep.values.range_step, sweep.range_step)
+        offset = round((sweep.range_first - sweep.values.range_first) / sweep.range_step)
+        for column, status in enumerate(sweep.mask[: sweep.range_count]):
+            if status == 0:
+                self.assertFalse(masked[0, offset + column])
+
+        # The views keep the volume alive
+        del level_2_file, sweep
+        self.assertTrue(np.isfinite(values[~masked]).all())
+
+    def test_clutter_filter(self) -> None:
+        """
+        Integration test to validate that the clutter filter only removes
+        reflectivity and velocity gates, masking velocity where it masks
+        reflectivity, and reports a score for each filtered sweep.
+        """
+        unfiltered = download_nexrad_file("KDMX20220305_233003_V06")
+        filtered = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(clutter_filter=PyClutterFilterOptions()),
+        )
+
+        self.assertEqual(len(unfiltered.clutter), 0)
+        self.assertEqual(len(filtered.clutter), len(filtered.reflectivity))
+        self.assertEqual(len(filtered.reflectivity), len(unfiltered.reflectivity))
+
+        for before, after in zip(unfiltered.reflectivity, filtered.reflectivity):
+            self.assertLessEqual(
+                sum(1 for value in after.data if value != 0),
+                sum(1 for value in before.data if value != 0),
+            )
+
+        self.assertEqual(len(filtered.velocity), len(unfiltered.velocity))
+        velocity_before = 0
+        velocity_after = 0
+        for before, after in zip(unfiltered.velocity, filtered.velocity):
+            before_count = int((~np.asarray(before.masked)).sum())
+            after_count = int((~np.asarray(after.masked)).sum())
+            self.assertLessEqual(after_count, before_count)
+            velocity_before += before_count
+            velocity_after += after_count
+
+        # Ground clutter near the radar has near zero velocity
+        self.assertLess(velocity_after, velocity_before)
+
+    def test_biological_filter(self) -> None:
+        """
+        Integration test to validate that the biological filter masks gates
+        on every moment and never unmasks any.
+        """
+        unfiltered = download_nexrad_file("KDMX20220305_233003_V06")
+        filtered = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(biological_filter=PyBiologicalFilterOptions()),
+        )
+
+        for moment in [
+            "reflectivity",
+            "velocity",
+            "correlation_coefficient",
+            "differential_reflectivity",
+            "differential_phase",
+        ]:
+            before_sweeps = getattr(unfiltered, moment)
+            after_sweeps = getattr(filtered, moment)
+            self.assertEqual(len(after_sweeps), len(before_sweeps), moment)
+
+            masked = 0
+            for before, after in zip(before_sweeps, after_sweeps):
+                before_mask = np.asarray(before.masked)
+                after_mask = np.asarray(after.masked)
+                self.assertEqual(before_mask.shape, after_mask.shape)
+                self.assertFalse((before_mask & ~after_mask).any(), moment)
+                masked += int((after_mask & ~before_mask).sum())
+
+            self.assertGreater(masked, 0, moment)
+
+    def test_hydrometeor_class(self) -> None:
+        """
+        Integration test to validate that every gate with reflectivity on a
+        dual pol sweep is given a known class code.
+        """
+        level_2_file = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(
+                classify_hydrometeors=True,
+                melting_layer=(1.0, 1.5),
+                images=[PyRenderOptions(product="hydrometeor_class", width=64, height=64)],
+            ),
+        )
+
+        self.assertGreater(len(level_2_file.hydrometeor_class), 0)
+        for sweep in level_2_file.hydrometeor_class:
+            self.assertEqual(len(sweep.data), len(sweep.mask))
+            for value in sweep.data:
+                self.assertLessEqual(value, 10)
+
+        self.assertEqual(len(level_2_file.images), 1)
+        self.assertEqual(level_2_file.images[0].product, "hydrometeor_class")
+        self.assertTrue(any(level_2_file.images[0].rgba()[3::4]))
+        self.assertEqual(PyColormap.builtin("hydrometeor_class").color(1.0), (0, 200, 0, 255))
+        self.assertEqual(PyColormap.builtin("clutter").color(1.0)[3], 255)
+
+    def test_estimate_kdp(self) -> None:
+        """
+        Integration test to validate that KDP and the filtered differential
+        phase are estimated on the same gates of each dual pol sweep.
+        """
+        level_2_file = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(estimate_kdp=PyKdpOptions()),
+        )
+
+        self.assertGreater(len(level_2_file.specific_differential_phase), 0)
+        self.assertEqual(
+            len(level_2_file.specific_differential_phase),
+            len(level_2_file.filtered_differential_phase),
+        )
+
+        for kdp, phidp in zip(
+            level_2_file.specific_differential_phase,
+            level_2_file.filtered_differential_phase,
+        ):
+            self.assertEqual(kdp.mask, phidp.mask)
+
+        # A stricter correlation threshold leaves fewer gates to fit
+        strict = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(
+                estimate_kdp=PyKdpOptions(correlation_threshold=0.99),
+            ),
+        )
+        self.assertNotEqual(
+            [sweep.data for sweep in strict.specific_differential_phase],
+            [sweep.data for sweep in level_2_file.specific_differential_phase],
+        )
+
+    def test_rain_accumulation(self) -> None:
+        """
+        Integration test to validate that the rain rates of consecutive
+        volumes accumulate into non-negative totals.
+        """
+        options = PyConvertOptions(qpe=PyQpeOptions(grid_size=100))
+        first = download_nexrad_file("KDMX20220305_232324_V06", options)
+        second = download_nexrad_file("KDMX20220305_233003_V06", options)
+
+        self.assertIsNotNone(first.rain_rate)
+        self.assertIsNotNone(first.rain_rate_grid)
+        self.assertEqual(len(first.rain_rate_grid.values), 100 * 100)
+
+        accumulator = PyRainAccumulator(grid_size=100)
+        accumulator.add(first.rain_rate_grid)
+        accumulator.add(second.rain_rate_grid)
+
+        self.assertGreater(accumulator.duration, 0)
+        for total in accumulator.totals:
+            self.assertGreaterEqual(total, 0.0)
+
+        with self.assertRaises(ValueError):
+            accumulator.add(first.rain_rate_grid)
+
+        with self.assertRaises(ValueError):
+            PyRainAccumulator(max_gap=2**62)
+
+        assert_grid_geotiff(self, first.rain_rate_grid.geotiff(), 100, 1)
+        assert_grid_geotiff(self, accumulator.geotiff(nodata=-1.0), 100, 1)
+        with self.assertRaises(ValueError):
+            PyRainAccumulator(grid_size=100).geotiff()
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "rainfall.tif")
+            accumulator.save(path)
+            with open(path, "rb") as file:
+                self.assertEqual(file.read(), accumulator.geotiff())
+
+    def test_detect_melting_layer(self) -> None:
+        """
+        Integration test to validate that a detected melting layer is a
+        plausible layer with a confidence between 0 and 1.
+        """
+        level_2_file = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(detect_melting_layer=PyMeltingLayerOptions()),
+        )
+
+        melting_layer = level_2_file.melting_layer
+        if melting_layer is None:
+            return
+
+        self.assertLess(melting_layer.bottom, melting_layer.top)
+        self.assertGreaterEqual(melting_layer.confidence, 0.0)
+        self.assertLessEqual(melting_layer.confidence, 1.0)
+
+    def test_stack_qvps(self) -> None:
+        """
+        Integration test to validate that the profiles of consecutive
+        volumes stack into a time-height array in time order.
+        """
+        options = PyConvertOptions(
+            qvps=[
+                PyQvpOptions("reflectivity", statistic="median"),
+                PyQvpOptions("correlation_coefficient", statistic="mean"),
+            ]
+        )
+
+        names = ["KDMX20220305_233003_V06", "KDMX20220305_232324_V06"]
+        files = [download_nexrad_file(name, options) for name in names]
+
+        for level_2_file in files:
+            self.assertEqual(len(level_2_file.qvps), 2)
+            for qvp in level_2_file.qvps:
+                self.assertEqual(len(qvp.heights), len(qvp.values))
+                self.assertAlmostEqual(qvp.elevation, math.radians(10.0), delta=0.05)
+
+        reflectivity = [level_2_file.qvps[0] for level_2_file in files]
+        time_height = stack_qvps(reflectivity)
+
+        self.assertEqual(time_height.times, sorted(qvp.time for qvp in reflectivity))
+        self.assertEqual(
+            len(time_height.values), len(time_height.times) * len(time_height.heights)
+        )
+
+        with self.assertRaises(ValueError):
+            stack_qvps(files[0].qvps)
+
+        qvp_options = PyQvpOptions()
+        with self.assertRaises(ValueError):
+            qvp_options.bin_size = 0.0
+        with self.assertRaises(ValueError):
+            qvp_options.max_height = 1e9
+        with self.assertRaises(ValueError):
+            PyQvpOptions(max_height=-1.0)
+        self.assertEqual(qvp_options.bin_size, PyQvpOptions().bin_size)
+
+    def test_track_storm_cells(self) -> None:
+        """
+        Integration test to validate that storm cells are tracked across
+        consecutive volumes with motion and forecasts.
+        """
+        options = PyConvertOptions(storm_cells=PyStormCellOptions())
+        names = ["KDMX20220305_232324_V06", "KDMX20220305_233003_V06"]
+        volumes = [download_nexrad_file(name, options).storm_cells for name in names]
+
+        tracker = PyStormTracker(forecast_minutes=[15, 30])
+        first = tracker.update(volumes[0])
+        second = tracker.update(volumes[1])
+
+        for tracked in first:
+            self.assertIsNone(tracked.motion)
+            self.assertLessEqual(tracked.cell.base, tracked.cell.top)
+            self.assertGreaterEqual(tracked.cell.max_reflectivity, 30.0)
+
+        first_ids = {tracked.id for tracked in first}
+        for tracked in second:
+            if tracked.id in first_ids:
+                self.assertIsNotNone(tracked.motion)
+                self.assertEqual(len(tracked.forecast), 2)
+                self.assertEqual(len(tracked.track), 1)
+
+        with self.assertRaises(ValueError):
+            tracker.update(volumes[0])
+
+        with self.assertRaises(ValueError):
+            PyStormTracker(max_gap=2**62)
+        with self.assertRaises(ValueError):
+            PyStormTracker(forecast_minutes=[15, 2**60])
+
+    def test_detect_rotation(self) -> None:
+        """
+        Integration test to validate that detected rotations span several
+        tilts and that their strength rank follows the rotational velocity.
+        """
+        level_2_file = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(rotation=PyRotationOptions()),
+        )
+
+        for rotation in level_2_file.rotations:
+            self.assertGreaterEqual(rotation.features, 2)
+            self.assertGreater(rotation.depth, 0.0)
+            self.assertGreaterEqual(rotation.rotational_velocity, 5.0)
+            self.assertGreaterEqual(
+                rotation.rotational_velocity, rotation.low_level_rotational_velocity
+            )
+            self.assertEqual(rotation.strength_rank, int(rotation.rotational_velocity // 5))
+
+    def test_azimuthal_shear(self) -> None:
+        """
+        Integration test to validate that azimuthal shear and divergence are
+        estimated for every velocity sweep and gridded for both layers.
+        """
+        level_2_file = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(azimuthal_shear=PyAzimuthalShearOptions(grid_size=100)),
+        )
+
+        self.assertEqual(len(level_2_file.azimuthal_shear), len(level_2_file.velocity))
+        self.assertEqual(len(level_2_file.divergence), len(level_2_file.velocity))
+
+        layers = level_2_file.shear_layers
+        self.assertIsNotNone(layers)
+        for grid in [
+            layers.low_level_shear,
+            layers.mid_level_shear,
+            layers.low_level_divergence,
+            layers.mid_level_divergence,
+        ]:
+            self.assertEqual(len(grid), 100 * 100)
+
+        values = [value for value in layers.low_level_shear if not math.isnan(value)]
+        self.assertGreater(len(values), 0)
+
+        assert_grid_geotiff(self, layers.geotiff(), 100, 4)
+
+    def test_storm_relative_velocity(self) -> None:
+        """
+        Integration test to validate that storm-relative velocity keeps the
+        geometry of the velocity sweeps and removes the given motion.
+        """
+        level_2_file = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(storm_relative=PyStormRelativeOptions(direction=270.0, speed=15.0)),
+        )
+
+        self.assertEqual(
+            len(level_2_file.storm_relative_velocity), len(level_2_file.velocity)
+        )
+        for relative, velocity in zip(
+            level_2_file.storm_relative_velocity, level_2_file.velocity
+        ):
+            self.assertEqual(relative.az_count, velocity.az_count)
+            self.assertEqual(relative.range_count, velocity.range_count)
+            self.assertEqual(relative.mask, velocity.mask)
+
+        # A storm moving from the west moves towards the east
+        u, v = level_2_file.storm_motion
+        self.assertAlmostEqual(u, 15.0, places=3)
+        self.assertAlmostEqual(v, 0.0, places=3)
+
+        with self.assertRaises(ValueError):
+            PyStormRelativeOptions(direction=270.0)
+
+        estimated = download_nexrad_file(
+            "KDMX20220305_233003_V06",
+            PyConvertOptions(storm_relative=PyStormRelativeOptions()),
+        )
+        if estimated.storm_motion is not None:
+            self.assertGreater(len(estimated.storm_relative_velocity), 0)
+
+    def test_hail_detection(self) -> None:
+        """
+        Integration test to validate the hail products of the storm cells
+        and grid, and that MESH swaths keep the largest size of each cell.
+        """
+        options = PyConvertOptions(
+            storm_cells=PyStormCellOptions(),
+            hail=PyHailOptions(freezing_level=3.0, minus_20_level=6.0, grid_size=100),
+        )
+        names = ["KDMX20220305_232324_V06", "KDMX20220305_233003_V06"]
+        files = [download_nexrad_file(name, options) for name in names]
+
+        for level_2_file in files:
+            for cell in level_2_file.storm_cells.cells:
+                self.assertIsNotNone(cell.hail)
+                self.assertGreaterEqual(cell.hail.mesh, 0.0)
+                self.assertTrue(0.0 <= cell.hail.poh <= 100.0)
+                self.assertTrue(0.0 <= cell.hail.posh <= 100.0)
+
+        accumulator = PyMeshAccumulator(grid_size=100)
+        for level_2_file in files:
+            accumulator.add(level_2_file.hail_grid)
+
+        for index, maximum in enumerate(accumulator.maximum):
+            for level_2_file in files:
+                mesh = level_2_file.hail_grid.mesh[index]
+                if not math.isnan(mesh):
+                    self.assertGreaterEqual(maximum, mesh)
+
+        with self.assertRaises(ValueError):
+            accumulator.add(files[0].hail_grid)
+
+        assert_grid_geotiff(self, files[0].hail_grid.geotiff(), 100, 4)
+        assert_grid_geotiff(self, accumulator.geotiff(), 100, 1)
+
+        with self.assertRaises(ValueError):
+            PyHailOptions(freezing_level=6.0, minus_20_level=3.0)
+        with self.assertRaises(ValueError):
+            PyHailOptions(freezing_level=3.0, minus_20_level=3.0)
+
+        # Setting the fields applies the same checks
+        hail_options = PyHailOptions(freezing_level=3.0, minus_20_level=6.0)
+        with self.assertRaises(ValueError):
+            hail_options.freezing_level = 7.0
+        with self.assertRaises(ValueError):
+            hail_options.minus_20_level = 2.0
+        with self.assertRaises(ValueError):
+            hail_options.grid_size = 0
+        with self.assertRaises(ValueError):
+            hail_options.grid_size = 10**9
+        with self.assertRaises(ValueError):
+            hail_options.grid_spacing = 0.0
+        self.assertEqual(
+            (hail_options.freezing_level, hail_options.minus_20_level, hail_options.grid_size),
+            (3.0, 6.0, 460),
+        )
+        with self.assertRaises(ValueError):
+            PyMeshAccumulator(grid_size=10**9)
+        with self.assertRaises(ValueError):
+            PyMeshAccumulator(grid_spacing=-1.0)
+
+    def test_render_images(self) -> None:
+        """
+        Integration test to validate that sweeps render to PNG images with
+        world files matching their bounds.
+        """
+        colormap = PyColormap([(0.0, (0, 0, 0, 255)), (30.0, (255, 0, 0, 255))])
+        self.assertEqual(colormap.color(-10.0)[3], 0)
+        self.assertEqual(colormap.color(40.0), (255, 0, 0, 255))
+
+        options = PyConvertOptions(images=[
+            PyRenderOptions(width=200, height=100),
+            PyRenderOptions(product="velocity", width=64, height=64, colormap=colormap),
+        ])
+        level_2_file = download_nexrad_file("KDMX20220305_233003_V06", options)
+        self.assertEqual(len(level_2_file.images), 2)
+
+        image = level_2_file.images[0]
+        self.assertEqual(len(image.rgba()), 200 * 100 * 4)
+        self.assertTrue(any(image.rgba()[3::4]))
+        self.assertEqual(image.png()[:8], b"\x89PNG\r\n\x1a\n")
+
+        west, south, east, north = image.bounds
+        lines = [float(line) for line in image.world_file.split()]
+        self.assertAlmostEqual(lines[0], (east - west) / 200, places=6)
+        self.assertAlmostEqual(lines[3], (south - north) / 100, places=6)
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "reflectivity.png")
+            image.save(path)
+            with open(path, "rb") as file:
+                self.assertEqual(file.read(), image.png())
+            self.assertTrue(os.path.exists(os.path.join(directory, "reflectivity.pgw")))
+
+        # Any sweep can be rendered, not just the one closest to an elevation
+        sweep = level_2_file.reflectivity[-1]
+        sweep_image = sweep.render(PyRenderOptions(product="velocity", width=50, height=40))
+        self.assertEqual(sweep_image.product, "reflectivity")
+        self.assertEqual(sweep_image.elevation, sweep.elevation)
+        self.assertEqual(sweep_image.start_time, sweep.start_time)
+        self.assertEqual(len(sweep_image.rgba()), 50 * 40 * 4)
+
+        with self.assertRaises(ValueError):
+            PyRenderOptions(bounds=(-90.0, 40.0, -95.0, 45.0))
+        render_options = PyRenderOptions(bounds=(-95.0, 40.0, -90.0, 45.0))
+        with self.assertRaises(ValueError):
+            render_options.bounds = (-95.0, 45.0, -90.0, 40.0)
+        self.assertEqual(render_options.bounds, (-95.0, 40.0, -90.0, 45.0))
+
+        with self.assertRaises(ValueError):
+            render_options.width = 0
+        with self.assertRaises(ValueError):
+            render_options.height = 10**9
+        with self.assertRaises(ValueError):
+            PyRenderOptions(width=0)
+        self.assertEqual((render_options.width, render_options.height), (1024, 1024))
+
+    def test_geotiff_export(self) -> None:
+        """
+        Integration test to validate the georeferencing and nodata of
+        multi-band GeoTIFF rasters, and that composite reflectivity is at
+        least the reflectivity of the lowest tilt.
+        """
+        bands = [
+            PyBandOptions(),
+            PyBandOptions(method="composite"),
+            PyBandOptions(method="cappi", altitude=3.0),
+        ]
+        options = PyConvertOptions(rasters=[
+            PyGeoTiffOptions(bands, width=120, height=100, nodata=-999.0),
+        ])
+        with self.assertRaises(ValueError):
+            PyGeoTiffOptions(bands, bounds=(-95.0, 45.0, -90.0, 40.0))
+        raster_options = PyGeoTiffOptions(bands)
+        with self.assertRaises(ValueError):
+            raster_options.bounds = (-90.0, 40.0, -95.0, 45.0)
+        self.assertIsNone(raster_options.bounds)
+        with self.assertRaises(ValueError):
+            raster_options.width = 0
+        with self.assertRaises(ValueError):
+            raster_options.height = 0
+        with self.assertRaises(ValueError):
+            raster_options.bands = []
+        self.assertEqual((raster_options.width, len(raster_options.bands)), (1024, 3))
+
+        level_2_file = download_nexrad_file("KDMX20220305_233003_V06", options)
+        raster = level_2_file.rasters[0]
+        self.assertEqual(len(raster.band_names), 3)
+
+        tilt, composite, cappi = raster.values
+        self.assertTrue(any(not math.isnan(value) for value in cappi))
+        for low, high in zip(tilt, composite):
+            if not math.isnan(low):
+                self.assertGreaterEqual(high, low)
+
+        data = raster.geotiff()
+        tags = geotiff_tags(self, data)
+
+        self.assertEqual(tags[277][2], 3)
+        self.assertEqual(tags[339][0], 3)
+
+        west, south, east, north = raster.bounds
+        _, _, scale_offset = tags[33550]
+        scale = struct.unpack_from("<3d", data, scale_offset)
+        self.assertAlmostEqual(scale[0], (east - west) / 120, places=5)
+        self.assertAlmostEqual(scale[1], (north - south) / 100, places=5)
+
+        _, _, tiepoint_offset = tags[33922]
+        tiepoint = struct.unpack_from("<6d", data, tiepoint_offset)
+        self.assertAlmostEqual(tiepoint[3], west, places=5)
+        self.assertAlmostEqual(tiepoint[4], north, places=5)
+
+        _, keys_count, keys_offset = tags[34735]
+        keys = struct.unpack_from("<%dH" % keys_count, data, keys_offset)
+        self.assertIn(4326, keys)
+
+        _, nodata_count, nodata_offset = tags[42113]
+        nodata = data[nodata_offset:nodata_offset + nodata_count - 1]
+        self.assertEqual(float(nodata), -999.0)
+
+        strip_offset = tags[273][2]
+        first = struct.unpack_from("<3f", data, strip_offset)
+        for value, band in zip(first, raster.values):
+            if math.isnan(band[0]):
+                self.assertEqual(value, -999.0)
+            else:
+                self.assertAlmostEqual(value, band[0], places=4)
+
+        raster_contours = raster.contours(PyContourOptions([20.0]), band=1)
+        self.assertEqual(raster_contours.product, raster.band_names[1])
+        self.assertEqual(
+            (raster_contours.start_time, raster_contours.end_time),
+            (raster.start_time, raster.end_time),
+        )
+        with self.assertRaises(ValueError):
+            raster.contours(PyContourOptions([20.0]), band=3)
+
+    def test_geojson_contours(self) -> None:
+        """
+        Integration test to validate that contours of tilts, composites and
+        product grids are closed polygons near the radar, with and without
+        simplification, whose exteriors are counterclockwise and holes
+        clockwise as in RFC 7946.
+        """
+        options = PyConvertOptions(
+            contours=[
+                PyContourOptions([20.0, 40.0]),
+                PyContourOptions([-10.0], band=PyBandOptions(product="velocity"), below=True, simplify=1.0),
+                PyContourOptions([30.0], band=PyBandOptions(method="composite"), resolution=2.0),
+            ],
+            qpe=PyQpeOptions(grid_size=100),
+            azimuthal_shear=PyAzimuthalShearOptions(grid_size=100),
+            hail=PyHailOptions(grid_size=100),
+        )
+        level_2_file = download_nexrad_file("KDMX20220305_233003_V06", options)
+        self.assertEqual(len(level_2_file.contours), 3)
+
+        grid_options = PyContourOptions([1.0, 10.0])
+        grid_contours = [
+            level_2_file.rain_rate_grid.contours(grid_options),
+            level_2_file.hail_grid.contours(grid_options),
+            level_2_file.hail_grid.contours(PyContourOptions([50.0]), product="poh"),
+            level_2_file.shear_layers.contours(PyContourOptions([0.002]), layer="mid_level_shear"),
+        ]
+        self.assertEqual(
+            [contours.product for contours in grid_contours],
+            ["rain_rate", "mesh", "poh", "mid_level_shear"],
+        )
+        with self.assertRaises(ValueError):
+            level_2_file.hail_grid.contours(grid_options, product="hail")
+
+        with self.assertRaises(ValueError):
+            grid_options.resolution = 0.0
+        with self.assertRaises(ValueError):
+            grid_options.resolution = 1e-6
+        with self.assertRaises(ValueError):
+            grid_options.distance = -1.0
+        with self.assertRaises(ValueError):
+            PyContourOptions([1.0], distance=1e9)
+        self.assertEqual((grid_options.distance, grid_options.resolution), (230.0, 1.0))
+
+        # Twice the signed area of a closed ring, positive when it is
+        # counterclockwise
+        def signed_area(ring: List[List[float]]) -> float:
+            return sum(x1 * y2 - x2 * y1 for (x1, y1), (x2, y2) in zip(ring, ring[1:]))
+
+        collections = [json.loads(contours.geojson) for contours in level_2_file.contours + grid_contours]
+        for collection in collections:
+            self.assertEqual(collection["type"], "FeatureCollection")
+            for feature in collection["features"]:
+                self.assertEqual(feature["geometry"]["type"], "MultiPolygon")
+                for exterior, *holes in feature["geometry"]["coordinates"]:
+                    self.assertGreater(signed_area(exterior), 0.0)
+                    for hole in holes:
+                        self.assertLess(signed_area(hole), 0.0)
+
+                    for ring in [exterior, *holes]:
+                        self.assertGreaterEqual(len(ring), 4)
+                        self.assertEqual(ring[0], ring[-1])
+                        for longitude, latitude in ring:
+                            self.assertTrue(-97.0 < longitude < -90.0)
+                            self.assertTrue(39.0 < latitude < 44.0)
+
+        weak, strong = collections[0]["features"]
+        self.assertEqual(weak["properties"]["threshold"], 20.0)
+        self.assertEqual(strong["properties"]["threshold"], 40.0)
+        self.assertGreater(len(weak["geometry"]["coordinates"]), 0)
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "reflectivity.geojson")
+            level_2_file.contours[0].save(path)
+            with open(path) as file:
+                self.assertEqual(json.load(file), collections[0])
+
+    def test_thread_count(self) -> None:
+        """
+        Integration test to validate that processing sweeps in parallel
+        gives the same result as processing them on a single thread.
+        """
+        try:
+            set_thread_count(1)
+            self.assertEqual(get_thread_count(), 1)
+            serial = download_nexrad_file("KDMX20220305_233003_V06")
+
+            set_thread_count(4)
+            self.assertEqual(get_thread_count(), 4)
+            parallel = download_nexrad_file("KDMX20220305_233003_V06")
+        finally:
+            set_thread_count(0)
+
+        assert_l2files_equal(self, serial, parallel)
+
+
+def geotiff_tags(
+    t: unittest.TestCase,
+    data: bytes,
+) -> Dict[int, Tuple[int, int, int]]:
+    """
+    Reads the (type, count, value or offset) of each tag of the first
+    directory of a little endian TIFF
+    """
+    t.assertEqual(data[:4], b"II*\x00")
+    (offset,) = struct.unpack_from("<I", data, 4)
+    (count,) = struct.unpack_from("<H", data, offset)
+    tags = {}
+    for entry in range(count):
+        tag, field_type, values, value = struct.unpack_from("<HHII", data, offset + 2 + entry * 12)
+        tags[tag] = (field_type, values, value)
+
+    return tags
+
+
+def assert_grid_geotiff(
+    t: unittest.TestCase,
+    data: bytes,
+    size: int,
+    bands: int,
+) -> None:
+    """
+    Asserts that a GeoTIFF of a grid has a pixel for each cell and a sample
+    for each band
+    """
+    tags = geotiff_tags(t, data)
+    t.assertEqual(tags[256][2], size)
+    t.assertEqual(tags[257][2], size)
+    t.assertEqual(tags[277][2], bands)
+
 
 def assert_l2files_equal(
     t: unittest.TestCase,